@@ -7,9 +7,20 @@ struct GenerateRequest {
     preset: Option<String>,
     format: Option<String>, // "png" or "svg"
     size_px: Option<u32>,
+    width_px: Option<u32>,
+    height_px: Option<u32>,
     padding_frac: Option<f32>,
     variant: Option<u64>,
     transparent_background: Option<bool>,
+    cvd_safe: Option<bool>,
+    monochrome: Option<String>, // single ink as "#RRGGBB"
+    grid_size: Option<u32>,
+    density: Option<f32>,
+    tile_set: Option<String>, // "arcs" or "diagonals"
+    dot_pitch: Option<f32>,
+    dot_angle: Option<f32>,
+    mask: Option<String>,     // "squircle", "superellipse", "hexagon", "shield", "octagon" or "adaptive-icon"
+    params: Option<std::collections::BTreeMap<String, f64>>, // preset parameter overrides by name
 }
 
 fn header(k: &[u8], v: &[u8]) -> tiny_http::Header {
@@ -45,6 +56,23 @@ fn main() {
     for mut request in server.incoming_requests() {
         let url = request.url().to_string();
 
+        if request.method() == &tiny_http::Method::Get && url == "/presets" {
+            let presets: Vec<serde_json::Value> = logen::Preset::all()
+                .iter()
+                .map(|p| {
+                    let params: Vec<serde_json::Value> = p
+                        .params()
+                        .iter()
+                        .map(|q| serde_json::json!({ "name": q.name, "type": q.kind.as_str(), "min": q.min, "max": q.max, "default": q.default, "description": q.description }))
+                        .collect();
+                    serde_json::json!({ "id": p.id(), "category": p.category(), "description": p.description(), "params": params })
+                })
+                .collect();
+            let body = serde_json::to_vec(&presets).unwrap_or_default();
+            let _ = request.respond(respond_with_cors(200, "application/json", body));
+            continue;
+        }
+
         if request.method() == &tiny_http::Method::Options && url == "/generate" {
             let _ = request.respond(respond_no_content());
             continue;
@@ -76,9 +104,52 @@ fn main() {
 
             let mut opts = logen::RenderOptions::default();
             if let Some(size) = payload.size_px { opts.size_px = size; }
+            opts.width_px = payload.width_px;
+            opts.height_px = payload.height_px;
             if let Some(pad) = payload.padding_frac { opts.padding_frac = pad; }
             if let Some(v) = payload.variant { opts.variant = Some(v); }
             if let Some(t) = payload.transparent_background { opts.transparent_background = t; }
+            if let Some(c) = payload.cvd_safe { opts.cvd_safe = c; }
+            // The per-preset fields are sugar for their `params` entry; an
+            // explicit entry wins.
+            opts.params = payload.params.unwrap_or_default();
+            let tile_set = match payload.tile_set.as_deref().map(str::parse::<logen::TileSet>) {
+                Some(Ok(t)) => Some(t.param_value()),
+                Some(Err(e)) => {
+                    let _ = request.respond(respond_with_cors(400, "text/plain", e.to_string().into_bytes()));
+                    continue;
+                }
+                None => None,
+            };
+            for (name, value) in [
+                ("grid_size", payload.grid_size.map(f64::from)),
+                ("density", payload.density.map(f64::from)),
+                ("tile_set", tile_set),
+                ("dot_pitch", payload.dot_pitch.map(f64::from)),
+                ("dot_angle", payload.dot_angle.map(f64::from)),
+            ] {
+                if let Some(value) = value {
+                    opts.params.entry(name.to_string()).or_insert(value);
+                }
+            }
+            if let Some(mask) = payload.mask.as_deref() {
+                match mask.parse::<logen::MaskShape>() {
+                    Ok(m) => opts.mask = Some(m),
+                    Err(e) => {
+                        let _ = request.respond(respond_with_cors(400, "text/plain", e.to_string().into_bytes()));
+                        continue;
+                    }
+                }
+            }
+            if let Some(ink) = payload.monochrome.as_deref() {
+                match logen::core::palette::Rgb::from_hex(ink) {
+                    Some(rgb) => opts.monochrome = Some(rgb),
+                    None => {
+                        let _ = request.respond(respond_with_cors(400, "text/plain", format!("invalid monochrome ink: {}", ink).into_bytes()));
+                        continue;
+                    }
+                }
+            }
 
             let format = payload.format.unwrap_or_else(|| "png".to_string());
 
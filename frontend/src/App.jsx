@@ -1,4 +1,4 @@
-import React, { useState } from 'react'
+import React, { useEffect, useState } from 'react'
 
 export default function App() {
   const [seed, setSeed] = useState('example')
@@ -10,6 +10,23 @@ export default function App() {
   const [transparent, setTransparent] = useState(false)
   const [imgUrl, setImgUrl] = useState(null)
   const [loading, setLoading] = useState(false)
+  // Presets with their parameter schemas, and overrides for the current one.
+  const [presets, setPresets] = useState([])
+  const [params, setParams] = useState({})
+
+  useEffect(() => {
+    fetch('http://127.0.0.1:3000/presets')
+      .then(res => res.json())
+      .then(setPresets)
+      .catch(() => setPresets([]))
+  }, [])
+
+  const schema = presets.find(p => p.id === preset)?.params ?? []
+
+  function choosePreset(id) {
+    setPreset(id)
+    setParams({})
+  }
 
   async function generate() {
     setLoading(true)
@@ -22,6 +39,7 @@ export default function App() {
       padding_frac: Number(padding),
       variant: variant === '' ? null : Number(variant),
       transparent_background: transparent,
+      params,
     }
 
     const res = await fetch('http://127.0.0.1:3000/generate', {
@@ -55,8 +73,11 @@ export default function App() {
           <h2>Parameters</h2>
 
           <label>Preset</label>
-          <select value={preset} onChange={e => setPreset(e.target.value)}>
-            <option value="monogram-badge">Monogram Badge</option>
+          <select value={preset} onChange={e => choosePreset(e.target.value)}>
+            {presets.length === 0 && <option value="monogram-badge">monogram-badge</option>}
+            {presets.map(p => (
+              <option key={p.id} value={p.id} title={p.description}>{p.id}</option>
+            ))}
           </select>
 
           <label>Seed</label>
@@ -77,6 +98,26 @@ export default function App() {
           <label>Variant (optional)</label>
           <input type="number" value={variant} onChange={e => setVariant(e.target.value)} placeholder="Leave empty for none" />
 
+          {schema.map(p => {
+            const value = params[p.name] ?? p.default
+            return (
+              <React.Fragment key={p.name}>
+                <label title={p.description}>{p.name}: {value}</label>
+                <input
+                  type="range"
+                  min={p.min}
+                  max={p.max}
+                  step={p.type === 'int' ? 1 : (p.max - p.min) / 100}
+                  value={value}
+                  onChange={e => setParams({ ...params, [p.name]: Number(e.target.value) })}
+                />
+              </React.Fragment>
+            )
+          })}
+          {schema.length > 0 && (
+            <button onClick={() => setParams({})}>Reset parameters</button>
+          )}
+
           <label className="checkbox">
             <input type="checkbox" checked={transparent} onChange={e => setTransparent(e.target.checked)} /> Transparent background
           </label>
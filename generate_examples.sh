@@ -19,6 +19,87 @@ cargo build --release
 ./target/release/LoGen --input "ArtLab" --preset geometric --format svg --out examples/geometric_art.svg
 ./target/release/LoGen --input "ModernBrand" --preset pattern --format png --out examples/geometric_modern.png --size 512 --transparent
 
+# Identicon examples
+./target/release/LoGen --input "octocat" --preset identicon --format png --out examples/identicon_octocat.png --size 512
+./target/release/LoGen --input "octocat" --preset identicon --format svg --out examples/identicon_octocat_7.svg --grid-size 7
+
+# Glyph grid examples
+./target/release/LoGen --input "Type Foundry" --preset glyph-grid-pattern --format png --out examples/glyph_grid_type.png --size 512
+./target/release/LoGen --input "Type Foundry" --preset glyphs --format svg --out examples/glyph_grid_sparse.svg --density 0.3 --grid-size 10
+
+# Ribbon mark examples
+./target/release/LoGen --input "Blue River" --preset ribbon-mark --format png --out examples/ribbon_blue_river.png --size 512
+./target/release/LoGen --input "Quartz Labs" --preset ribbons --format svg --out examples/ribbon_quartz.svg
+
+# Emblem examples
+./target/release/LoGen --input "Acme Power" --preset emblem --format png --out examples/emblem_acme.png --size 512
+./target/release/LoGen --input "International Business Machines" --preset seal --format svg --out examples/emblem_ibm.svg
+
+# Wordmark examples
+./target/release/LoGen --input "Acme Power" --preset wordmark --format png --out examples/wordmark_acme.png --size 512
+./target/release/LoGen --input "Northwind Traders" --preset wordmark --format svg --out examples/wordmark_northwind.svg
+./target/release/LoGen --input "Acme Power" --preset wordmark --format png --out examples/wordmark_social_card.png --width 1200 --height 630
+
+# Low-poly examples
+./target/release/LoGen --input "Summit Outdoors" --preset low-poly --format png --out examples/low_poly_summit.png --size 512
+./target/release/LoGen --input "Summit Outdoors" --preset triangles --format svg --out examples/low_poly_summit_coarse.svg --grid-size 5
+
+# Truchet examples
+./target/release/LoGen --input "Maze Works" --preset truchet --format png --out examples/truchet_maze_works.png --size 512
+./target/release/LoGen --input "Maze Works" --preset maze --format svg --out examples/truchet_maze_works_diagonals.svg --tile-set diagonals --grid-size 10
+
+# Pixel avatar examples
+./target/release/LoGen --input "Player One" --preset pixel-avatar --format png --out examples/pixel_avatar_player_one.png --size 512
+./target/release/LoGen --input "Player One" --preset sprite --format svg --out examples/pixel_avatar_player_one.svg
+
+# Sunburst examples
+./target/release/LoGen --input "Sun Co" --preset sunburst --format png --out examples/sunburst_sun_co.png --size 512
+./target/release/LoGen --input "Halo Audio" --preset rings --format svg --out examples/sunburst_halo.svg
+
+# Isometric examples
+./target/release/LoGen --input "Grand Oak" --preset isometric --format png --out examples/isometric_grand_oak.png --size 512
+./target/release/LoGen --input "Block Party" --preset blocks --format svg --out examples/isometric_block_party.svg
+
+# Blob examples
+./target/release/LoGen --input "Startup Labs" --preset blob --format png --out examples/blob_startup_labs.png --size 512
+./target/release/LoGen --input "Flow Co" --preset organic --format svg --out examples/blob_flow_co.svg
+
+# Voronoi examples
+./target/release/LoGen --input "Mosaic Lab" --preset voronoi --format png --out examples/voronoi_mosaic_lab.png --size 512
+./target/release/LoGen --input "Acme Works" --preset mosaic --format svg --out examples/voronoi_acme.svg
+
+# Negative space examples
+./target/release/LoGen --input "Harbor Supply" --preset negative-space --format png --out examples/negative_space_harbor.png --size 512
+./target/release/LoGen --input "Kite Co" --preset cutout --format svg --out examples/negative_space_kite.svg
+
+# Topographic examples
+./target/release/LoGen --input "Ridge Line" --preset topographic --format png --out examples/topographic_ridge_line.png --size 512
+./target/release/LoGen --input "Trailhead Co" --preset contours --format svg --out examples/topographic_trailhead.svg
+
+# Halftone examples
+./target/release/LoGen --input "Press Room" --preset halftone --format png --out examples/halftone_press_room.png --size 512
+./target/release/LoGen --input "Daily Print" --preset dots --format svg --out examples/halftone_daily_print.svg --dot-pitch 0.02 --dot-angle 45
+
+# Interlocking rings examples
+./target/release/LoGen --input "Blue Harbor Works" --preset interlocking-rings --format png --out examples/interlocking_rings_blue_harbor_works.png --size 512
+./target/release/LoGen --input "North Star" --preset interlock --format svg --out examples/interlocking_rings_north_star.svg
+
+# Circuit examples
+./target/release/LoGen --input "Dev Tools" --preset circuit --format png --out examples/circuit_dev_tools.png --size 512
+./target/release/LoGen --input "Byte Forge" --preset pcb --format svg --out examples/circuit_byte_forge.svg --grid-size 19
+
+# Plant examples
+./target/release/LoGen --input "Green Leaf" --preset plant --format png --out examples/plant_green_leaf.png --size 512
+./target/release/LoGen --input "Willow Health" --preset botanical --format svg --out examples/plant_willow_health.svg
+
+# Stacked monogram examples
+./target/release/LoGen --input "Sofia Grant" --preset stacked-monogram --format png --out examples/stacked_monogram_sofia_grant.png --size 512
+./target/release/LoGen --input "Atelier Weiss" --preset luxury --format svg --out examples/stacked_monogram_atelier_weiss.svg
+
+# App icon mask examples
+./target/release/LoGen --input "Acme Power" --preset monogram-badge --format png --out examples/mask_squircle.png --size 512 --mask squircle --padding 0.2
+./target/release/LoGen --input "Acme Power" --preset sunburst --format svg --out examples/mask_hexagon.svg --mask hexagon
+
 # Variant examples (same input, different outputs)
 ./target/release/LoGen --input "Brand X" --preset monogram-badge --format png --out examples/brand_x_v1.png --size 512 --variant 1
 ./target/release/LoGen --input "Brand X" --preset monogram-badge --format png --out examples/brand_x_v2.png --size 512 --variant 2
@@ -0,0 +1,254 @@
+use rand::Rng;
+
+use super::params::{self, Param};
+use super::{DrawOp, Scene};
+use crate::core::geometry::{Path, Point, Shape};
+use crate::{LoGenError, RenderOptions};
+
+const MIN_BLOBS: Param = Param::int("min_blobs", 1, 6, 2, "Fewest blobs");
+const MAX_BLOBS: Param = Param::int("max_blobs", 1, 6, 3, "Most blobs");
+
+const MIN_POINTS: Param = Param::int("min_points", 4, 24, 7, "Fewest outline points per blob");
+const MAX_POINTS: Param = Param::int("max_points", 4, 24, 11, "Most outline points per blob");
+
+const MIN_RADIUS: Param = Param::float(
+    "min_radius",
+    0.2,
+    1.0,
+    0.5,
+    "Smallest blob radius as fraction of the half side",
+);
+const MAX_RADIUS: Param = Param::float(
+    "max_radius",
+    0.2,
+    1.0,
+    0.7,
+    "Largest blob radius as fraction of the half side",
+);
+const MIN_SPREAD: Param = Param::float(
+    "min_spread",
+    0.0,
+    0.8,
+    0.25,
+    "Smallest distance of a blob from the center as fraction of the half side",
+);
+const MAX_SPREAD: Param = Param::float(
+    "max_spread",
+    0.0,
+    0.8,
+    0.45,
+    "Largest distance of a blob from the center as fraction of the half side",
+);
+
+/// Random turn of each blob's angle, as fraction of its share of the turn.
+const ANGLE_JITTER: f32 = 0.3;
+
+/// Number of sine harmonics perturbing each blob's radius, starting at the
+/// second so blobs stay roughly round rather than egg-shaped.
+const HARMONICS: usize = 3;
+const MAX_AMPLITUDE: Param = Param::float(
+    "max_amplitude",
+    0.01,
+    0.4,
+    0.18,
+    "Largest wobble of the outline as fraction of the radius",
+);
+
+const OVERLAP_MIX: Param = Param::float(
+    "overlap_mix",
+    0.0,
+    1.0,
+    0.5,
+    "How far overlaps are tinted towards the blob below",
+);
+
+/// Parameters `RenderOptions::params` can override.
+pub const PARAMS: &[Param] = &[
+    MIN_BLOBS,
+    MAX_BLOBS,
+    MIN_POINTS,
+    MAX_POINTS,
+    MIN_RADIUS,
+    MAX_RADIUS,
+    MIN_SPREAD,
+    MAX_SPREAD,
+    MAX_AMPLITUDE,
+    OVERLAP_MIX,
+];
+
+/// Organic mark: a few smooth, closed blobs (circles perturbed by seeded
+/// low-frequency harmonics, drawn as cubic splines) layered with their
+/// overlaps tinted in a mix of both colors.
+pub fn build<R: Rng>(
+    _normalized: &str,
+    rng: &mut R,
+    opts: &RenderOptions,
+) -> Result<Scene, LoGenError> {
+    let (width, height) = opts.canvas_size();
+    let square = super::centered_square(opts);
+    let center = Point::new(square.x + square.w / 2.0, square.y + square.h / 2.0);
+    let palette = super::palette_for(rng, opts);
+
+    // Blobs are built around the origin in units of their base radius,
+    // then the whole cluster is fitted into the padded square.
+    let colors = [palette.primary, palette.secondary, palette.tertiary];
+    let start = rng.gen_range(0..colors.len());
+    let count = rng.gen_range(MIN_BLOBS.value_usize(opts)..=MAX_BLOBS.value_usize(opts));
+    let turn = rng.gen_range(0.0..std::f32::consts::TAU);
+    let slot = std::f32::consts::TAU / count as f32;
+    let blobs: Vec<Path> = (0..count)
+        .map(|i| {
+            let angle = turn + slot * (i as f32 + rng.gen_range(-ANGLE_JITTER..ANGLE_JITTER));
+            let spread =
+                params::gen_between(rng, MIN_SPREAD.value_f32(opts), MAX_SPREAD.value_f32(opts));
+            let center = Point::new(spread * angle.cos(), spread * angle.sin());
+            // Scaled to about the drawn size, so the bounds below are
+            // measured on curves flattened as finely as when drawing.
+            blob(rng, center, opts).map(|p| Point::new(p.x * square.w / 2.0, p.y * square.w / 2.0))
+        })
+        .collect();
+
+    let (mut min, mut max) = (
+        Point::new(f32::MAX, f32::MAX),
+        Point::new(f32::MIN, f32::MIN),
+    );
+    for path in &blobs {
+        for (poly, _) in path.flatten() {
+            for p in poly {
+                min = Point::new(min.x.min(p.x), min.y.min(p.y));
+                max = Point::new(max.x.max(p.x), max.y.max(p.y));
+            }
+        }
+    }
+    let scale = (square.w / (max.x - min.x)).min(square.h / (max.y - min.y));
+    let mid = Point::new((min.x + max.x) / 2.0, (min.y + max.y) / 2.0);
+    let blobs: Vec<(Path, _)> = blobs
+        .iter()
+        .enumerate()
+        .map(|(i, path)| {
+            let fitted = path.map(|p| {
+                Point::new(
+                    center.x + (p.x - mid.x) * scale,
+                    center.y + (p.y - mid.y) * scale,
+                )
+            });
+            (fitted, colors[(start + i) % colors.len()])
+        })
+        .collect();
+
+    let mut ops = vec![DrawOp::Background {
+        color: palette.background,
+    }];
+    for (i, (path, color)) in blobs.iter().enumerate() {
+        ops.push(DrawOp::ShapeFill {
+            shape: Shape::Path(path.clone()),
+            color: *color,
+        });
+        // Retint the parts covering earlier blobs.
+        for (under, under_color) in &blobs[..i] {
+            ops.push(DrawOp::Group {
+                clip: Some(Shape::Path(under.clone())),
+                ops: vec![DrawOp::ShapeFill {
+                    shape: Shape::Path(path.clone()),
+                    color: color.lerp(*under_color, OVERLAP_MIX.value_f32(opts)),
+                }],
+            });
+        }
+    }
+
+    Ok(Scene {
+        width,
+        height,
+        ops,
+        palette,
+    })
+}
+
+/// One blob around `c`, with a base radius of about one unit.
+fn blob<R: Rng>(rng: &mut R, c: Point, opts: &RenderOptions) -> Path {
+    let harmonics: Vec<(f32, f32)> = (0..HARMONICS)
+        .map(|k| {
+            let amplitude = rng.gen_range(0.0..MAX_AMPLITUDE.value_f32(opts)) / (1 << k) as f32;
+            (amplitude, rng.gen_range(0.0..std::f32::consts::TAU))
+        })
+        .collect();
+    let n = rng.gen_range(MIN_POINTS.value_usize(opts)..=MAX_POINTS.value_usize(opts));
+    let radii: Vec<f32> = (0..n)
+        .map(|i| {
+            let t = i as f32 / n as f32 * std::f32::consts::TAU;
+            1.0 + harmonics
+                .iter()
+                .enumerate()
+                .map(|(k, (amplitude, phase))| amplitude * ((k + 2) as f32 * t + phase).sin())
+                .sum::<f32>()
+        })
+        .collect();
+
+    let base = params::gen_between(rng, MIN_RADIUS.value_f32(opts), MAX_RADIUS.value_f32(opts));
+
+    let points: Vec<Point> = radii
+        .iter()
+        .enumerate()
+        .map(|(i, k)| {
+            let t = i as f32 / n as f32 * std::f32::consts::TAU;
+            Point::new(c.x + base * k * t.cos(), c.y + base * k * t.sin())
+        })
+        .collect();
+    Path::catmull_rom(&points, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::build;
+    use crate::algorithms::{DrawOp, Scene};
+    use crate::core::geometry::Shape;
+    use crate::RenderOptions;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn scene(seed: u64) -> Scene {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        build("Blobby", &mut rng, &RenderOptions::default()).expect("build failed")
+    }
+
+    #[test]
+    fn blobs_are_closed_and_inside_the_padded_square() {
+        for seed in 0..16 {
+            for op in &scene(seed).ops {
+                if let DrawOp::ShapeFill {
+                    shape: Shape::Path(path),
+                    ..
+                } = op
+                {
+                    let polys = path.flatten();
+                    assert_eq!(polys.len(), 1);
+                    assert!(polys[0].1, "seed {seed}: blob not closed");
+                    for p in &polys[0].0 {
+                        // The padded square spans 61..451.
+                        assert!(p.x >= 60.9 && p.x <= 451.1, "seed {seed}: {p:?}");
+                        assert!(p.y >= 60.9 && p.y <= 451.1, "seed {seed}: {p:?}");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn every_later_blob_retints_each_earlier_one() {
+        for seed in 0..16 {
+            let s = scene(seed);
+            let blobs = s
+                .ops
+                .iter()
+                .filter(|op| matches!(op, DrawOp::ShapeFill { .. }))
+                .count();
+            let overlaps = s
+                .ops
+                .iter()
+                .filter(|op| matches!(op, DrawOp::Group { clip: Some(_), .. }))
+                .count();
+            assert!((2..=3).contains(&blobs), "seed {seed}: {blobs}");
+            assert_eq!(overlaps, blobs * (blobs - 1) / 2);
+        }
+    }
+}
@@ -0,0 +1,391 @@
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use super::monogram_badge::initials_from_normalized;
+use super::params::Param;
+use super::router::{self, Cell, Grid};
+use super::{DrawOp, Scene};
+use crate::core::geometry::{Circle, Path, Point, Rect, Shape};
+use crate::core::glyphs;
+use crate::{LoGenError, RenderOptions};
+
+const GRID_SIZE: Param = Param::int(
+    "grid_size",
+    2,
+    64,
+    15,
+    "Routing grid cells across the badge",
+);
+const DENSITY: Param = Param::float(
+    "density",
+    0.0,
+    1.0,
+    0.8,
+    "Fraction of chip pins that get a trace",
+);
+
+const CIRCLE_PROBABILITY: Param = Param::float(
+    "circle_probability",
+    0.0,
+    1.0,
+    0.5,
+    "Chance of a circular badge instead of a rounded square",
+);
+
+/// Corner radius of the rounded square badge as fraction of its side.
+const CORNER_RADIUS: f32 = 0.2;
+/// Free margin inside the badge outline, in grid cells.
+const EDGE_MARGIN: f32 = 0.6;
+
+const CHIP_SIZE: Param = Param::float(
+    "chip_size",
+    0.15,
+    0.6,
+    0.34,
+    "Chip side as fraction of the grid",
+);
+
+/// Corner radius of the chip as fraction of its side.
+const CHIP_CORNER: f32 = 0.12;
+/// Inset of the initials from the chip edge as fraction of its side.
+const CHIP_PADDING: f32 = 0.18;
+/// Gap between the initials as fraction of the font size.
+const TRACKING: f32 = 0.08;
+
+/// Minimum distance of a trace's far end from its pin, in cells, along the
+/// pin's outward direction.
+const MIN_REACH: usize = 2;
+const MAX_DETOUR: Param = Param::float(
+    "max_detour",
+    1.0,
+    4.0,
+    1.5,
+    "Longest accepted route as multiple of the direct distance to its end",
+);
+
+/// Far ends tried per pin before it is left unrouted.
+const ATTEMPTS: usize = 6;
+
+const TRACE_WIDTH: Param = Param::float("trace_width", 0.1, 0.8, 0.3, "Trace width in grid cells");
+const VIA_RADIUS: Param = Param::float("via_radius", 0.2, 0.5, 0.4, "Via pad radius in grid cells");
+
+const VIA_HOLE: f32 = 0.17;
+/// Length of the stub drawn on pins without a trace, in grid cells.
+const STUB: f32 = 0.5;
+
+const TRACE_TINT: Param = Param::float(
+    "trace_tint",
+    0.0,
+    1.0,
+    0.2,
+    "How far traces are tinted from the chip color towards the badge",
+);
+
+/// Parameters `RenderOptions::params` can override.
+pub const PARAMS: &[Param] = &[
+    GRID_SIZE,
+    DENSITY,
+    CIRCLE_PROBABILITY,
+    CHIP_SIZE,
+    MAX_DETOUR,
+    TRACE_WIDTH,
+    VIA_RADIUS,
+    TRACE_TINT,
+];
+
+/// Circuit board: a chip carrying the initials in the middle of a badge,
+/// with orthogonal traces routed from its pins out to via pads without
+/// crossing each other.
+pub fn build<R: Rng>(
+    normalized: &str,
+    rng: &mut R,
+    opts: &RenderOptions,
+) -> Result<Scene, LoGenError> {
+    let (width, height) = opts.canvas_size();
+    let square = super::centered_square(opts);
+    let palette = super::palette_for(rng, opts);
+    let side = square.w;
+
+    let badge = if rng.gen_bool(CIRCLE_PROBABILITY.value(opts)) {
+        Shape::Circle(Circle {
+            cx: square.x + side / 2.0,
+            cy: square.y + side / 2.0,
+            r: side / 2.0,
+        })
+    } else {
+        Shape::Rect {
+            rect: square,
+            rx: side * CORNER_RADIUS,
+            ry: side * CORNER_RADIUS,
+        }
+    };
+
+    let n = GRID_SIZE.value_usize(opts);
+    let density = DENSITY.value_f32(opts) as f64;
+    let pitch = side / n as f32;
+    let center_of = |(col, row): Cell| {
+        Point::new(
+            square.x + (col as f32 + 0.5) * pitch,
+            square.y + (row as f32 + 0.5) * pitch,
+        )
+    };
+
+    // Chip cells, centered exactly: the chip has the grid's parity.
+    let mut k = ((n as f32 * CHIP_SIZE.value_f32(opts)).round() as usize).clamp(1, n);
+    if (n - k) % 2 == 1 {
+        k += 1;
+    }
+    let first = (n - k) / 2;
+    let last = first + k - 1;
+
+    let mut grid = Grid::new(n, n);
+    for row in 0..n {
+        for col in 0..n {
+            let on_chip = (first..=last).contains(&col) && (first..=last).contains(&row);
+            // The ring around the chip is kept for pins.
+            let around = (first.saturating_sub(1)..=last + 1).contains(&col)
+                && (first.saturating_sub(1)..=last + 1).contains(&row);
+            let outside = badge.signed_distance(center_of((col, row))) > -EDGE_MARGIN * pitch;
+            if on_chip || around || outside {
+                grid.block((col, row));
+            }
+        }
+    }
+
+    // Pins on every other cell along each chip side, with their outward
+    // direction.
+    let mut pins: Vec<(Cell, (isize, isize))> = Vec::new();
+    if first > 0 && last + 1 < n {
+        for i in (first..=last).step_by(2) {
+            pins.push(((i, first - 1), (0, -1)));
+            pins.push(((last + 1, i), (1, 0)));
+            pins.push(((i, last + 1), (0, 1)));
+            pins.push(((first - 1, i), (-1, 0)));
+        }
+    }
+    pins.shuffle(rng);
+
+    let mut traces = Vec::new();
+    let mut vias = Vec::new();
+    let mut stubs = Vec::new();
+    for &(pin, (dx, dy)) in &pins {
+        let edge = {
+            let c = center_of(pin);
+            Point::new(c.x - dx as f32 * pitch / 2.0, c.y - dy as f32 * pitch / 2.0)
+        };
+        let mut routed = None;
+        if rng.gen_bool(density) {
+            // Far ends lie beyond the pin, away from the chip.
+            let reach = |cell: Cell| {
+                let along = (cell.0 as isize - pin.0 as isize) * dx
+                    + (cell.1 as isize - pin.1 as isize) * dy;
+                along >= MIN_REACH as isize
+            };
+            let mut ends: Vec<Cell> = (0..n)
+                .flat_map(|row| (0..n).map(move |col| (col, row)))
+                .filter(|&cell| grid.is_free(cell) && reach(cell))
+                .collect();
+            ends.shuffle(rng);
+            grid.unblock(pin);
+            for &end in ends.iter().take(ATTEMPTS) {
+                let Some(route) = grid.route(pin, end) else {
+                    continue;
+                };
+                let direct = pin.0.abs_diff(end.0) + pin.1.abs_diff(end.1);
+                if route.len() as f32 <= MAX_DETOUR.value_f32(opts) * direct as f32 + 1.0 {
+                    routed = Some(route);
+                    break;
+                }
+                // Winding around the chip would wall off the other pins.
+                for &cell in &route {
+                    grid.unblock(cell);
+                }
+            }
+            if routed.is_none() {
+                grid.block(pin);
+            }
+        }
+        match routed {
+            Some(route) => {
+                let mut path = Path::new();
+                path.move_to(edge);
+                for cell in router::corners(&route) {
+                    path.line_to(center_of(cell));
+                }
+                traces.push(path);
+                vias.push(center_of(route[route.len() - 1]));
+            }
+            None => {
+                let mut path = Path::new();
+                path.move_to(edge);
+                path.line_to(Point::new(
+                    edge.x + dx as f32 * STUB * pitch,
+                    edge.y + dy as f32 * STUB * pitch,
+                ));
+                stubs.push(path);
+            }
+        }
+    }
+
+    let chip_color = palette.text_color;
+    let trace_color = chip_color.lerp(palette.primary, TRACE_TINT.value_f32(opts));
+    let mut ops = vec![
+        DrawOp::Background {
+            color: palette.background,
+        },
+        DrawOp::ShapeFill {
+            shape: badge,
+            color: palette.primary,
+        },
+    ];
+    ops.extend(
+        traces
+            .into_iter()
+            .chain(stubs)
+            .map(|path| DrawOp::ShapeStroke {
+                shape: Shape::Path(path),
+                color: trace_color,
+                width: TRACE_WIDTH.value_f32(opts) * pitch,
+            }),
+    );
+    for via in vias {
+        let pad = |r: f32| {
+            Shape::Circle(Circle {
+                cx: via.x,
+                cy: via.y,
+                r: r * pitch,
+            })
+        };
+        ops.push(DrawOp::ShapeFill {
+            shape: pad(VIA_RADIUS.value_f32(opts)),
+            color: trace_color,
+        });
+        ops.push(DrawOp::ShapeFill {
+            shape: pad(VIA_HOLE),
+            color: palette.primary,
+        });
+    }
+
+    let chip = Rect {
+        x: square.x + first as f32 * pitch,
+        y: square.y + first as f32 * pitch,
+        w: k as f32 * pitch,
+        h: k as f32 * pitch,
+    };
+    let inset = chip.w * CHIP_PADDING;
+    let initials = initials_from_normalized(normalized);
+    let size = 100.0;
+    let laid_out = glyphs::lay_out(glyphs::geometry_font(), &initials, size, TRACKING * size);
+    let letters = glyphs::fit_in(
+        &laid_out,
+        Rect {
+            x: chip.x + inset,
+            y: chip.y + inset,
+            w: chip.w - 2.0 * inset,
+            h: chip.h - 2.0 * inset,
+        },
+    );
+    // The initials are cut out of the chip, showing the badge through.
+    let mut chip_ops = vec![DrawOp::ShapeFill {
+        shape: Shape::Rect {
+            rect: chip,
+            rx: chip.w * CHIP_CORNER,
+            ry: chip.w * CHIP_CORNER,
+        },
+        color: chip_color,
+    }];
+    chip_ops.extend(letters.into_iter().map(|letter| DrawOp::Knockout {
+        op: Box::new(DrawOp::ShapeFill {
+            shape: Shape::Path(letter),
+            color: chip_color,
+        }),
+    }));
+    ops.push(DrawOp::Group {
+        clip: None,
+        ops: chip_ops,
+    });
+
+    Ok(Scene {
+        width,
+        height,
+        ops,
+        palette,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::build;
+    use crate::algorithms::{DrawOp, Scene};
+    use crate::core::geometry::{segment_distance, Point, Shape};
+    use crate::RenderOptions;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn scene(seed: u64, opts: &RenderOptions) -> Scene {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        build("Dev Tools", &mut rng, opts).expect("build failed")
+    }
+
+    /// Polylines of the traces and stubs.
+    fn traces(scene: &Scene) -> Vec<Vec<Point>> {
+        scene
+            .ops
+            .iter()
+            .filter_map(|op| match op {
+                DrawOp::ShapeStroke {
+                    shape: Shape::Path(path),
+                    ..
+                } => Some(path.flatten().remove(0).0),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn traces_are_orthogonal_and_never_touch() {
+        for seed in 0..8 {
+            let s = scene(seed, &RenderOptions::default());
+            let traces = traces(&s);
+            assert!(
+                traces.iter().filter(|t| t.len() > 2).count() >= 4,
+                "seed {seed}"
+            );
+            for trace in &traces {
+                for w in trace.windows(2) {
+                    let (dx, dy) = ((w[1].x - w[0].x).abs(), (w[1].y - w[0].y).abs());
+                    assert!(dx < 1e-3 || dy < 1e-3, "seed {seed}: diagonal segment");
+                }
+            }
+            // Distinct traces keep at least most of a cell apart (pitch is
+            // 390 / 15 = 26 px).
+            for (i, a) in traces.iter().enumerate() {
+                for b in &traces[i + 1..] {
+                    for p in b {
+                        for w in a.windows(2) {
+                            let d = segment_distance(*p, w[0], w[1]);
+                            assert!(d > 0.4 * 26.0, "seed {seed}: traces {d} apart");
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn density_sets_the_routed_share() {
+        let routed = |density: f32| {
+            let opts = RenderOptions {
+                params: [("density".to_string(), density as f64)].into(),
+                ..Default::default()
+            };
+            // Stubs are single segments.
+            traces(&scene(3, &opts))
+                .iter()
+                .filter(|t| t.len() > 2)
+                .count()
+        };
+        assert_eq!(routed(0.0), 0);
+        assert!(routed(1.0) > routed(0.3));
+    }
+}
@@ -0,0 +1,233 @@
+use rand::Rng;
+
+use super::monogram_badge::{badge_circle, initials_from_normalized};
+use super::params::{self, Param};
+use super::{DrawOp, Scene};
+use crate::core::geometry::{self, Point};
+use crate::core::typography;
+use crate::{LoGenError, RenderOptions};
+
+/// Radius of the outer ring as fraction of the badge radius.
+const OUTER_RING: f32 = 0.92;
+/// Radius of the inner ring as fraction of the badge radius.
+const INNER_RING: f32 = 0.62;
+const RING_WIDTH: Param = Param::float(
+    "ring_width",
+    0.005,
+    0.1,
+    0.025,
+    "Ring stroke width as fraction of the seal radius",
+);
+
+const DOUBLE_RING_PROBABILITY: Param = Param::float(
+    "double_ring_probability",
+    0.0,
+    1.0,
+    0.5,
+    "Chance of a second outer ring",
+);
+
+/// Maximum circumference text size as fraction of the text band width.
+const MAX_BAND_TEXT: f32 = 0.6;
+/// Estimated advance of an uppercase glyph as fraction of the font size,
+/// used to fit the circumference text.
+const GLYPH_ADVANCE: f32 = 0.68;
+/// Fraction of the circumference the text may occupy.
+const MAX_TEXT_ARC: f32 = 0.9;
+
+const MIN_INITIALS_SIZE: Param = Param::float(
+    "min_initials_size",
+    0.3,
+    1.0,
+    0.75,
+    "Smallest initials size as fraction of the inner ring diameter",
+);
+const MAX_INITIALS_SIZE: Param = Param::float(
+    "max_initials_size",
+    0.3,
+    1.0,
+    0.9,
+    "Largest initials size as fraction of the inner ring diameter",
+);
+
+/// Parameters `RenderOptions::params` can override.
+pub const PARAMS: &[Param] = &[
+    RING_WIDTH,
+    DOUBLE_RING_PROBABILITY,
+    MIN_INITIALS_SIZE,
+    MAX_INITIALS_SIZE,
+];
+
+/// Emblem/seal preset: concentric rings on the circular badge, the full
+/// input set around the circumference and the initials in the center.
+pub fn build<R: Rng>(
+    normalized: &str,
+    rng: &mut R,
+    opts: &RenderOptions,
+) -> Result<Scene, LoGenError> {
+    let (width, height) = opts.canvas_size();
+    let badge = badge_circle(&super::centered_square(opts));
+    let center = Point::new(badge.cx, badge.cy);
+    let r = badge.r;
+
+    let palette = super::palette_for(rng, opts);
+    let typo = typography::Typography::default();
+    let ring_width = r * RING_WIDTH.value_f32(opts);
+
+    let ring = |radius: f32| DrawOp::ShapeStroke {
+        shape: geometry::Shape::Circle(geometry::Circle {
+            cx: center.x,
+            cy: center.y,
+            r: radius,
+        }),
+        color: palette.secondary,
+        width: ring_width,
+    };
+
+    let mut ops = vec![
+        DrawOp::Background {
+            color: palette.background,
+        },
+        DrawOp::ShapeFill {
+            shape: geometry::Shape::Circle(badge),
+            color: palette.primary,
+        },
+        ring(r * OUTER_RING),
+        ring(r * INNER_RING),
+    ];
+    let mut band_outer = r * OUTER_RING;
+    if rng.gen_bool(DOUBLE_RING_PROBABILITY.value(opts)) {
+        band_outer -= 2.5 * ring_width;
+        ops.push(ring(band_outer));
+    }
+
+    // Circumference text runs clockwise from the bottom, so that its middle
+    // (where it is centered) sits upright at the top of the band.
+    let text = normalized.to_uppercase();
+    let band_radius = (band_outer + r * INNER_RING) / 2.0;
+    let band_width = band_outer - r * INNER_RING;
+    let arc = 2.0 * std::f32::consts::PI * band_radius;
+    let chars = text.chars().count().max(1) as f32;
+    let band_font_size =
+        (band_width * MAX_BAND_TEXT).min(arc * MAX_TEXT_ARC / (chars * GLYPH_ADVANCE));
+    ops.push(DrawOp::Text {
+        text,
+        x: center.x,
+        y: center.y - band_radius,
+        font_family: typo.family.to_string(),
+        font_weight: typo.weight,
+        font_size: band_font_size,
+        color: palette.secondary,
+        anchor_middle: true,
+        letter_spacing: 0.0,
+        text_length: None,
+        path: Some(geometry::Path::arc(
+            center,
+            band_radius,
+            std::f32::consts::FRAC_PI_2,
+            2.0 * std::f32::consts::PI,
+        )),
+    });
+
+    // Short names leave the bottom of the band empty; mark it with a dot.
+    if chars * GLYPH_ADVANCE * band_font_size < arc / 2.0 {
+        ops.push(DrawOp::ShapeFill {
+            shape: geometry::Shape::Circle(geometry::Circle {
+                cx: center.x,
+                cy: center.y + band_radius,
+                r: band_font_size * 0.2,
+            }),
+            color: palette.secondary,
+        });
+    }
+
+    let initials = initials_from_normalized(normalized);
+    // Sized for two letters; three-letter initials shrink to fit the ring.
+    let initials_size = params::gen_between(
+        rng,
+        MIN_INITIALS_SIZE.value_f32(opts),
+        MAX_INITIALS_SIZE.value_f32(opts),
+    ) * r
+        * INNER_RING
+        * 2.0
+        / initials.chars().count().max(2) as f32;
+    ops.push(DrawOp::Text {
+        text: initials,
+        x: center.x,
+        y: center.y,
+        font_family: typo.family.to_string(),
+        font_weight: typo.weight,
+        font_size: initials_size,
+        color: palette.secondary,
+        anchor_middle: true,
+        letter_spacing: 0.0,
+        text_length: None,
+        path: None,
+    });
+
+    Ok(Scene {
+        width,
+        height,
+        ops,
+        palette,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::build;
+    use crate::algorithms::DrawOp;
+    use crate::RenderOptions;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn texts(input: &str) -> Vec<DrawOp> {
+        let mut rng = ChaCha8Rng::seed_from_u64(3);
+        let scene = build(input, &mut rng, &RenderOptions::default()).expect("build failed");
+        scene
+            .ops
+            .into_iter()
+            .filter(|op| matches!(op, DrawOp::Text { .. }))
+            .collect()
+    }
+
+    #[test]
+    fn full_name_follows_the_circle() {
+        match &texts("Acme Power")[0] {
+            DrawOp::Text {
+                text,
+                path: Some(path),
+                ..
+            } => {
+                assert_eq!(text, "ACME POWER");
+                // Centered in the band between the rings of the 195px badge.
+                let radius = path.length() / (2.0 * std::f32::consts::PI);
+                assert!(radius > 195.0 * 0.62 && radius < 195.0 * 0.92, "{radius}");
+            }
+            other => panic!("expected text on a path, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn initials_are_centered() {
+        match &texts("Acme Power")[1] {
+            DrawOp::Text {
+                text, x, y, path, ..
+            } => {
+                assert_eq!(text, "AP");
+                assert!(path.is_none());
+                assert_eq!((*x, *y), (256.0, 256.0));
+            }
+            other => panic!("expected initials, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn long_names_shrink_to_fit() {
+        let size = |input: &str| match &texts(input)[0] {
+            DrawOp::Text { font_size, .. } => *font_size,
+            _ => unreachable!(),
+        };
+        assert!(size("International Brotherhood of Electrical Workers Local Union") < size("Acme"));
+    }
+}
@@ -1,24 +1,94 @@
 use rand::Rng;
 
+use super::params::{self, Param};
 use super::{DrawOp, Scene};
-use crate::core::{geometry, palette, typography};
+use crate::core::palette::Rgb;
+use crate::core::{geometry, typography};
 use crate::{LoGenError, RenderOptions};
 
-/// Probability of using circles instead of rectangles.
-const CIRCLE_PROBABILITY: f64 = 0.4;
-
-/// Minimum number of geometric shapes to generate.
-const MIN_SHAPES: usize = 3;
-/// Maximum number of geometric shapes to generate.
-const MAX_SHAPES: usize = 6;
+const CIRCLE_PROBABILITY: Param = Param::float(
+    "circle_probability",
+    0.0,
+    1.0,
+    0.4,
+    "Chance of each shape being a circle instead of a rectangle",
+);
+
+const MIN_SHAPES: Param = Param::int("min_shapes", 1, 24, 3, "Fewest shapes");
+const MAX_SHAPES: Param = Param::int("max_shapes", 1, 24, 6, "Most shapes");
+
+const MIN_SHAPE_SIZE: Param = Param::float(
+    "min_shape_size",
+    0.02,
+    1.0,
+    0.15,
+    "Smallest shape side as fraction of the canvas",
+);
+const MAX_SHAPE_SIZE: Param = Param::float(
+    "max_shape_size",
+    0.02,
+    1.0,
+    0.35,
+    "Largest shape side as fraction of the canvas",
+);
+
+const TEXT_SIZE_FRACTION: Param = Param::float(
+    "text_size",
+    0.05,
+    0.5,
+    0.18,
+    "Lettermark font size as fraction of the canvas",
+);
+
+/// Parameters `RenderOptions::params` can override.
+pub const PARAMS: &[Param] = &[
+    CIRCLE_PROBABILITY,
+    MIN_SHAPES,
+    MAX_SHAPES,
+    MIN_SHAPE_SIZE,
+    MAX_SHAPE_SIZE,
+    TEXT_SIZE_FRACTION,
+];
+
+/// First letter or first two letters of the input, uppercased, or `?` when
+/// it has no alphanumerics.
+pub(crate) fn lettermark_from_normalized(normalized: &str) -> String {
+    let lettermark: String = normalized
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .take(2)
+        .collect::<String>()
+        .to_uppercase();
 
-/// Minimum shape size as fraction of canvas.
-const MIN_SHAPE_SIZE: f32 = 0.15;
-/// Maximum shape size as fraction of canvas.
-const MAX_SHAPE_SIZE: f32 = 0.35;
+    if lettermark.is_empty() {
+        "?".to_string()
+    } else {
+        lettermark
+    }
+}
 
-/// Text size as fraction of canvas width.
-const TEXT_SIZE_FRACTION: f32 = 0.18;
+/// Lettermark text op centered on `center`.
+pub(crate) fn centered_lettermark(
+    normalized: &str,
+    center: geometry::Point,
+    font_size: f32,
+    color: Rgb,
+) -> DrawOp {
+    let typo = typography::Typography::default();
+    DrawOp::Text {
+        text: lettermark_from_normalized(normalized),
+        x: center.x,
+        y: center.y,
+        font_family: typo.family.to_string(),
+        font_weight: 700,
+        font_size,
+        color,
+        anchor_middle: true,
+        letter_spacing: 0.0,
+        text_length: None,
+        path: None,
+    }
+}
 
 /// Simple geometric pattern: overlapping shapes with a centered lettermark.
 pub fn build<R: Rng>(
@@ -26,22 +96,29 @@ pub fn build<R: Rng>(
     rng: &mut R,
     opts: &RenderOptions,
 ) -> Result<Scene, LoGenError> {
-    let size = opts.size_px;
-    let w = size as f32;
-    let h = size as f32;
+    let (width, height) = opts.canvas_size();
+    let w = width as f32;
+    let h = height as f32;
 
-    let palette = palette::derive_palette(rng, opts.transparent_background);
-    let typo = typography::Typography::default();
+    let palette = super::palette_for(rng, opts);
 
-    let num_shapes = rng.gen_range(MIN_SHAPES..=MAX_SHAPES);
+    let num_shapes = rng.gen_range(MIN_SHAPES.value_usize(opts)..=MAX_SHAPES.value_usize(opts));
     let mut ops = vec![DrawOp::Background {
         color: palette.background,
     }];
 
     // Generate random geometric shapes
     for i in 0..num_shapes {
-        let shape_w = rng.gen_range(MIN_SHAPE_SIZE..MAX_SHAPE_SIZE) * w;
-        let shape_h = rng.gen_range(MIN_SHAPE_SIZE..MAX_SHAPE_SIZE) * h;
+        let shape_w = params::gen_between(
+            rng,
+            MIN_SHAPE_SIZE.value_f32(opts),
+            MAX_SHAPE_SIZE.value_f32(opts),
+        ) * w;
+        let shape_h = params::gen_between(
+            rng,
+            MIN_SHAPE_SIZE.value_f32(opts),
+            MAX_SHAPE_SIZE.value_f32(opts),
+        ) * h;
 
         let x = rng.gen_range(0.0..=(w - shape_w));
         let y = rng.gen_range(0.0..=(h - shape_h));
@@ -53,7 +130,7 @@ pub fn build<R: Rng>(
             _ => palette.tertiary,
         };
 
-        let shape = if rng.gen_bool(CIRCLE_PROBABILITY) {
+        let shape = if rng.gen_bool(CIRCLE_PROBABILITY.value(opts)) {
             geometry::Shape::Circle(geometry::Circle {
                 cx: x + shape_w / 2.0,
                 cy: y + shape_h / 2.0,
@@ -76,37 +153,20 @@ pub fn build<R: Rng>(
         ops.push(DrawOp::ShapeFill { shape, color });
     }
 
-    // Extract first letter or first two letters for lettermark
-    let lettermark: String = normalized
-        .chars()
-        .filter(|c| c.is_alphanumeric())
-        .take(2)
-        .collect::<String>()
-        .to_uppercase();
-
-    let lettermark = if lettermark.is_empty() {
-        "?".to_string()
-    } else {
-        lettermark
-    };
-
     // Add centered text on top
-    let font_size = TEXT_SIZE_FRACTION * w;
-    ops.push(DrawOp::Text {
-        text: lettermark,
-        x: w / 2.0,
-        y: h / 2.0 + font_size * 0.35,
-        font_family: typo.family.to_string(),
-        font_weight: 700,
+    let font_size = TEXT_SIZE_FRACTION.value_f32(opts) * w.min(h);
+    ops.push(centered_lettermark(
+        normalized,
+        geometry::Point::new(w / 2.0, h / 2.0 + font_size * 0.35),
         font_size,
-        color: palette.text_color,
-        anchor_middle: true,
-    });
+        palette.text_color,
+    ));
 
     Ok(Scene {
-        width: size,
-        height: size,
+        width,
+        height,
         ops,
+        palette,
     })
 }
 
@@ -123,8 +183,7 @@ mod tests {
         let opts = RenderOptions {
             size_px: 256,
             padding_frac: 0.1,
-            variant: None,
-            transparent_background: false,
+            ..Default::default()
         };
         let scene = build("TestCompany", &mut rng, &opts).expect("build failed");
 
@@ -0,0 +1,269 @@
+use rand::Rng;
+
+use super::monogram_badge::initials_from_normalized;
+use super::params::Param;
+use super::{DrawOp, Scene};
+use crate::core::palette::Rgb;
+use crate::core::{geometry, typography};
+use crate::{LoGenError, RenderOptions};
+
+const GRID_SIZE: Param = Param::int("grid_size", 2, 64, 8, "Tiles per row and column");
+const DENSITY: Param = Param::float("density", 0.0, 1.0, 0.6, "Fraction of populated tiles");
+
+const GLYPH_PROBABILITY: Param = Param::float(
+    "glyph_probability",
+    0.0,
+    1.0,
+    0.5,
+    "Chance of a populated cell holding a letter instead of a dot or square",
+);
+
+const GLYPH_SIZE: Param = Param::float(
+    "glyph_size",
+    0.1,
+    1.0,
+    0.7,
+    "Letter size as fraction of the cell",
+);
+const DOT_RADIUS: Param = Param::float(
+    "dot_radius",
+    0.05,
+    0.5,
+    0.28,
+    "Dot radius as fraction of the cell",
+);
+const SQUARE_SIZE: Param = Param::float(
+    "square_size",
+    0.1,
+    1.0,
+    0.5,
+    "Square side as fraction of the cell",
+);
+
+const LETTERMARK_SIZE: Param = Param::float(
+    "lettermark_size",
+    0.05,
+    0.5,
+    0.2,
+    "Lettermark font size as fraction of the canvas",
+);
+
+/// Parameters `RenderOptions::params` can override.
+pub const PARAMS: &[Param] = &[
+    GRID_SIZE,
+    DENSITY,
+    GLYPH_PROBABILITY,
+    GLYPH_SIZE,
+    DOT_RADIUS,
+    SQUARE_SIZE,
+    LETTERMARK_SIZE,
+];
+
+/// Backing plate padding around the lettermark, as fraction of its size.
+const PLATE_PADDING: f32 = 0.35;
+
+/// Fallback plate color on transparent canvases.
+const PLATE_FALLBACK: Rgb = Rgb {
+    r: 255,
+    g: 255,
+    b: 255,
+};
+
+/// Glyph grid pattern: a seeded tiling of small glyphs (taken from the input)
+/// and shapes, with the lettermark set on a backing plate for readability.
+pub fn build<R: Rng>(
+    normalized: &str,
+    rng: &mut R,
+    opts: &RenderOptions,
+) -> Result<Scene, LoGenError> {
+    let (width, height) = opts.canvas_size();
+    let w = width as f32;
+    let h = height as f32;
+
+    let palette = super::palette_for(rng, opts);
+    let typo = typography::Typography::default();
+
+    let n = GRID_SIZE.value_usize(opts);
+    let density = DENSITY.value_f32(opts) as f64;
+
+    // `n` tiles span the shorter axis; the longer one fits as many more
+    // tiles as the padded canvas allows, so patterns fill banners too.
+    let (pad_x, pad_y) = opts.padding_px();
+    let tile = (w - 2.0 * pad_x).min(h - 2.0 * pad_y) / n as f32;
+    let cols = (((w - 2.0 * pad_x) / tile + 1e-3).floor() as usize).max(n);
+    let rows = (((h - 2.0 * pad_y) / tile + 1e-3).floor() as usize).max(n);
+    let origin_x = (w - tile * cols as f32) / 2.0;
+    let origin_y = (h - tile * rows as f32) / 2.0;
+
+    let glyphs: Vec<char> = normalized
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(|c| c.to_uppercase())
+        .collect();
+    let colors = [palette.primary, palette.secondary, palette.tertiary];
+
+    let mut ops = vec![DrawOp::Background {
+        color: palette.background,
+    }];
+
+    for row in 0..rows {
+        for col in 0..cols {
+            if !rng.gen_bool(density) {
+                continue;
+            }
+            let cx = origin_x + (col as f32 + 0.5) * tile;
+            let cy = origin_y + (row as f32 + 0.5) * tile;
+            let color = colors[rng.gen_range(0..colors.len())];
+
+            if !glyphs.is_empty() && rng.gen_bool(GLYPH_PROBABILITY.value(opts)) {
+                let glyph = glyphs[rng.gen_range(0..glyphs.len())];
+                let font_size = tile * GLYPH_SIZE.value_f32(opts);
+                ops.push(DrawOp::Text {
+                    text: glyph.to_string(),
+                    x: cx,
+                    y: cy,
+                    font_family: typo.family.to_string(),
+                    font_weight: typo.weight,
+                    font_size,
+                    color,
+                    anchor_middle: true,
+                    letter_spacing: 0.0,
+                    text_length: None,
+                    path: None,
+                });
+            } else if rng.gen_bool(0.5) {
+                ops.push(DrawOp::ShapeFill {
+                    shape: geometry::Shape::Circle(geometry::Circle {
+                        cx,
+                        cy,
+                        r: tile * DOT_RADIUS.value_f32(opts),
+                    }),
+                    color,
+                });
+            } else {
+                let side = tile * SQUARE_SIZE.value_f32(opts);
+                ops.push(DrawOp::ShapeFill {
+                    shape: geometry::Shape::Rect {
+                        rect: geometry::Rect {
+                            x: cx - side / 2.0,
+                            y: cy - side / 2.0,
+                            w: side,
+                            h: side,
+                        },
+                        rx: side * 0.2,
+                        ry: side * 0.2,
+                    },
+                    color,
+                });
+            }
+        }
+    }
+
+    // Lettermark on a plate in the background color, so it stays legible
+    // regardless of what the tiles underneath look like.
+    let lettermark = initials_from_normalized(normalized);
+    let font_size = LETTERMARK_SIZE.value_f32(opts) * w.min(h);
+    let plate_h = font_size * (1.0 + 2.0 * PLATE_PADDING);
+    let plate_w =
+        typography::text_width(&lettermark, font_size, 0.0) + 2.0 * PLATE_PADDING * font_size;
+    let plate = geometry::Shape::Rect {
+        rect: geometry::Rect {
+            x: (w - plate_w) / 2.0,
+            y: (h - plate_h) / 2.0,
+            w: plate_w,
+            h: plate_h,
+        },
+        rx: plate_h * 0.25,
+        ry: plate_h * 0.25,
+    };
+    ops.push(DrawOp::ShapeFill {
+        shape: plate.clone(),
+        color: palette.background.unwrap_or(PLATE_FALLBACK),
+    });
+    ops.push(DrawOp::ShapeStroke {
+        shape: plate,
+        color: palette.primary,
+        width: font_size * 0.06,
+    });
+    ops.push(DrawOp::Text {
+        text: lettermark,
+        x: w / 2.0,
+        y: h / 2.0,
+        font_family: typo.family.to_string(),
+        font_weight: typo.weight,
+        font_size,
+        color: palette.primary,
+        anchor_middle: true,
+        letter_spacing: 0.0,
+        text_length: None,
+        path: None,
+    });
+
+    Ok(Scene {
+        width,
+        height,
+        ops,
+        palette,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::build;
+    use crate::algorithms::DrawOp;
+    use crate::core::geometry::Shape;
+    use crate::core::typography;
+    use crate::RenderOptions;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn tile_count(density: f32) -> usize {
+        let mut rng = ChaCha8Rng::seed_from_u64(11);
+        let opts = RenderOptions {
+            params: [("density".to_string(), density as f64)].into(),
+            ..Default::default()
+        };
+        let scene = build("Glyph Grid", &mut rng, &opts).expect("build failed");
+        // background + plate fill + plate stroke + lettermark
+        scene.ops.len() - 4
+    }
+
+    #[test]
+    fn density_controls_tile_count() {
+        assert_eq!(tile_count(0.0), 0);
+        assert_eq!(tile_count(1.0), 64);
+        let half = tile_count(0.5);
+        assert!(half > 16 && half < 48, "got {half}");
+    }
+
+    #[test]
+    fn lettermark_is_drawn_last() {
+        let mut rng = ChaCha8Rng::seed_from_u64(5);
+        let scene = build("Glyph Grid", &mut rng, &RenderOptions::default()).expect("build failed");
+        match scene.ops.last() {
+            Some(DrawOp::Text { text, .. }) => assert_eq!(text, "GG"),
+            other => panic!("expected lettermark text, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn plate_fits_wide_initials() {
+        let mut rng = ChaCha8Rng::seed_from_u64(5);
+        let scene = build("Wide Mark", &mut rng, &RenderOptions::default()).expect("build failed");
+        let n = scene.ops.len();
+        let (
+            DrawOp::ShapeFill {
+                shape: Shape::Rect { rect, .. },
+                ..
+            },
+            DrawOp::Text {
+                text, font_size, ..
+            },
+        ) = (&scene.ops[n - 3], &scene.ops[n - 1])
+        else {
+            panic!("expected plate and lettermark");
+        };
+        assert_eq!(text, "WM");
+        assert!(rect.w > typography::text_width(text, *font_size, 0.0) + font_size * 0.5);
+    }
+}
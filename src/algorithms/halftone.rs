@@ -0,0 +1,356 @@
+use rand::Rng;
+
+use super::monogram_badge::initials_from_normalized;
+use super::params::{self, Param};
+use super::{DrawOp, Scene};
+use crate::core::geometry::{Circle, Path, Point, Rect, Shape};
+use crate::core::glyphs;
+use crate::core::palette::Rgb;
+use crate::{LoGenError, RenderOptions};
+
+const DOT_PITCH: Param = Param::float(
+    "dot_pitch",
+    0.01,
+    0.2,
+    0.03,
+    "Distance between dot centers as fraction of the badge side",
+);
+const DOT_ANGLE: Param = Param::float(
+    "dot_angle",
+    0.0,
+    90.0,
+    45.0,
+    "Screen angle in degrees; one of the classic angles is picked by the seed when not set",
+);
+/// Classic screen angles in degrees, picked by the seed when `DOT_ANGLE` is
+/// not set.
+const SCREEN_ANGLES: [f32; 4] = [0.0, 15.0, 45.0, 75.0];
+
+const CIRCLE_PROBABILITY: Param = Param::float(
+    "circle_probability",
+    0.0,
+    1.0,
+    0.5,
+    "Chance of a circular badge instead of a rounded square",
+);
+const CORNER_RADIUS: Param = Param::float(
+    "corner_radius",
+    0.0,
+    0.5,
+    0.2,
+    "Corner radius of the rounded square badge as fraction of its side",
+);
+
+const INITIALS_PROBABILITY: Param = Param::float(
+    "initials_probability",
+    0.0,
+    1.0,
+    0.5,
+    "Chance of screening the initials over the gradient",
+);
+const INITIALS_SIZE: Param = Param::float(
+    "initials_size",
+    0.2,
+    1.0,
+    0.62,
+    "Initials block size as fraction of the badge side",
+);
+
+/// Gap between the initials as fraction of the font size.
+const TRACKING: f32 = 0.1;
+const FIELD_TONE: Param = Param::float(
+    "field_tone",
+    0.0,
+    1.0,
+    0.3,
+    "Tone of the gradient behind the initials",
+);
+const MUTE: Param = Param::float(
+    "mute",
+    0.0,
+    1.0,
+    0.45,
+    "How far the field dots fade towards the background behind the initials",
+);
+
+/// Fade target on transparent canvases.
+const MUTE_FALLBACK: Rgb = Rgb {
+    r: 255,
+    g: 255,
+    b: 255,
+};
+
+const RADIAL_PROBABILITY: Param = Param::float(
+    "radial_probability",
+    0.0,
+    1.0,
+    0.5,
+    "Chance of a radial gradient instead of a linear one",
+);
+
+/// Parameters `RenderOptions::params` can override.
+pub const PARAMS: &[Param] = &[
+    DOT_PITCH,
+    DOT_ANGLE,
+    CIRCLE_PROBABILITY,
+    CORNER_RADIUS,
+    INITIALS_PROBABILITY,
+    INITIALS_SIZE,
+    FIELD_TONE,
+    MUTE,
+    RADIAL_PROBABILITY,
+];
+
+/// How far from the badge center a radial gradient's peak may sit, as
+/// fraction of the side.
+const MAX_PEAK_OFFSET: f32 = 0.25;
+
+/// Tones are quantized to this many steps, so the SVG shares one circle per
+/// radius between thousands of dots.
+const TONE_STEPS: f32 = 12.0;
+
+/// Halftone screen: a rotated lattice of dots inside a badge, each dot's
+/// area encoding the tone of a seeded gradient or of the initials laid
+/// over a lighter gradient.
+pub fn build<R: Rng>(
+    normalized: &str,
+    rng: &mut R,
+    opts: &RenderOptions,
+) -> Result<Scene, LoGenError> {
+    let (width, height) = opts.canvas_size();
+    let square = super::centered_square(opts);
+    let palette = super::palette_for(rng, opts);
+    let side = square.w;
+    let center = Point::new(square.x + side / 2.0, square.y + side / 2.0);
+
+    let badge = if rng.gen_bool(CIRCLE_PROBABILITY.value(opts)) {
+        Shape::Circle(Circle {
+            cx: center.x,
+            cy: center.y,
+            r: side / 2.0,
+        })
+    } else {
+        Shape::Rect {
+            rect: square,
+            rx: side * CORNER_RADIUS.value_f32(opts),
+            ry: side * CORNER_RADIUS.value_f32(opts),
+        }
+    };
+
+    // Always draw the seeded angle so a forced one keeps the rest of the
+    // mark unchanged.
+    let seeded_angle = SCREEN_ANGLES[rng.gen_range(0..SCREEN_ANGLES.len())];
+    let angle = DOT_ANGLE
+        .overridden(opts)
+        .map_or(seeded_angle, |a| a as f32)
+        .to_radians();
+    let pitch = side * DOT_PITCH.value_f32(opts);
+
+    let gradient = if rng.gen_bool(RADIAL_PROBABILITY.value(opts)) {
+        let offset = MAX_PEAK_OFFSET * side;
+        Gradient::Radial {
+            peak: Point::new(
+                center.x + params::gen_between(rng, -offset, offset),
+                center.y + params::gen_between(rng, -offset, offset),
+            ),
+            // Far enough that the tone reaches zero only outside the badge.
+            reach: side * (0.5 + MAX_PEAK_OFFSET) * std::f32::consts::SQRT_2,
+        }
+    } else {
+        let (sin, cos) = rng.gen_range(0.0..std::f32::consts::TAU).sin_cos();
+        Gradient::Linear {
+            center,
+            direction: Point::new(cos, sin),
+            length: side,
+        }
+    };
+    let letters = if rng.gen_bool(INITIALS_PROBABILITY.value(opts)) {
+        let initials = initials_from_normalized(normalized).to_uppercase();
+        let size = 100.0;
+        let laid_out = glyphs::lay_out(glyphs::geometry_font(), &initials, size, TRACKING * size);
+        let block = INITIALS_SIZE.value_f32(opts) * side;
+        let target = Rect {
+            x: center.x - block / 2.0,
+            y: center.y - block / 2.0,
+            w: block,
+            h: block,
+        };
+        let mut outline = Path::new();
+        for glyph in glyphs::fit_in(&laid_out, target) {
+            outline.cmds.extend(glyph.cmds);
+        }
+        Some(Shape::Path(outline))
+    } else {
+        None
+    };
+
+    // Lattice rows and columns along the screen angle, covering the badge.
+    let (sin, cos) = angle.sin_cos();
+    let (u, v) = (Point::new(cos, sin), Point::new(-sin, cos));
+    let reach = (side / 2.0 * std::f32::consts::SQRT_2 / pitch).ceil() as i32;
+    let mut field = Vec::new();
+    let mut inked = Vec::new();
+    for row in -reach..=reach {
+        for col in -reach..=reach {
+            let (a, b) = (col as f32 * pitch, row as f32 * pitch);
+            let p = Point::new(center.x + a * u.x + b * v.x, center.y + a * u.y + b * v.y);
+            if !badge.contains(p) {
+                continue;
+            }
+            let (tone, on_letter) = match &letters {
+                Some(outline) => {
+                    // Antialiased over one pitch across the outline.
+                    let d = outline.signed_distance(p);
+                    let coverage = (0.5 - d / pitch).clamp(0.0, 1.0);
+                    let behind = FIELD_TONE.value_f32(opts) * gradient.tone(p);
+                    (coverage.max(behind), coverage > behind)
+                }
+                None => (gradient.tone(p), false),
+            };
+            let steps = (tone * TONE_STEPS).round();
+            if steps <= 0.0 {
+                continue;
+            }
+            // Dot area is proportional to tone; full tone dots just touch.
+            let r = pitch / 2.0 * (steps / TONE_STEPS).sqrt();
+            let dot = Circle {
+                cx: p.x,
+                cy: p.y,
+                r,
+            };
+            if on_letter {
+                inked.push(dot);
+            } else {
+                field.push(dot);
+            }
+        }
+    }
+
+    // Without initials the whole screen is inked in primary.
+    let (field_color, letter_color) = match letters {
+        Some(_) => {
+            let faded = palette.background.unwrap_or(MUTE_FALLBACK);
+            (
+                palette.secondary.lerp(faded, MUTE.value_f32(opts)),
+                palette.primary,
+            )
+        }
+        None => (palette.primary, palette.primary),
+    };
+    let mut ops = vec![DrawOp::Background {
+        color: palette.background,
+    }];
+    // One run per color keeps the SVG's shared dots in long groups.
+    for (dots, color) in [(field, field_color), (inked, letter_color)] {
+        ops.extend(dots.into_iter().map(|dot| DrawOp::ShapeFill {
+            shape: Shape::Circle(dot),
+            color,
+        }));
+    }
+
+    Ok(Scene {
+        width,
+        height,
+        ops,
+        palette,
+    })
+}
+
+/// Tone in [0, 1] over the badge.
+enum Gradient {
+    /// Ramping from 0 to 1 along `direction` across `length` about `center`.
+    Linear {
+        center: Point,
+        direction: Point,
+        length: f32,
+    },
+    /// Falling from 1 at `peak` to 0 at `reach` away from it.
+    Radial { peak: Point, reach: f32 },
+}
+
+impl Gradient {
+    fn tone(&self, p: Point) -> f32 {
+        match self {
+            Gradient::Linear {
+                center,
+                direction,
+                length,
+            } => {
+                let along = (p.x - center.x) * direction.x + (p.y - center.y) * direction.y;
+                (0.5 + along / length).clamp(0.0, 1.0)
+            }
+            Gradient::Radial { peak, reach } => (1.0 - p.distance(*peak) / reach).clamp(0.0, 1.0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::build;
+    use crate::algorithms::{DrawOp, Scene};
+    use crate::core::geometry::Shape;
+    use crate::RenderOptions;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn scene(seed: u64, opts: &RenderOptions) -> Scene {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        build("Half Tone", &mut rng, opts).expect("build failed")
+    }
+
+    fn radii(scene: &Scene) -> Vec<f32> {
+        scene
+            .ops
+            .iter()
+            .filter_map(|op| match op {
+                DrawOp::ShapeFill {
+                    shape: Shape::Circle(c),
+                    ..
+                } => Some(c.r),
+                DrawOp::Background { .. } => None,
+                other => panic!("expected a dot, got {:?}", other),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn dots_encode_tone_in_few_radii() {
+        for seed in 0..8 {
+            let s = scene(seed, &RenderOptions::default());
+            let radii = radii(&s);
+            assert!(radii.len() > 200, "seed {seed}: {}", radii.len());
+            // Full-tone dots just touch at the default pitch of 3% of 390 px.
+            let max_r = 0.03 * 390.0 / 2.0;
+            assert!(radii.iter().all(|&r| r > 0.0 && r <= max_r + 1e-3));
+            let mut distinct: Vec<u32> = radii.iter().map(|r| r.to_bits()).collect();
+            distinct.sort_unstable();
+            distinct.dedup();
+            assert!((2..=12).contains(&distinct.len()), "seed {seed}");
+        }
+    }
+
+    #[test]
+    fn pitch_sets_the_dot_count_and_angle_keeps_the_rest() {
+        let count = |pitch: f32| {
+            let opts = RenderOptions {
+                params: [
+                    ("dot_pitch".to_string(), pitch as f64),
+                    ("dot_angle".to_string(), 0.0),
+                ]
+                .into(),
+                ..Default::default()
+            };
+            radii(&scene(2, &opts)).len()
+        };
+        // Halving the pitch roughly quadruples the dots.
+        let (coarse, fine) = (count(0.04), count(0.02));
+        assert!(fine > coarse * 3 && fine < coarse * 5, "{coarse} {fine}");
+
+        let angled = RenderOptions {
+            params: [("dot_angle".to_string(), 30.0)].into(),
+            ..Default::default()
+        };
+        let (a, b) = (scene(2, &RenderOptions::default()), scene(2, &angled));
+        assert_eq!(a.palette.primary, b.palette.primary);
+    }
+}
@@ -0,0 +1,148 @@
+use rand::Rng;
+
+use super::params::Param;
+use super::{DrawOp, Scene};
+use crate::core::geometry;
+use crate::{LoGenError, RenderOptions};
+
+const GRID_SIZE: Param = Param::int("grid_size", 2, 64, 5, "Cells per row and column");
+
+const FILL_PROBABILITY: Param = Param::float(
+    "fill_probability",
+    0.0,
+    1.0,
+    0.5,
+    "Chance of each cell being filled",
+);
+
+/// Parameters `RenderOptions::params` can override.
+pub const PARAMS: &[Param] = &[GRID_SIZE, FILL_PROBABILITY];
+
+/// Classic mirrored-grid identicon: cells of the left half are filled from
+/// the seed and mirrored onto the right half.
+pub fn build<R: Rng>(
+    _normalized: &str,
+    rng: &mut R,
+    opts: &RenderOptions,
+) -> Result<Scene, LoGenError> {
+    let (width, height) = opts.canvas_size();
+
+    let palette = super::palette_for(rng, opts);
+    let n = GRID_SIZE.value_usize(opts);
+
+    // Snap cells to whole pixels so adjacent cells never leave hairline gaps.
+    // The grid stays square and centered on non-square canvases.
+    let square = super::centered_square(opts);
+    let cell = (square.w / n as f32).floor().max(1.0);
+    let origin_x = ((width as f32 - cell * n as f32) / 2.0).round();
+    let origin_y = ((height as f32 - cell * n as f32) / 2.0).round();
+
+    let half = n.div_ceil(2);
+    let mut cells = vec![false; n * n];
+    for row in 0..n {
+        for col in 0..half {
+            let filled = rng.gen_bool(FILL_PROBABILITY.value(opts));
+            cells[row * n + col] = filled;
+            cells[row * n + (n - 1 - col)] = filled;
+        }
+    }
+
+    // An empty grid is not much of an avatar; light up the center instead.
+    if cells.iter().all(|c| !c) {
+        let mid = n / 2;
+        cells[mid * n + mid] = true;
+        cells[mid * n + (n - 1 - mid)] = true;
+    }
+
+    let mut ops = vec![DrawOp::Background {
+        color: palette.background,
+    }];
+
+    for row in 0..n {
+        for col in 0..n {
+            if !cells[row * n + col] {
+                continue;
+            }
+            ops.push(DrawOp::ShapeFill {
+                shape: geometry::Shape::Rect {
+                    rect: geometry::Rect {
+                        x: origin_x + col as f32 * cell,
+                        y: origin_y + row as f32 * cell,
+                        w: cell,
+                        h: cell,
+                    },
+                    rx: 0.0,
+                    ry: 0.0,
+                },
+                color: palette.primary,
+            });
+        }
+    }
+
+    Ok(Scene {
+        width,
+        height,
+        ops,
+        palette,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::build;
+    use crate::algorithms::DrawOp;
+    use crate::core::geometry::Shape;
+    use crate::RenderOptions;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn cell_origins(opts: &RenderOptions, seed: u64) -> Vec<(f32, f32)> {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        let scene = build("Alice", &mut rng, opts).expect("build failed");
+        scene
+            .ops
+            .iter()
+            .filter_map(|op| match op {
+                DrawOp::ShapeFill {
+                    shape: Shape::Rect { rect, .. },
+                    ..
+                } => Some((rect.x, rect.y)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn grid_is_mirrored() {
+        let opts = RenderOptions {
+            size_px: 250,
+            padding_frac: 0.0,
+            ..Default::default()
+        };
+        for seed in 0..8 {
+            let cells = cell_origins(&opts, seed);
+            assert!(!cells.is_empty());
+            for (x, y) in &cells {
+                let mirrored = 250.0 - 50.0 - x;
+                assert!(
+                    cells.iter().any(|(mx, my)| *mx == mirrored && my == y),
+                    "seed {seed}: cell at ({x}, {y}) has no mirror"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn grid_size_is_configurable() {
+        let opts = RenderOptions {
+            size_px: 256,
+            padding_frac: 0.0,
+            params: [("grid_size".to_string(), 8.0)].into(),
+            ..Default::default()
+        };
+        let cells = cell_origins(&opts, 3);
+        assert!(cells
+            .iter()
+            .all(|(x, y)| x % 32.0 == 0.0 && y % 32.0 == 0.0));
+    }
+}
@@ -0,0 +1,349 @@
+use rand::Rng;
+
+use super::monogram_badge::initials_from_normalized;
+use super::params::{self, Param};
+use super::{DrawOp, Scene};
+use crate::core::geometry::{Path, Point, Rect, Shape};
+use crate::core::glyphs;
+use crate::{LoGenError, RenderOptions};
+
+/// Font size the initials are first laid out at to measure the block.
+const NOMINAL_SIZE: f32 = 100.0;
+/// Gap between letters as fraction of the font size.
+const TRACKING: f32 = 0.08;
+
+const MIN_DEPTH: Param = Param::float(
+    "min_depth",
+    0.05,
+    0.6,
+    0.18,
+    "Shallowest extrusion as fraction of the letter height",
+);
+const MAX_DEPTH: Param = Param::float(
+    "max_depth",
+    0.05,
+    0.6,
+    0.32,
+    "Deepest extrusion as fraction of the letter height",
+);
+
+const TOP_LIGHTEN: Param = Param::float(
+    "top_lighten",
+    0.0,
+    0.4,
+    0.16,
+    "Lightness added to the top walls",
+);
+const SIDE_DARKEN: Param = Param::float(
+    "side_darken",
+    0.0,
+    0.4,
+    0.16,
+    "Lightness taken from the side walls",
+);
+
+/// Parameters `RenderOptions::params` can override.
+pub const PARAMS: &[Param] = &[MIN_DEPTH, MAX_DEPTH, TOP_LIGHTEN, SIDE_DARKEN];
+
+/// Screen axes of the isometric projection: cos 30° and sin 30°, as
+/// constants so coordinates do not depend on the platform's trigonometry.
+const ISO_X: f32 = 0.866_025_4;
+const ISO_Y: f32 = 0.5;
+
+/// Distance, as fraction of the font size, at which either side of an
+/// outline edge is probed to find the glyph's inside.
+const PROBE: f32 = 0.01;
+
+/// How far each wall is stretched past both ends of its edge in pixels, so
+/// antialiased edges of neighbouring walls overlap instead of showing
+/// hairline seams.
+const SEAM_OVERLAP: f32 = 0.5;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Face {
+    /// Looking up, lit from above.
+    Top,
+    /// Looking right, in shade.
+    Side,
+}
+
+/// A visible wall of the extrusion: the quad swept by one outline edge.
+#[derive(Debug, Clone)]
+struct Wall {
+    quad: [Point; 4],
+    face: Face,
+    /// Position along the extrusion direction; walls further along it are
+    /// closer to the viewer.
+    order: f32,
+}
+
+/// Isometric block monogram: the initials' glyph outlines extruded into 3D
+/// and drawn in isometric projection, with front, top and side faces shaded
+/// in lightness steps of the primary color.
+pub fn build<R: Rng>(
+    normalized: &str,
+    rng: &mut R,
+    opts: &RenderOptions,
+) -> Result<Scene, LoGenError> {
+    let (width, height) = opts.canvas_size();
+    let square = super::centered_square(opts);
+    let palette = super::palette_for(rng, opts);
+    let depth = params::gen_between(rng, MIN_DEPTH.value_f32(opts), MAX_DEPTH.value_f32(opts));
+
+    let initials = initials_from_normalized(normalized).to_uppercase();
+    let font = glyphs::geometry_font();
+
+    // Measure at a nominal size, then lay out again at the fitted size so
+    // curves are flattened at the resolution they are drawn at.
+    let measured = extruded_bounds(
+        &glyphs::lay_out(font, &initials, NOMINAL_SIZE, TRACKING * NOMINAL_SIZE),
+        depth * NOMINAL_SIZE,
+    );
+    let scale = (square.w / measured.w).min(square.h / measured.h);
+    let font_size = NOMINAL_SIZE * scale;
+    let letters = glyphs::lay_out(font, &initials, font_size, TRACKING * font_size);
+    let bounds = extruded_bounds(&letters, depth * font_size);
+    let dx = square.x + square.w / 2.0 - (bounds.x + bounds.w / 2.0);
+    let dy = square.y + square.h / 2.0 - (bounds.y + bounds.h / 2.0);
+    let place = |p: Point, w: f32| {
+        let q = project(p, w);
+        Point::new(q.x + dx, q.y + dy)
+    };
+
+    let mut walls: Vec<Wall> = letters
+        .iter()
+        .flat_map(|outline| walls(outline, depth * font_size, PROBE * font_size))
+        .collect();
+    walls.sort_by(|a, b| a.order.total_cmp(&b.order));
+
+    let top = palette
+        .primary
+        .with_lightness_offset(TOP_LIGHTEN.value_f32(opts));
+    let side = palette
+        .primary
+        .with_lightness_offset(-SIDE_DARKEN.value_f32(opts));
+    let mut ops = vec![DrawOp::Background {
+        color: palette.background,
+    }];
+    for wall in &walls {
+        let quad = wall.quad.map(|p| Point::new(p.x + dx, p.y + dy));
+        ops.push(DrawOp::ShapeFill {
+            shape: Shape::polygon(&quad),
+            color: match wall.face {
+                Face::Top => top,
+                Face::Side => side,
+            },
+        });
+    }
+
+    // Front faces last: nothing in the extrusion is nearer than them.
+    let mut front = Path::new();
+    for outline in &letters {
+        front.cmds.extend(outline.map(|p| place(p, 0.0)).cmds);
+    }
+    ops.push(DrawOp::ShapeFill {
+        shape: Shape::Path(front),
+        color: palette.primary,
+    });
+
+    Ok(Scene {
+        width,
+        height,
+        ops,
+        palette,
+    })
+}
+
+/// Isometric projection of the point `p` in the letters' plane, pushed
+/// `w` back along the extrusion.
+///
+/// The letters stand on the left face of an isometric cube: their x axis
+/// runs down-right, their y axis straight down, and the extrusion recedes
+/// up-right.
+fn project(p: Point, w: f32) -> Point {
+    Point::new(ISO_X * (p.x + w), ISO_Y * (p.x - w) + p.y)
+}
+
+/// Screen bounds of the letters extruded by `depth`.
+fn extruded_bounds(letters: &[Path], depth: f32) -> Rect {
+    let mut min = Point::new(f32::MAX, f32::MAX);
+    let mut max = Point::new(f32::MIN, f32::MIN);
+    for outline in letters {
+        for (poly, _) in outline.flatten() {
+            for p in poly {
+                for q in [project(p, 0.0), project(p, depth)] {
+                    min = Point::new(min.x.min(q.x), min.y.min(q.y));
+                    max = Point::new(max.x.max(q.x), max.y.max(q.y));
+                }
+            }
+        }
+    }
+    Rect {
+        x: min.x,
+        y: min.y,
+        w: (max.x - min.x).max(f32::EPSILON),
+        h: (max.y - min.y).max(f32::EPSILON),
+    }
+}
+
+/// The visible walls swept by `outline`'s edges when extruded by `depth`.
+///
+/// Each edge's outside is found by probing both sides against the outline's
+/// fill, so the result does not depend on the contours' winding direction.
+/// Edges with the glyph on both sides (between block font cells) or on
+/// neither (degenerate contours) sweep no wall.
+fn walls(outline: &Path, depth: f32, probe: f32) -> Vec<Wall> {
+    let mut out = Vec::new();
+    for (poly, _) in outline.flatten() {
+        for i in 0..poly.len() {
+            let (a, b) = (poly[i], poly[(i + 1) % poly.len()]);
+            let len = a.distance(b);
+            if len <= f32::EPSILON {
+                continue;
+            }
+            let normal = Point::new((b.y - a.y) / len, (a.x - b.x) / len);
+            let mid = a.lerp(b, 0.5);
+            let probe_at = |s: f32| {
+                outline.winding(Point::new(mid.x + normal.x * s, mid.y + normal.y * s)) != 0
+            };
+            let outward = match (probe_at(probe), probe_at(-probe)) {
+                (false, true) => normal,
+                (true, false) => Point::new(-normal.x, -normal.y),
+                _ => continue,
+            };
+
+            // Walls facing right (+x) or up (-y) turn towards the viewer.
+            if outward.x - outward.y <= 0.0 {
+                continue;
+            }
+            let face = if -outward.y > outward.x {
+                Face::Top
+            } else {
+                Face::Side
+            };
+            let front = project(mid, 0.0);
+            let stretch = Point::new(
+                (b.x - a.x) / len * SEAM_OVERLAP,
+                (b.y - a.y) / len * SEAM_OVERLAP,
+            );
+            let (a, b) = (
+                Point::new(a.x - stretch.x, a.y - stretch.y),
+                Point::new(b.x + stretch.x, b.y + stretch.y),
+            );
+            out.push(Wall {
+                quad: [
+                    project(a, 0.0),
+                    project(b, 0.0),
+                    project(b, depth),
+                    project(a, depth),
+                ],
+                face,
+                order: front.x * ISO_X - front.y * ISO_Y,
+            });
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{build, walls, Face};
+    use crate::algorithms::{DrawOp, Scene};
+    use crate::core::geometry::{Path, Point, Shape};
+    use crate::core::palette::rgb_to_hsl;
+    use crate::RenderOptions;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn scene(seed: u64) -> Scene {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        build("Iso Metric", &mut rng, &RenderOptions::default()).expect("build failed")
+    }
+
+    #[test]
+    fn square_shows_its_top_and_right_walls() {
+        let corners = [
+            Point::new(0.0, 0.0),
+            Point::new(10.0, 0.0),
+            Point::new(10.0, 10.0),
+            Point::new(0.0, 10.0),
+        ];
+        let mut reversed = corners;
+        reversed.reverse();
+        for square in [corners, reversed] {
+            let mut faces: Vec<Face> = walls(&Path::polygon(&square), 4.0, 0.1)
+                .iter()
+                .map(|w| w.face)
+                .collect();
+            faces.sort_by_key(|f| *f as u8);
+            assert_eq!(faces, vec![Face::Top, Face::Side]);
+        }
+    }
+
+    #[test]
+    fn stays_inside_the_padded_square() {
+        let (pad, _) = RenderOptions::default().padding_px();
+        for seed in 0..8 {
+            for op in &scene(seed).ops {
+                if let DrawOp::ShapeFill {
+                    shape: Shape::Path(path),
+                    ..
+                } = op
+                {
+                    for (poly, _) in path.flatten() {
+                        for p in poly {
+                            // Walls are stretched by half a pixel.
+                            assert!(p.x >= pad - 1.0 && p.x <= 512.0 - pad + 1.0, "{p:?}");
+                            assert!(p.y >= pad - 1.0 && p.y <= 512.0 - pad + 1.0, "{p:?}");
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn faces_step_through_primary_lightness() {
+        let s = scene(3);
+        let primary = s.palette.primary;
+        match s.ops.last() {
+            Some(DrawOp::ShapeFill { color, .. }) => assert_eq!(*color, primary),
+            other => panic!("expected the front faces, got {:?}", other),
+        }
+
+        let (_, _, l) = rgb_to_hsl(primary);
+        let mut lighter = false;
+        let mut darker = false;
+        for op in &s.ops[1..s.ops.len() - 1] {
+            match op {
+                DrawOp::ShapeFill { color, .. } => {
+                    let (_, _, wall) = rgb_to_hsl(*color);
+                    lighter |= wall > l + 0.1;
+                    darker |= wall < l - 0.1;
+                }
+                other => panic!("expected a wall, got {:?}", other),
+            }
+        }
+        assert!(lighter && darker);
+    }
+
+    #[cfg(feature = "embed-font")]
+    #[test]
+    fn front_faces_come_from_font_outlines() {
+        use crate::core::geometry::PathCmd;
+
+        // The block font draws an O from straight cells only.
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let s = build("Orbit", &mut rng, &RenderOptions::default()).unwrap();
+        match s.ops.last() {
+            Some(DrawOp::ShapeFill {
+                shape: Shape::Path(front),
+                ..
+            }) => assert!(front
+                .cmds
+                .iter()
+                .any(|cmd| matches!(cmd, PathCmd::QuadTo { .. } | PathCmd::CubicTo { .. }))),
+            other => panic!("expected the front faces, got {:?}", other),
+        }
+    }
+}
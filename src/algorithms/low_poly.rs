@@ -0,0 +1,232 @@
+use rand::Rng;
+
+use super::monogram_badge::initials_from_normalized;
+use super::params::Param;
+use super::{DrawOp, Scene};
+use crate::core::geometry::{self, Point, Shape};
+use crate::core::palette::Rgb;
+use crate::core::{triangulation, typography};
+use crate::{LoGenError, RenderOptions};
+
+const GRID_SIZE: Param = Param::int(
+    "grid_size",
+    2,
+    64,
+    8,
+    "Point lattice cells along the shorter axis",
+);
+
+const JITTER: Param = Param::float(
+    "jitter",
+    0.01,
+    0.49,
+    0.38,
+    "Largest offset of a mesh point from its grid position, as fraction of a cell",
+);
+const SHADE_JITTER: Param = Param::float(
+    "shade_jitter",
+    0.001,
+    0.5,
+    0.08,
+    "Largest random shift of a facet along the palette gradient",
+);
+
+/// How far each triangle is grown outwards in pixels, so antialiased edges
+/// of neighbouring facets overlap instead of letting the background show
+/// through as hairline seams.
+const SEAM_OVERLAP: f32 = 0.5;
+
+const LETTERMARK_PROBABILITY: Param = Param::float(
+    "lettermark_probability",
+    0.0,
+    1.0,
+    0.5,
+    "Chance of a lettermark over the mosaic",
+);
+const LETTERMARK_SIZE: Param = Param::float(
+    "lettermark_size",
+    0.05,
+    0.5,
+    0.3,
+    "Lettermark font size as fraction of the mosaic",
+);
+
+/// Parameters `RenderOptions::params` can override.
+pub const PARAMS: &[Param] = &[
+    GRID_SIZE,
+    JITTER,
+    SHADE_JITTER,
+    LETTERMARK_PROBABILITY,
+    LETTERMARK_SIZE,
+];
+
+/// Fallback lettermark color on transparent canvases.
+const LETTERMARK_FALLBACK: Rgb = Rgb {
+    r: 255,
+    g: 255,
+    b: 255,
+};
+
+/// Low-poly mosaic: seeded points on a jittered lattice, Delaunay
+/// triangulated, each facet filled from the palette gradient along a seeded
+/// direction, optionally with the lettermark on top.
+pub fn build<R: Rng>(
+    normalized: &str,
+    rng: &mut R,
+    opts: &RenderOptions,
+) -> Result<Scene, LoGenError> {
+    let (width, height) = opts.canvas_size();
+    let (w, h) = (width as f32, height as f32);
+    let palette = super::palette_for(rng, opts);
+
+    // The mosaic fills the padded canvas; `n` cells span its shorter side.
+    let (pad_x, pad_y) = opts.padding_px();
+    let (left, top) = (pad_x, pad_y);
+    let (mosaic_w, mosaic_h) = (w - 2.0 * pad_x, h - 2.0 * pad_y);
+    let n = GRID_SIZE.value_usize(opts);
+    let cell = mosaic_w.min(mosaic_h) / n as f32;
+    let cols = ((mosaic_w / cell).round() as usize).max(1);
+    let rows = ((mosaic_h / cell).round() as usize).max(1);
+    let (cell_w, cell_h) = (mosaic_w / cols as f32, mosaic_h / rows as f32);
+
+    // Points on the border only slide along it, so the triangles always
+    // cover the whole mosaic rectangle.
+    let mut points = Vec::with_capacity((cols + 1) * (rows + 1));
+    for row in 0..=rows {
+        for col in 0..=cols {
+            let mut dx = rng.gen_range(-JITTER.value_f32(opts)..JITTER.value_f32(opts)) * cell_w;
+            let mut dy = rng.gen_range(-JITTER.value_f32(opts)..JITTER.value_f32(opts)) * cell_h;
+            if col == 0 || col == cols {
+                dx = 0.0;
+            }
+            if row == 0 || row == rows {
+                dy = 0.0;
+            }
+            points.push(Point::new(
+                left + col as f32 * cell_w + dx,
+                top + row as f32 * cell_h + dy,
+            ));
+        }
+    }
+
+    // Gradient runs along a seeded direction across the mosaic.
+    let angle = rng.gen_range(0.0..std::f32::consts::TAU);
+    let (gx, gy) = (angle.cos(), angle.sin());
+    let reach = (mosaic_w * gx.abs() + mosaic_h * gy.abs()) / 2.0;
+    let center = Point::new(left + mosaic_w / 2.0, top + mosaic_h / 2.0);
+
+    let mut ops = vec![DrawOp::Background {
+        color: palette.background,
+    }];
+
+    for tri in triangulation::delaunay(&points) {
+        let corners = tri.map(|i| points[i]);
+        let centroid = Point::new(
+            (corners[0].x + corners[1].x + corners[2].x) / 3.0,
+            (corners[0].y + corners[1].y + corners[2].y) / 3.0,
+        );
+        let along = ((centroid.x - center.x) * gx + (centroid.y - center.y) * gy) / reach;
+        let t = (along + 1.0) / 2.0
+            + rng.gen_range(-SHADE_JITTER.value_f32(opts)..SHADE_JITTER.value_f32(opts));
+
+        let grown = geometry::grow_from_centroid(&corners, SEAM_OVERLAP);
+        ops.push(DrawOp::ShapeFill {
+            shape: Shape::polygon(&grown),
+            color: palette.gradient(t),
+        });
+    }
+
+    if rng.gen_bool(LETTERMARK_PROBABILITY.value(opts)) {
+        let typo = typography::Typography::default();
+        let lettermark = initials_from_normalized(normalized);
+        ops.push(DrawOp::Text {
+            // Sized for two letters; three-letter initials shrink to fit.
+            font_size: LETTERMARK_SIZE.value_f32(opts) * mosaic_w.min(mosaic_h) * 2.0
+                / lettermark.chars().count().max(2) as f32,
+            text: lettermark,
+            x: center.x,
+            y: center.y,
+            font_family: typo.family.to_string(),
+            font_weight: typo.weight,
+            color: palette.background.unwrap_or(LETTERMARK_FALLBACK),
+            anchor_middle: true,
+            letter_spacing: 0.0,
+            text_length: None,
+            path: None,
+        });
+    }
+
+    Ok(Scene {
+        width,
+        height,
+        ops,
+        palette,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::build;
+    use crate::algorithms::{DrawOp, Scene};
+    use crate::core::geometry::Shape;
+    use crate::RenderOptions;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn scene(seed: u64, opts: &RenderOptions) -> Scene {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        build("Low Poly", &mut rng, opts).expect("build failed")
+    }
+
+    fn facets(scene: &Scene) -> Vec<&Shape> {
+        scene
+            .ops
+            .iter()
+            .filter_map(|op| match op {
+                DrawOp::ShapeFill { shape, .. } => Some(shape),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn facets_tile_the_padded_canvas() {
+        let opts = RenderOptions::default();
+        let s = scene(1, &opts);
+        // A lattice of (n+1)^2 points with every border point on the hull
+        // triangulates into 2 * n^2 triangles.
+        assert_eq!(facets(&s).len(), 2 * 8 * 8);
+
+        let (pad, _) = opts.padding_px();
+        let inner = 512.0 - pad;
+        for (x, y) in [(pad + 1.0, pad + 1.0), (256.0, 256.0), (inner - 1.0, 300.0)] {
+            let p = crate::core::geometry::Point::new(x, y);
+            assert!(
+                facets(&s).iter().any(|f| f.contains(p)),
+                "({x}, {y}) not covered"
+            );
+        }
+    }
+
+    #[test]
+    fn grid_size_sets_facet_count() {
+        let opts = RenderOptions {
+            params: [("grid_size".to_string(), 4.0)].into(),
+            ..Default::default()
+        };
+        assert_eq!(facets(&scene(2, &opts)).len(), 2 * 4 * 4);
+    }
+
+    #[test]
+    fn lettermark_is_optional() {
+        let with_text = (0..16)
+            .filter(|&seed| {
+                scene(seed, &RenderOptions::default())
+                    .ops
+                    .iter()
+                    .any(|op| matches!(op, DrawOp::Text { .. }))
+            })
+            .count();
+        assert!(with_text > 0 && with_text < 16, "{with_text}");
+    }
+}
@@ -3,14 +3,38 @@
 //! Each algorithm (preset) builds a scene graph from normalized input
 //! and a seeded RNG for deterministic output.
 
-use rand::SeedableRng;
+use rand::{Rng, SeedableRng};
 use rand_chacha::ChaCha20Rng;
 
+use crate::core::geometry::{MaskShape, Rect};
+use crate::core::palette::{self, Palette};
 use crate::core::seed::{derive_seed_32, normalize_input};
 use crate::{LoGenError, Preset, RenderOptions};
 
+pub mod blob;
+pub mod circuit;
+pub mod emblem;
 pub mod geometric_pattern;
+pub mod glyph_grid_pattern;
+pub mod halftone;
+pub mod identicon;
+pub mod isometric;
+pub mod low_poly;
+pub mod monochrome;
 pub mod monogram_badge;
+pub mod negative_space;
+pub mod params;
+pub mod pixel_avatar;
+pub mod plant;
+pub mod ribbon_mark;
+pub mod rings;
+pub mod router;
+pub mod stacked_monogram;
+pub mod sunburst;
+pub mod topographic;
+pub mod truchet;
+pub mod voronoi;
+pub mod wordmark;
 
 /// Minimal scene graph for this stub.
 #[derive(Debug, Clone)]
@@ -18,6 +42,8 @@ pub struct Scene {
     pub width: u32,
     pub height: u32,
     pub ops: Vec<DrawOp>,
+    /// Palette the preset picked for this input.
+    pub palette: Palette,
 }
 
 #[derive(Debug, Clone)]
@@ -43,7 +69,125 @@ pub enum DrawOp {
         font_size: f32,
         color: crate::core::palette::Rgb,
         anchor_middle: bool,
+        /// Extra space after every glyph in pixels (SVG `letter-spacing`).
+        letter_spacing: f32,
+        /// When set, the space between glyphs is evened out so the text
+        /// spans this many pixels from its start to the end of the last
+        /// glyph's advance, in place of `letter_spacing` (SVG `textLength`
+        /// with `lengthAdjust="spacing"`). Lets presets place shapes against
+        /// a width they measured without the font.
+        text_length: Option<f32>,
+        /// When set, the text is set along this path instead of at `x`/`y`:
+        /// centered on the path's midpoint if `anchor_middle`, otherwise
+        /// starting at its start. Glyphs are vertically centered on the path.
+        path: Option<crate::core::geometry::Path>,
     },
+    /// Grid of square cells, `cols` per row, with its top-left corner at
+    /// `x`/`y`, listed in row-major order (`None` leaves a cell empty).
+    /// Drawn without antialiasing: PNG pixels take the color of the cell
+    /// under their center (nearest-neighbour) and SVG merges cells into
+    /// rectangles rendered with `shape-rendering="crispEdges"`.
+    Pixels {
+        x: f32,
+        y: f32,
+        cell: f32,
+        cols: usize,
+        cells: Vec<Option<crate::core::palette::Rgb>>,
+    },
+    /// Erases the coverage of the wrapped op from everything drawn before it
+    /// in the same layer.
+    Knockout { op: Box<DrawOp> },
+    /// Draws `ops` into an isolated layer (knockouts inside only erase the
+    /// group's own content), optionally clipped to `clip`.
+    Group {
+        clip: Option<crate::core::geometry::Shape>,
+        ops: Vec<DrawOp>,
+    },
+}
+
+impl DrawOp {
+    /// Copy of this op painted in `color` instead of its own color(s).
+    pub fn recolored(&self, color: crate::core::palette::Rgb) -> DrawOp {
+        let mut op = self.clone();
+        match &mut op {
+            DrawOp::Background { color: c } => {
+                if c.is_some() {
+                    *c = Some(color);
+                }
+            }
+            DrawOp::ShapeFill { color: c, .. }
+            | DrawOp::ShapeStroke { color: c, .. }
+            | DrawOp::Text { color: c, .. } => *c = color,
+            DrawOp::Pixels { cells, .. } => {
+                for c in cells.iter_mut().flatten() {
+                    *c = color;
+                }
+            }
+            DrawOp::Knockout { op } => **op = op.recolored(color),
+            DrawOp::Group { ops, .. } => {
+                *ops = ops.iter().map(|op| op.recolored(color)).collect();
+            }
+        }
+        op
+    }
+}
+
+/// Smallest accepted canvas dimension in pixels.
+pub const MIN_SIZE_PX: u32 = 64;
+/// Largest canvas dimension in pixels, also for presets that size their own
+/// canvas.
+pub const MAX_SIZE_PX: u32 = 8192;
+
+/// Largest square inside the padded canvas, centered. Presets that draw a
+/// single mark use it so the mark keeps its proportions on non-square
+/// canvases while the background fills the whole canvas.
+pub(crate) fn centered_square(opts: &RenderOptions) -> crate::core::geometry::Rect {
+    let (width, height) = opts.canvas_size();
+    let (w, h) = (width as f32, height as f32);
+    let (pad_x, pad_y) = opts.padding_px();
+    let side = (w - 2.0 * pad_x).min(h - 2.0 * pad_y).max(0.0);
+    crate::core::geometry::Rect {
+        x: (w - side) / 2.0,
+        y: (h - side) / 2.0,
+        w: side,
+        h: side,
+    }
+}
+
+/// Derive the palette for a preset, honoring the palette-related options.
+pub fn palette_for<R: Rng>(rng: &mut R, opts: &RenderOptions) -> Palette {
+    if opts.cvd_safe {
+        palette::derive_cvd_safe_palette(rng, opts.transparent_background)
+    } else {
+        palette::derive_palette(rng, opts.transparent_background)
+    }
+}
+
+/// Tunable parameters of `preset`.
+pub fn params_for(preset: Preset) -> &'static [params::Param] {
+    match preset {
+        Preset::MonogramBadge => monogram_badge::PARAMS,
+        Preset::GeometricPattern => geometric_pattern::PARAMS,
+        Preset::Identicon => identicon::PARAMS,
+        Preset::GlyphGridPattern => glyph_grid_pattern::PARAMS,
+        Preset::RibbonMark => ribbon_mark::PARAMS,
+        Preset::Emblem => emblem::PARAMS,
+        Preset::Wordmark => wordmark::PARAMS,
+        Preset::LowPoly => low_poly::PARAMS,
+        Preset::Truchet => truchet::PARAMS,
+        Preset::PixelAvatar => pixel_avatar::PARAMS,
+        Preset::Sunburst => sunburst::PARAMS,
+        Preset::Isometric => isometric::PARAMS,
+        Preset::Blob => blob::PARAMS,
+        Preset::VoronoiMosaic => voronoi::PARAMS,
+        Preset::NegativeSpace => negative_space::PARAMS,
+        Preset::Topographic => topographic::PARAMS,
+        Preset::Halftone => halftone::PARAMS,
+        Preset::InterlockingRings => rings::PARAMS,
+        Preset::CircuitTraces => circuit::PARAMS,
+        Preset::Plant => plant::PARAMS,
+        Preset::StackedMonogram => stacked_monogram::PARAMS,
+    }
 }
 
 pub fn build_scene(input: &str, preset: Preset, opts: &RenderOptions) -> Result<Scene, LoGenError> {
@@ -53,12 +197,30 @@ pub fn build_scene(input: &str, preset: Preset, opts: &RenderOptions) -> Result<
             opts.padding_frac
         )));
     }
-    if opts.size_px < 64 || opts.size_px > 8192 {
+    if !(MIN_SIZE_PX..=MAX_SIZE_PX).contains(&opts.size_px) {
         return Err(LoGenError::InvalidOptions(format!(
-            "size_px must be within [64..8192], got {}",
+            "size_px must be within [{MIN_SIZE_PX}..{MAX_SIZE_PX}], got {}",
             opts.size_px
         )));
     }
+    for (name, value) in [("width_px", opts.width_px), ("height_px", opts.height_px)] {
+        if let Some(px) = value {
+            if !(MIN_SIZE_PX..=MAX_SIZE_PX).contains(&px) {
+                return Err(LoGenError::InvalidOptions(format!(
+                    "{name} must be within [{MIN_SIZE_PX}..{MAX_SIZE_PX}], got {px}"
+                )));
+            }
+        }
+    }
+
+    let mut opts = opts.clone();
+    params::validate(preset.params(), &opts.params)?;
+
+    // Masks with a safe area raise the padding so the mark stays inside it.
+    if let Some(mask) = opts.mask {
+        opts.padding_frac = opts.padding_frac.max(mask.min_padding_frac());
+    }
+    let opts = &opts;
 
     let normalized = normalize_input(input);
     if normalized.is_empty() {
@@ -69,8 +231,59 @@ pub fn build_scene(input: &str, preset: Preset, opts: &RenderOptions) -> Result<
     let seed = derive_seed_32(&normalized, opts.variant);
     let mut rng = ChaCha20Rng::from_seed(seed);
 
-    match preset {
-        Preset::MonogramBadge => monogram_badge::build(&normalized, &mut rng, opts),
-        Preset::GeometricPattern => geometric_pattern::build(&normalized, &mut rng, opts),
-    }
+    let scene = match preset {
+        Preset::MonogramBadge => monogram_badge::build(&normalized, &mut rng, opts)?,
+        Preset::GeometricPattern => geometric_pattern::build(&normalized, &mut rng, opts)?,
+        Preset::Identicon => identicon::build(&normalized, &mut rng, opts)?,
+        Preset::GlyphGridPattern => glyph_grid_pattern::build(&normalized, &mut rng, opts)?,
+        Preset::RibbonMark => ribbon_mark::build(&normalized, &mut rng, opts)?,
+        Preset::Emblem => emblem::build(&normalized, &mut rng, opts)?,
+        Preset::Wordmark => wordmark::build(&normalized, &mut rng, opts)?,
+        Preset::LowPoly => low_poly::build(&normalized, &mut rng, opts)?,
+        Preset::Truchet => truchet::build(&normalized, &mut rng, opts)?,
+        Preset::PixelAvatar => pixel_avatar::build(&normalized, &mut rng, opts)?,
+        Preset::Sunburst => sunburst::build(&normalized, &mut rng, opts)?,
+        Preset::Isometric => isometric::build(&normalized, &mut rng, opts)?,
+        Preset::Blob => blob::build(&normalized, &mut rng, opts)?,
+        Preset::VoronoiMosaic => voronoi::build(&normalized, &mut rng, opts)?,
+        Preset::NegativeSpace => negative_space::build(&normalized, &mut rng, opts)?,
+        Preset::Topographic => topographic::build(&normalized, &mut rng, opts)?,
+        Preset::Halftone => halftone::build(&normalized, &mut rng, opts)?,
+        Preset::InterlockingRings => rings::build(&normalized, &mut rng, opts)?,
+        Preset::CircuitTraces => circuit::build(&normalized, &mut rng, opts)?,
+        Preset::Plant => plant::build(&normalized, &mut rng, opts)?,
+        Preset::StackedMonogram => stacked_monogram::build(&normalized, &mut rng, opts)?,
+    };
+
+    let scene = match opts.monochrome {
+        Some(ink) => monochrome::apply(scene, ink),
+        None => scene,
+    };
+    Ok(match opts.mask {
+        Some(mask) => apply_mask(scene, mask),
+        None => scene,
+    })
+}
+
+/// Clip everything in `scene`, background included, to `mask` fitted to the
+/// canvas. An adaptive icon leaves its background unclipped.
+fn apply_mask(scene: Scene, mask: MaskShape) -> Scene {
+    let canvas = Rect {
+        x: 0.0,
+        y: 0.0,
+        w: scene.width as f32,
+        h: scene.height as f32,
+    };
+    let (mut ops, content) = match mask {
+        MaskShape::AdaptiveIcon => scene
+            .ops
+            .into_iter()
+            .partition(|op| matches!(op, DrawOp::Background { .. })),
+        _ => (Vec::new(), scene.ops),
+    };
+    ops.push(DrawOp::Group {
+        clip: Some(mask.shape(canvas)),
+        ops: content,
+    });
+    Scene { ops, ..scene }
 }
@@ -0,0 +1,480 @@
+//! Single-ink rendering mode.
+//!
+//! Rewrites a finished scene so every palette role uses one ink color over a
+//! transparent canvas. Since overlapping shapes would otherwise merge into a
+//! single blob, each element drawn on top of existing ink is separated from
+//! it by a knocked-out gap, and text sitting on a filled shape is knocked out
+//! of it instead of being inked. Shapes painted over a filled shape in the
+//! paper color (the background, or white on transparent canvases), and lines
+//! in tints of it, are knocked out of the fill too, even from inside a group.
+
+use super::{DrawOp, Scene};
+use crate::core::geometry::{Circle, Point, Shape};
+use crate::core::palette::{delta_e, Palette, Rgb};
+
+/// Width of the knocked-out separation gap as fraction of the canvas size.
+const GAP_FRACTION: f32 = 0.012;
+
+/// Paper tone on transparent canvases, the color presets fall back to for
+/// lines and plates drawn "in the background color".
+const PAPER_FALLBACK: Rgb = Rgb {
+    r: 255,
+    g: 255,
+    b: 255,
+};
+
+/// Map every op in `scene` to `ink`, preserving shape separation. Every
+/// palette role becomes the ink, and the background is dropped.
+pub fn apply(scene: Scene, ink: Rgb) -> Scene {
+    let gap = scene.width.min(scene.height) as f32 * GAP_FRACTION;
+    let palette = &scene.palette;
+    let style = Style {
+        ink,
+        gap,
+        accents: [palette.secondary, palette.tertiary],
+        paper: palette.background.unwrap_or(PAPER_FALLBACK),
+        roles: [
+            palette.primary,
+            palette.secondary,
+            palette.tertiary,
+            palette.text_color,
+        ],
+    };
+    let mut inked = Inked::default();
+    let ops = map_ops(scene.ops, &style, &mut inked)
+        .into_iter()
+        .map(Mapped::into_op)
+        .collect();
+    Scene {
+        ops,
+        palette: Palette {
+            background: None,
+            primary: ink,
+            secondary: ink,
+            tertiary: ink,
+            text_color: ink,
+        },
+        ..scene
+    }
+}
+
+struct Style {
+    ink: Rgb,
+    gap: f32,
+    /// Colors of `Pixels` cells that are left empty instead of inked, so
+    /// accents stay visible inside the body they sit on.
+    accents: [Rgb; 2],
+    /// Tone of the canvas the preset was drawn on.
+    paper: Rgb,
+    /// Palette roles, which are always inked.
+    roles: [Rgb; 4],
+}
+
+impl Style {
+    /// Whether a line in `color` drawn over a fill in `under` stands for
+    /// paper showing through: the paper itself, or any color but a palette
+    /// role that is closer to the paper than to the fill, such as lines
+    /// tinted from the background.
+    fn is_paper_line(&self, color: Rgb, under: Rgb) -> bool {
+        color == self.paper
+            || (!self.roles.contains(&color) && delta_e(color, self.paper) < delta_e(color, under))
+    }
+}
+
+/// A filled shape laid down so far, in its original color.
+struct Fill {
+    shape: Shape,
+    color: Rgb,
+    /// Knocked out of the ink below rather than inked.
+    paper: bool,
+}
+
+/// Ink laid down so far, across all layers.
+#[derive(Default)]
+struct Inked {
+    fills: Vec<Fill>,
+    /// Inked shapes, each with the distance its ink reaches past the shape.
+    shapes: Vec<(Shape, f32)>,
+}
+
+impl Inked {
+    /// Whether any ink lies within `reach` of `shape`.
+    fn near(&self, shape: &Shape, reach: f32) -> bool {
+        self.shapes.iter().any(|(s, r)| near(s, shape, r + reach))
+    }
+
+    /// Color of the topmost fill within `reach` of `shape`, if inked.
+    fn ink_under(&self, shape: &Shape, reach: f32) -> Option<Rgb> {
+        self.fills
+            .iter()
+            .rev()
+            .find(|f| near(&f.shape, shape, reach))
+            .filter(|f| !f.paper)
+            .map(|f| f.color)
+    }
+}
+
+/// An op of the single-ink scene.
+enum Mapped {
+    /// Drawn into the current layer.
+    Op(DrawOp),
+    /// Paper showing through: erases its coverage from every layer below,
+    /// not just the group it was drawn in.
+    Paper(DrawOp),
+}
+
+impl Mapped {
+    fn into_op(self) -> DrawOp {
+        match self {
+            Mapped::Op(op) => op,
+            Mapped::Paper(op) => DrawOp::Knockout { op: Box::new(op) },
+        }
+    }
+}
+
+/// Whether `a` and `b` come within `d` of each other: exact for two
+/// circles, by bounding boxes otherwise.
+fn near(a: &Shape, b: &Shape, d: f32) -> bool {
+    if let (Shape::Circle(a), Shape::Circle(b)) = (a, b) {
+        return (a.cx - b.cx).hypot(a.cy - b.cy) < a.r + b.r + d;
+    }
+    let (a, b) = (a.bounds(), b.bounds());
+    a.x < b.x + b.w + d && b.x < a.x + a.w + d && a.y < b.y + b.h + d && b.y < a.y + a.h + d
+}
+
+fn map_ops(ops: Vec<DrawOp>, style: &Style, inked: &mut Inked) -> Vec<Mapped> {
+    let (ink, gap) = (style.ink, style.gap);
+    let mut out = Vec::with_capacity(ops.len() * 2);
+
+    for op in ops {
+        match op {
+            DrawOp::Background { .. } => out.push(Mapped::Op(DrawOp::Background { color: None })),
+            // Plates and the like in the paper itself.
+            DrawOp::ShapeFill { shape, color }
+                if color == style.paper && inked.ink_under(&shape, 0.0).is_some() =>
+            {
+                out.push(Mapped::Paper(DrawOp::ShapeFill {
+                    shape: shape.clone(),
+                    color: ink,
+                }));
+                inked.fills.push(Fill {
+                    shape,
+                    color,
+                    paper: true,
+                });
+            }
+            DrawOp::ShapeStroke {
+                shape,
+                color,
+                width,
+            } if inked
+                .ink_under(&shape, width / 2.0)
+                .is_some_and(|under| style.is_paper_line(color, under)) =>
+            {
+                out.push(Mapped::Paper(DrawOp::ShapeStroke {
+                    shape,
+                    color: ink,
+                    width,
+                }));
+            }
+            DrawOp::ShapeFill { shape, color } => {
+                // Only shapes touching earlier ink need a gap cut around
+                // them. Circles cut one larger circle, which keeps runs of
+                // halftone dots compact in SVG output.
+                if inked.near(&shape, 0.0) {
+                    let cut = match &shape {
+                        Shape::Circle(c) => DrawOp::ShapeFill {
+                            shape: Shape::Circle(Circle { r: c.r + gap, ..*c }),
+                            color: ink,
+                        },
+                        _ => DrawOp::ShapeStroke {
+                            shape: shape.clone(),
+                            color: ink,
+                            width: 2.0 * gap,
+                        },
+                    };
+                    out.push(Mapped::Op(DrawOp::Knockout { op: Box::new(cut) }));
+                }
+                out.push(Mapped::Op(DrawOp::ShapeFill {
+                    shape: shape.clone(),
+                    color: ink,
+                }));
+                inked.fills.push(Fill {
+                    shape: shape.clone(),
+                    color,
+                    paper: false,
+                });
+                inked.shapes.push((shape, 0.0));
+            }
+            DrawOp::ShapeStroke { shape, width, .. } => {
+                if inked.near(&shape, width / 2.0) {
+                    out.push(Mapped::Op(DrawOp::Knockout {
+                        op: Box::new(DrawOp::ShapeStroke {
+                            shape: shape.clone(),
+                            color: ink,
+                            width: width + 2.0 * gap,
+                        }),
+                    }));
+                }
+                out.push(Mapped::Op(DrawOp::ShapeStroke {
+                    shape: shape.clone(),
+                    color: ink,
+                    width,
+                }));
+                inked.shapes.push((shape, width / 2.0));
+            }
+            DrawOp::Text { x, y, ref path, .. } => {
+                // Text set along a path is judged by the middle of the path.
+                let anchor = match path {
+                    Some(path) => path
+                        .point_at_length(path.length() / 2.0)
+                        .map_or(Point { x, y }, |(p, _)| p),
+                    None => Point { x, y },
+                };
+                // On the topmost fill under it, which may be paper.
+                let on_ink = inked
+                    .fills
+                    .iter()
+                    .rev()
+                    .find(|f| f.shape.contains(anchor))
+                    .is_some_and(|f| !f.paper);
+                if on_ink {
+                    out.push(Mapped::Op(DrawOp::Knockout {
+                        op: Box::new(op.recolored(ink)),
+                    }));
+                } else {
+                    out.push(Mapped::Op(op.recolored(ink)));
+                }
+            }
+            DrawOp::Pixels {
+                x,
+                y,
+                cell,
+                cols,
+                cells,
+            } => out.push(Mapped::Op(DrawOp::Pixels {
+                x,
+                y,
+                cell,
+                cols,
+                cells: cells
+                    .into_iter()
+                    .map(|c| c.filter(|c| !style.accents.contains(c)).map(|_| ink))
+                    .collect(),
+            })),
+            DrawOp::Knockout { op } => out.push(Mapped::Op(DrawOp::Knockout {
+                op: Box::new(op.recolored(ink)),
+            })),
+            DrawOp::Group { clip, ops } => {
+                // Split the group where paper shows through, so the paper
+                // runs, clipped like the group, also erase the layers below.
+                let group = |op| DrawOp::Group {
+                    clip: clip.clone(),
+                    ops: vec![op],
+                };
+                let mut runs: Vec<Mapped> = Vec::new();
+                for mapped in map_ops(ops, style, inked) {
+                    match (runs.last_mut(), mapped) {
+                        (Some(Mapped::Op(DrawOp::Group { ops, .. })), Mapped::Op(op))
+                        | (Some(Mapped::Paper(DrawOp::Group { ops, .. })), Mapped::Paper(op)) => {
+                            ops.push(op)
+                        }
+                        (_, Mapped::Op(op)) => runs.push(Mapped::Op(group(op))),
+                        (_, Mapped::Paper(op)) => runs.push(Mapped::Paper(group(op))),
+                    }
+                }
+                if runs.is_empty() {
+                    runs.push(Mapped::Op(DrawOp::Group {
+                        clip: clip.clone(),
+                        ops: Vec::new(),
+                    }));
+                }
+                out.extend(runs);
+            }
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::apply;
+    use crate::algorithms::{build_scene, DrawOp, Scene};
+    use crate::core::geometry::{Rect, Shape};
+    use crate::core::palette::{Palette, Rgb};
+    use crate::{LoGen, Preset, RenderOptions};
+
+    const INK: Rgb = Rgb {
+        r: 20,
+        g: 30,
+        b: 40,
+    };
+
+    fn op_colors(op: &DrawOp) -> Vec<Rgb> {
+        match op {
+            DrawOp::Background { color } => color.iter().copied().collect(),
+            DrawOp::ShapeFill { color, .. }
+            | DrawOp::ShapeStroke { color, .. }
+            | DrawOp::Text { color, .. } => vec![*color],
+            DrawOp::Pixels { cells, .. } => cells.iter().flatten().copied().collect(),
+            DrawOp::Knockout { op } => op_colors(op),
+            DrawOp::Group { ops, .. } => ops.iter().flat_map(op_colors).collect(),
+        }
+    }
+
+    #[test]
+    fn every_op_uses_the_ink() {
+        for preset in Preset::all() {
+            let scene = build_scene("Acme Power", preset, &RenderOptions::default()).unwrap();
+            let mono = apply(scene, INK);
+            for op in &mono.ops {
+                assert!(op_colors(op).iter().all(|c| *c == INK), "{:?}", op);
+            }
+        }
+    }
+
+    #[test]
+    fn badge_text_is_knocked_out() {
+        let scene = build_scene(
+            "Acme Power",
+            Preset::MonogramBadge,
+            &RenderOptions::default(),
+        )
+        .unwrap();
+        let mono = apply(scene, INK);
+        assert!(mono.ops.iter().any(|op| matches!(
+            op,
+            DrawOp::Knockout { op } if matches!(**op, DrawOp::Text { .. })
+        )));
+    }
+
+    #[test]
+    fn only_shapes_touching_earlier_ink_are_knocked_out() {
+        let square = |x: f32| DrawOp::ShapeFill {
+            shape: Shape::Rect {
+                rect: Rect {
+                    x,
+                    y: 10.0,
+                    w: 20.0,
+                    h: 20.0,
+                },
+                rx: 0.0,
+                ry: 0.0,
+            },
+            color: Rgb { r: 200, g: 0, b: 0 },
+        };
+        let scene = Scene {
+            width: 128,
+            height: 64,
+            ops: vec![square(10.0), square(60.0), square(20.0)],
+            palette: build_scene("Acme Power", Preset::Blob, &RenderOptions::default())
+                .unwrap()
+                .palette,
+        };
+        let kinds: Vec<&str> = apply(scene, INK)
+            .ops
+            .iter()
+            .map(|op| match op {
+                DrawOp::Knockout { .. } => "knockout",
+                _ => "fill",
+            })
+            .collect();
+        assert_eq!(kinds, ["fill", "fill", "knockout", "fill"]);
+    }
+
+    #[test]
+    fn pixel_accents_are_left_empty() {
+        let scene =
+            build_scene("Player One", Preset::PixelAvatar, &RenderOptions::default()).unwrap();
+        let accents = [scene.palette.secondary, scene.palette.tertiary];
+        let cells = |scene: &Scene| match scene
+            .ops
+            .iter()
+            .find(|op| matches!(op, DrawOp::Pixels { .. }))
+        {
+            Some(DrawOp::Pixels { cells, .. }) => cells.clone(),
+            _ => panic!("expected pixels"),
+        };
+        let before = cells(&scene);
+        let after = cells(&apply(scene, INK));
+        assert!(before.iter().flatten().any(|c| accents.contains(c)));
+        for (b, a) in before.iter().zip(&after) {
+            let expected = b.filter(|c| !accents.contains(c)).map(|_| INK);
+            assert_eq!(*a, expected);
+        }
+    }
+
+    #[test]
+    fn truchet_lines_are_knocked_out_of_the_badge() {
+        let raster = |monochrome| {
+            let opts = RenderOptions {
+                size_px: 128,
+                monochrome,
+                ..Default::default()
+            };
+            let png = LoGen::generate_png("Maze Works", Preset::Truchet, &opts).unwrap();
+            image::load_from_memory(&png).unwrap().to_rgba8()
+        };
+        let (color, mono) = (raster(None), raster(Some(INK)));
+        let palette = build_scene("Maze Works", Preset::Truchet, &RenderOptions::default())
+            .unwrap()
+            .palette;
+        let rgba = |c: Rgb| [c.r, c.g, c.b, 255];
+        let (line, badge) = (rgba(palette.background.unwrap()), rgba(palette.primary));
+
+        // Inside the badge, line pixels show the paper and the rest is
+        // inked.
+        let (mut lines, mut inked) = (0, 0);
+        for (x, y, px) in color.enumerate_pixels() {
+            if !(40..88).contains(&x) || !(40..88).contains(&y) {
+                continue;
+            }
+            let alpha = mono.get_pixel(x, y)[3];
+            if px.0 == line {
+                lines += 1;
+                assert_eq!(alpha, 0, "line pixel at {x},{y} is inked");
+            } else if px.0 == badge {
+                inked += 1;
+                assert!(alpha > 0, "badge pixel at {x},{y} is not inked");
+            }
+        }
+        assert!(lines > 0 && inked > 0);
+    }
+
+    #[test]
+    fn tinted_contours_are_knocked_out_of_the_badge() {
+        let scene =
+            build_scene("Maze Works", Preset::Topographic, &RenderOptions::default()).unwrap();
+        let mono = apply(scene, INK);
+        // The badge, then every contour as paper, none inked.
+        assert!(matches!(mono.ops[1], DrawOp::ShapeFill { .. }));
+        for op in &mono.ops[2..] {
+            let DrawOp::Knockout { op } = op else {
+                panic!("expected a knockout, got {:?}", op);
+            };
+            assert!(matches!(**op, DrawOp::Group { clip: Some(_), .. }));
+        }
+    }
+
+    #[test]
+    fn palette_roles_become_the_ink() {
+        let scene = build_scene(
+            "Acme Power",
+            Preset::MonogramBadge,
+            &RenderOptions::default(),
+        )
+        .unwrap();
+        let Palette {
+            background,
+            primary,
+            secondary,
+            tertiary,
+            text_color,
+        } = apply(scene, INK).palette;
+        assert_eq!(background, None);
+        assert!([primary, secondary, tertiary, text_color]
+            .iter()
+            .all(|c| *c == INK));
+    }
+}
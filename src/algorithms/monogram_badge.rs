@@ -1,32 +1,79 @@
 use rand::Rng;
 
+use super::params::{self, Param};
 use super::{DrawOp, Scene};
-use crate::core::{geometry, palette, typography};
+use crate::core::{geometry, typography};
 use crate::{LoGenError, RenderOptions};
 
-/// Probability of generating a circular badge instead of rounded rectangle.
-const CIRCLE_PROBABILITY: f64 = 0.35;
-
-/// Probability of adding a border/stroke to the badge.
-const BORDER_PROBABILITY: f64 = 0.5;
-
-/// Border width as fraction of shape size.
-const BORDER_WIDTH_FRACTION: f32 = 0.025;
-
-/// Minimum corner radius as fraction of badge width.
-const MIN_CORNER_RADIUS: f32 = 0.16;
-/// Maximum corner radius as fraction of badge width.
-const MAX_CORNER_RADIUS: f32 = 0.22;
-
-/// Minimum font size as fraction of canvas width.
-const MIN_FONT_SIZE: f32 = 0.52;
-/// Maximum font size as fraction of canvas width.
-const MAX_FONT_SIZE: f32 = 0.62;
+const CIRCLE_PROBABILITY: Param = Param::float(
+    "circle_probability",
+    0.0,
+    1.0,
+    0.35,
+    "Chance of a circular badge instead of a rounded square",
+);
+
+const BORDER_PROBABILITY: Param = Param::float(
+    "border_probability",
+    0.0,
+    1.0,
+    0.5,
+    "Chance of a border inside the badge",
+);
+
+const BORDER_WIDTH_FRACTION: Param = Param::float(
+    "border_width",
+    0.0,
+    0.1,
+    0.025,
+    "Border width as fraction of the badge size",
+);
+
+const MIN_CORNER_RADIUS: Param = Param::float(
+    "min_corner_radius",
+    0.0,
+    0.5,
+    0.16,
+    "Smallest corner radius as fraction of the badge width",
+);
+const MAX_CORNER_RADIUS: Param = Param::float(
+    "max_corner_radius",
+    0.0,
+    0.5,
+    0.22,
+    "Largest corner radius as fraction of the badge width",
+);
+
+const MIN_FONT_SIZE: Param = Param::float(
+    "min_font_size",
+    0.2,
+    0.9,
+    0.52,
+    "Smallest font size as fraction of the canvas",
+);
+const MAX_FONT_SIZE: Param = Param::float(
+    "max_font_size",
+    0.2,
+    0.9,
+    0.62,
+    "Largest font size as fraction of the canvas",
+);
+
+/// Parameters `RenderOptions::params` can override.
+pub const PARAMS: &[Param] = &[
+    CIRCLE_PROBABILITY,
+    BORDER_PROBABILITY,
+    BORDER_WIDTH_FRACTION,
+    MIN_CORNER_RADIUS,
+    MAX_CORNER_RADIUS,
+    MIN_FONT_SIZE,
+    MAX_FONT_SIZE,
+];
 
 /// Baseline adjustment factor for vertical text centering.
 const TEXT_BASELINE_ADJUST: f32 = 0.35;
 
-fn initials_from_normalized(s: &str) -> String {
+pub(crate) fn initials_from_normalized(s: &str) -> String {
     // If input contains multiple words, take the first alnum letter of the
     // first two words (classic initials). If the input is a single word,
     // include up to three alphanumeric characters from that word (so
@@ -62,6 +109,16 @@ fn initials_from_normalized(s: &str) -> String {
     init.to_uppercase()
 }
 
+/// Largest circle centered in the padded canvas; shared with presets that
+/// build on the circular badge.
+pub(crate) fn badge_circle(inner: &geometry::Rect) -> geometry::Circle {
+    geometry::Circle {
+        cx: inner.x + inner.w / 2.0,
+        cy: inner.y + inner.h / 2.0,
+        r: inner.w.min(inner.h) / 2.0,
+    }
+}
+
 /// Simple "Monogram Badge" preset: rounded rect + initials.
 /// All choices are deterministic via the supplied RNG.
 pub fn build<R: Rng>(
@@ -69,31 +126,25 @@ pub fn build<R: Rng>(
     rng: &mut R,
     opts: &RenderOptions,
 ) -> Result<Scene, LoGenError> {
-    let size = opts.size_px;
-    let w = size as f32;
-    let h = size as f32;
-
-    let pad = (opts.padding_frac * w).round();
-    let inner = geometry::Rect {
-        x: pad,
-        y: pad,
-        w: w - 2.0 * pad,
-        h: h - 2.0 * pad,
-    };
+    let (width, height) = opts.canvas_size();
+    let w = width as f32;
+    let h = height as f32;
+    // The badge stays square and centered on non-square canvases.
+    let inner = super::centered_square(opts);
 
-    let palette = palette::derive_palette(rng, opts.transparent_background);
+    let palette = super::palette_for(rng, opts);
     let typo = typography::Typography::default();
 
     // Badge shape variation (rounded rect vs circle) — keep constrained.
-    let use_circle = rng.gen_bool(CIRCLE_PROBABILITY);
+    let use_circle = rng.gen_bool(CIRCLE_PROBABILITY.value(opts));
     let badge_shape = if use_circle {
-        geometry::Shape::Circle(geometry::Circle {
-            cx: w / 2.0,
-            cy: h / 2.0,
-            r: (inner.w.min(inner.h) / 2.0),
-        })
+        geometry::Shape::Circle(badge_circle(&inner))
     } else {
-        let rx = rng.gen_range(MIN_CORNER_RADIUS..MAX_CORNER_RADIUS) * inner.w;
+        let rx = params::gen_between(
+            rng,
+            MIN_CORNER_RADIUS.value_f32(opts),
+            MAX_CORNER_RADIUS.value_f32(opts),
+        ) * inner.w;
         geometry::Shape::Rect {
             rect: inner,
             rx,
@@ -102,22 +153,26 @@ pub fn build<R: Rng>(
     };
 
     let initials = initials_from_normalized(normalized);
-    let font_size = rng.gen_range(MIN_FONT_SIZE..MAX_FONT_SIZE) * w;
+    let font_size = params::gen_between(
+        rng,
+        MIN_FONT_SIZE.value_f32(opts),
+        MAX_FONT_SIZE.value_f32(opts),
+    ) * w.min(h);
 
-    let add_border = rng.gen_bool(BORDER_PROBABILITY);
+    let add_border = rng.gen_bool(BORDER_PROBABILITY.value(opts));
     let mut ops = vec![
         DrawOp::Background {
             color: palette.background,
         },
         DrawOp::ShapeFill {
-            shape: badge_shape,
+            shape: badge_shape.clone(),
             color: palette.primary,
         },
     ];
 
     // Optionally add a border
     if add_border {
-        let border_width = w * BORDER_WIDTH_FRACTION;
+        let border_width = w.min(h) * BORDER_WIDTH_FRACTION.value_f32(opts);
         ops.push(DrawOp::ShapeStroke {
             shape: badge_shape,
             color: palette.tertiary,
@@ -135,12 +190,16 @@ pub fn build<R: Rng>(
         font_size,
         color: palette.secondary,
         anchor_middle: true,
+        letter_spacing: 0.0,
+        text_length: None,
+        path: None,
     });
 
     Ok(Scene {
-        width: size,
-        height: size,
+        width,
+        height,
         ops,
+        palette,
     })
 }
 
@@ -176,8 +235,7 @@ mod tests {
         let opts = RenderOptions {
             size_px: 128,
             padding_frac: 0.1,
-            variant: None,
-            transparent_background: false,
+            ..Default::default()
         };
         let scene = build("Alice", &mut rng, &opts).expect("build failed");
         // there should be a text op present and the width/height match
@@ -0,0 +1,289 @@
+use rand::Rng;
+
+use super::monogram_badge::initials_from_normalized;
+use super::params::Param;
+use super::{DrawOp, Scene};
+use crate::core::geometry::{Circle, Path, Point, Rect, Shape};
+use crate::core::{boolean, glyphs};
+use crate::{LoGenError, RenderOptions};
+
+const CIRCLE_PROBABILITY: Param = Param::float(
+    "circle_probability",
+    0.0,
+    1.0,
+    0.5,
+    "Chance of a circular badge instead of a rounded square",
+);
+const CORNER_RADIUS: Param = Param::float(
+    "corner_radius",
+    0.0,
+    0.5,
+    0.18,
+    "Corner radius of the rounded square badge as fraction of its side",
+);
+
+const MIN_PIECES: Param = Param::int("min_pieces", 1, 8, 2, "Fewest solid pieces");
+const MAX_PIECES: Param = Param::int("max_pieces", 1, 8, 4, "Most solid pieces");
+const GUTTER: Param = Param::float(
+    "gutter",
+    0.0,
+    0.1,
+    0.03,
+    "Gap between pieces as fraction of the badge side",
+);
+
+/// How far cutters reach past the badge, as fraction of its side, so no
+/// cutter edge lies along a badge edge.
+const OVERREACH: f32 = 0.05;
+
+/// Height of the letter's outline as fraction of the badge side.
+const LETTER_SIZE: f32 = 0.62;
+/// Font size the letter is first laid out at to measure it.
+const NOMINAL_SIZE: f32 = 100.0;
+
+const MONO_PROBABILITY: Param = Param::float(
+    "mono_probability",
+    0.0,
+    1.0,
+    0.5,
+    "Chance of every piece in the primary color",
+);
+
+/// Parameters `RenderOptions::params` can override.
+pub const PARAMS: &[Param] = &[
+    CIRCLE_PROBABILITY,
+    CORNER_RADIUS,
+    MIN_PIECES,
+    MAX_PIECES,
+    GUTTER,
+    MONO_PROBABILITY,
+];
+
+/// Negative-space lettermark: a badge cut into solid pieces by thin
+/// gutters, with the first initial carved out of them, so the letter is
+/// read from the gaps between the shapes rather than drawn on top.
+pub fn build<R: Rng>(
+    normalized: &str,
+    rng: &mut R,
+    opts: &RenderOptions,
+) -> Result<Scene, LoGenError> {
+    let (width, height) = opts.canvas_size();
+    let square = super::centered_square(opts);
+    let palette = super::palette_for(rng, opts);
+    let side = square.w;
+
+    let badge = if rng.gen_bool(CIRCLE_PROBABILITY.value(opts)) {
+        Shape::Circle(Circle {
+            cx: square.x + side / 2.0,
+            cy: square.y + side / 2.0,
+            r: side / 2.0,
+        })
+    } else {
+        Shape::Rect {
+            rect: square,
+            rx: side * CORNER_RADIUS.value_f32(opts),
+            ry: side * CORNER_RADIUS.value_f32(opts),
+        }
+    }
+    .to_path();
+
+    let count = rng.gen_range(MIN_PIECES.value_usize(opts)..=MAX_PIECES.value_usize(opts));
+    let vertical = rng.gen_bool(0.5);
+    let mono = rng.gen_bool(MONO_PROBABILITY.value(opts));
+
+    // The gaps: the letter plus a gutter on every boundary between pieces.
+    let letter = letter_outline(normalized, square);
+    let reach = side * OVERREACH;
+    let band = side / count as f32;
+    let gutter = side * GUTTER.value_f32(opts);
+    let gaps = (1..count).fold(letter, |gaps, i| {
+        let at = i as f32 * band;
+        let cut = if vertical {
+            rect_path(Rect {
+                x: square.x + at - gutter / 2.0,
+                y: square.y - reach,
+                w: gutter,
+                h: side + 2.0 * reach,
+            })
+        } else {
+            rect_path(Rect {
+                x: square.x - reach,
+                y: square.y + at - gutter / 2.0,
+                w: side + 2.0 * reach,
+                h: gutter,
+            })
+        };
+        boolean::union(&gaps, &cut)
+    });
+
+    let colors = [palette.primary, palette.secondary, palette.tertiary];
+    let mut ops = vec![DrawOp::Background {
+        color: palette.background,
+    }];
+    for i in 0..count {
+        // Outer bands reach past the badge; inner boundaries sit inside the
+        // gutters, so no band edge lies along another operand's edge.
+        let start = if i == 0 { -reach } else { i as f32 * band };
+        let end = if i + 1 == count {
+            side + reach
+        } else {
+            (i + 1) as f32 * band
+        };
+        let band_rect = if vertical {
+            Rect {
+                x: square.x + start,
+                y: square.y - reach,
+                w: end - start,
+                h: side + 2.0 * reach,
+            }
+        } else {
+            Rect {
+                x: square.x - reach,
+                y: square.y + start,
+                w: side + 2.0 * reach,
+                h: end - start,
+            }
+        };
+        let piece =
+            boolean::difference(&boolean::intersection(&badge, &rect_path(band_rect)), &gaps);
+        if piece.cmds.is_empty() {
+            continue;
+        }
+        ops.push(DrawOp::ShapeFill {
+            shape: Shape::Path(piece),
+            color: if mono {
+                palette.primary
+            } else {
+                colors[i % colors.len()]
+            },
+        });
+    }
+
+    Ok(Scene {
+        width,
+        height,
+        ops,
+        palette,
+    })
+}
+
+/// Outline of the first initial, sized and centered in `square`.
+fn letter_outline(normalized: &str, square: Rect) -> Path {
+    let ch = initials_from_normalized(normalized)
+        .to_uppercase()
+        .chars()
+        .next()
+        .unwrap_or('?');
+    let font = glyphs::geometry_font();
+
+    // Measure at a nominal size, then build again at the fitted size so
+    // curves are flattened at the resolution they are cut at.
+    let measured = flattened_bounds(&glyphs::glyph(font, ch, NOMINAL_SIZE).outline);
+    let font_size = NOMINAL_SIZE * LETTER_SIZE * square.w / measured.h.max(f32::EPSILON);
+    let outline = glyphs::glyph(font, ch, font_size).outline;
+    let b = flattened_bounds(&outline);
+    let dx = square.x + square.w / 2.0 - (b.x + b.w / 2.0);
+    let dy = square.y + square.h / 2.0 - (b.y + b.h / 2.0);
+    outline.map(|p| Point::new(p.x + dx, p.y + dy))
+}
+
+fn flattened_bounds(path: &Path) -> Rect {
+    let mut min = Point::new(f32::MAX, f32::MAX);
+    let mut max = Point::new(f32::MIN, f32::MIN);
+    for (poly, _) in path.flatten() {
+        for p in poly {
+            min = Point::new(min.x.min(p.x), min.y.min(p.y));
+            max = Point::new(max.x.max(p.x), max.y.max(p.y));
+        }
+    }
+    if min.x > max.x {
+        return Rect {
+            x: 0.0,
+            y: 0.0,
+            w: 0.0,
+            h: 0.0,
+        };
+    }
+    Rect {
+        x: min.x,
+        y: min.y,
+        w: max.x - min.x,
+        h: max.y - min.y,
+    }
+}
+
+fn rect_path(rect: Rect) -> Path {
+    Shape::Rect {
+        rect,
+        rx: 0.0,
+        ry: 0.0,
+    }
+    .to_path()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::build;
+    use crate::algorithms::{DrawOp, Scene};
+    use crate::core::geometry::{Point, Shape};
+    use crate::RenderOptions;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn scene(seed: u64) -> Scene {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        build("Harbor", &mut rng, &RenderOptions::default()).expect("build failed")
+    }
+
+    fn pieces(scene: &Scene) -> Vec<&Shape> {
+        scene
+            .ops
+            .iter()
+            .filter_map(|op| match op {
+                DrawOp::ShapeFill { shape, .. } => Some(shape),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn pieces_stay_inside_the_badge() {
+        for seed in 0..8 {
+            let s = scene(seed);
+            let shapes = pieces(&s);
+            assert!((2..=4).contains(&shapes.len()), "seed {seed}");
+            for shape in shapes {
+                let Shape::Path(path) = shape else {
+                    panic!("expected a path piece");
+                };
+                for (poly, _) in path.flatten() {
+                    for p in poly {
+                        // The padded square spans 61..451.
+                        assert!(p.x > 60.0 && p.x < 452.0, "seed {seed}: {p:?}");
+                        assert!(p.y > 60.0 && p.y < 452.0, "seed {seed}: {p:?}");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn letter_is_carved_out_of_the_pieces() {
+        // The block font's H has its crossbar on the
+        // center line, and the middle of its left stem is left of it.
+        for seed in 0..8 {
+            let s = scene(seed);
+            let shapes = pieces(&s);
+            let stem = Point::new(256.0 - 0.62 * 390.0 * 5.0 / 7.0 / 2.0 + 5.0, 230.0);
+            assert!(
+                shapes.iter().all(|shape| !shape.contains(stem)),
+                "seed {seed}: stem is filled"
+            );
+            // Beside the letter the badge is solid; band boundaries fall at
+            // thirds, quarters or halves of the padded square, clear of y 230.
+            for p in [Point::new(80.0, 230.0), Point::new(432.0, 230.0)] {
+                assert!(shapes.iter().any(|shape| shape.contains(p)), "seed {seed}");
+            }
+        }
+    }
+}
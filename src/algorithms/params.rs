@@ -0,0 +1,217 @@
+//! Typed, introspectable preset parameters.
+//!
+//! Each preset lists its tunables as [`Param`]s: a name, a value type, an
+//! inclusive range and a default. Callers override them by name through
+//! `RenderOptions::params`, and `build_scene` rejects unknown names and
+//! out-of-range values before any preset runs. A `min_*` parameter may not
+//! exceed the preset's matching `max_*` one.
+
+use std::collections::BTreeMap;
+
+use rand::Rng;
+
+use crate::{LoGenError, RenderOptions};
+
+/// Value type of a parameter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParamKind {
+    /// Whole numbers, e.g. counts.
+    Int,
+    /// Real numbers, e.g. probabilities and size fractions.
+    Float,
+}
+
+impl ParamKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ParamKind::Int => "int",
+            ParamKind::Float => "float",
+        }
+    }
+}
+
+/// One tunable of a preset. Values of either kind are carried as `f64`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Param {
+    pub name: &'static str,
+    pub kind: ParamKind,
+    pub min: f64,
+    pub max: f64,
+    pub default: f64,
+    pub description: &'static str,
+}
+
+impl Param {
+    pub const fn int(
+        name: &'static str,
+        min: i64,
+        max: i64,
+        default: i64,
+        description: &'static str,
+    ) -> Self {
+        Param {
+            name,
+            kind: ParamKind::Int,
+            min: min as f64,
+            max: max as f64,
+            default: default as f64,
+            description,
+        }
+    }
+
+    pub const fn float(
+        name: &'static str,
+        min: f64,
+        max: f64,
+        default: f64,
+        description: &'static str,
+    ) -> Self {
+        Param {
+            name,
+            kind: ParamKind::Float,
+            min,
+            max,
+            default,
+            description,
+        }
+    }
+
+    /// The override from `opts`, or the default.
+    pub fn value(&self, opts: &RenderOptions) -> f64 {
+        opts.params.get(self.name).copied().unwrap_or(self.default)
+    }
+
+    /// The override from `opts`, if any. For parameters whose default only
+    /// describes the range: presets pick from the seed unless one is set.
+    pub fn overridden(&self, opts: &RenderOptions) -> Option<f64> {
+        opts.params.get(self.name).copied()
+    }
+
+    pub fn value_f32(&self, opts: &RenderOptions) -> f32 {
+        self.value(opts) as f32
+    }
+
+    pub fn value_usize(&self, opts: &RenderOptions) -> usize {
+        self.value(opts).round() as usize
+    }
+
+    pub fn value_u32(&self, opts: &RenderOptions) -> u32 {
+        self.value(opts).round() as u32
+    }
+
+    fn check(&self, value: f64) -> Result<(), LoGenError> {
+        if !(self.min..=self.max).contains(&value) {
+            return Err(LoGenError::InvalidOptions(format!(
+                "{} must be within [{}..{}], got {}",
+                self.name, self.min, self.max, value
+            )));
+        }
+        if self.kind == ParamKind::Int && value.fract() != 0.0 {
+            return Err(LoGenError::InvalidOptions(format!(
+                "{} must be a whole number, got {}",
+                self.name, value
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Check `overrides` against a preset's `params`.
+pub fn validate(params: &[Param], overrides: &BTreeMap<String, f64>) -> Result<(), LoGenError> {
+    for (name, &value) in overrides {
+        let param = params
+            .iter()
+            .find(|p| p.name == name)
+            .ok_or_else(|| LoGenError::InvalidOptions(format!("unknown parameter: {name}")))?;
+        param.check(value)?;
+    }
+    let value = |p: &Param| overrides.get(p.name).copied().unwrap_or(p.default);
+    for low in params {
+        let Some(rest) = low.name.strip_prefix("min_") else {
+            continue;
+        };
+        if let Some(high) = params
+            .iter()
+            .find(|p| p.name.strip_prefix("max_") == Some(rest))
+        {
+            if value(low) > value(high) {
+                return Err(LoGenError::InvalidOptions(format!(
+                    "{} must not exceed {}, got {} > {}",
+                    low.name,
+                    high.name,
+                    value(low),
+                    value(high)
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Sample from `[low..high)`, or `low` when an override made the range
+/// empty. Both cases draw the same amount from `rng`, so the rest of the
+/// mark stays put.
+pub(crate) fn gen_between<R: Rng>(rng: &mut R, low: f32, high: f32) -> f32 {
+    if low < high {
+        rng.gen_range(low..high)
+    } else {
+        let _: f32 = rng.gen();
+        low
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PARAMS: &[Param] = &[
+        Param::int("min_count", 1, 10, 2, "Fewest things"),
+        Param::int("max_count", 1, 10, 4, "Most things"),
+        Param::float("probability", 0.0, 1.0, 0.5, "Chance of a thing"),
+    ];
+
+    fn overrides(pairs: &[(&str, f64)]) -> BTreeMap<String, f64> {
+        pairs.iter().map(|&(k, v)| (k.to_string(), v)).collect()
+    }
+
+    #[test]
+    fn values_fall_back_to_defaults() {
+        let mut opts = RenderOptions::default();
+        assert_eq!(PARAMS[0].value_usize(&opts), 2);
+        opts.params = overrides(&[("min_count", 3.0)]);
+        assert_eq!(PARAMS[0].value_usize(&opts), 3);
+        assert_eq!(PARAMS[2].value(&opts), 0.5);
+    }
+
+    #[test]
+    fn validate_rejects_unknown_out_of_range_and_crossed_values() {
+        assert!(validate(
+            PARAMS,
+            &overrides(&[("probability", 0.2), ("max_count", 9.0)])
+        )
+        .is_ok());
+        for bad in [
+            overrides(&[("nope", 1.0)]),
+            overrides(&[("probability", 1.5)]),
+            overrides(&[("max_count", 2.5)]),
+            overrides(&[("min_count", 5.0)]),
+            overrides(&[("min_count", 3.0), ("max_count", 2.0)]),
+        ] {
+            assert!(
+                matches!(validate(PARAMS, &bad), Err(LoGenError::InvalidOptions(_))),
+                "{bad:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn gen_between_draws_the_same_either_way() {
+        use rand::SeedableRng;
+        use rand_chacha::ChaCha8Rng;
+        let mut a = ChaCha8Rng::seed_from_u64(1);
+        let mut b = ChaCha8Rng::seed_from_u64(1);
+        assert!((0.1..0.2).contains(&gen_between(&mut a, 0.1, 0.2)));
+        assert_eq!(gen_between(&mut b, 0.3, 0.3), 0.3);
+        assert_eq!(a.gen::<u64>(), b.gen::<u64>());
+    }
+}
@@ -0,0 +1,259 @@
+use rand::Rng;
+
+use super::params::Param;
+use super::{DrawOp, Scene};
+use crate::core::palette::Rgb;
+use crate::{LoGenError, RenderOptions};
+
+/// Coarser grids than the minimum cannot fit a face with an outline.
+const GRID_SIZE: Param = Param::int("grid_size", 8, 64, 12, "Sprite pixels per row and column");
+
+const HOLE_PROBABILITY: Param = Param::float(
+    "hole_probability",
+    0.0,
+    1.0,
+    0.12,
+    "Chance of a body cell being left empty",
+);
+const SPROUT_PROBABILITY: Param = Param::float(
+    "sprout_probability",
+    0.0,
+    1.0,
+    0.3,
+    "Chance of a cell next to the body growing onto it",
+);
+const ACCENT_PROBABILITY: Param = Param::float(
+    "accent_probability",
+    0.0,
+    1.0,
+    0.15,
+    "Chance of a body cell taking the accent color",
+);
+const MOUTH_PROBABILITY: Param = Param::float(
+    "mouth_probability",
+    0.0,
+    1.0,
+    0.6,
+    "Chance of a mouth below the eyes",
+);
+
+/// Parameters `RenderOptions::params` can override.
+pub const PARAMS: &[Param] = &[
+    GRID_SIZE,
+    HOLE_PROBABILITY,
+    SPROUT_PROBABILITY,
+    ACCENT_PROBABILITY,
+    MOUTH_PROBABILITY,
+];
+
+/// How far the outline color is darkened towards black.
+const OUTLINE_SHADE: f32 = 0.55;
+const BLACK: Rgb = Rgb { r: 0, g: 0, b: 0 };
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Pixel {
+    Empty,
+    Body,
+    Accent,
+    Outline,
+    /// Left empty, so the canvas shows through; never outlined.
+    Eye,
+}
+
+/// 8-bit style avatar: a mirrored creature silhouette grown from seeded
+/// rules on a coarse grid, outlined, with eyes and an optional mouth, drawn
+/// as crisp pixels.
+pub fn build<R: Rng>(
+    _normalized: &str,
+    rng: &mut R,
+    opts: &RenderOptions,
+) -> Result<Scene, LoGenError> {
+    let (width, height) = opts.canvas_size();
+    let palette = super::palette_for(rng, opts);
+
+    let n = GRID_SIZE.value_usize(opts);
+    let sprite = sprite(rng, n, opts);
+
+    // Whole-pixel cells keep every sprite pixel the same size.
+    let square = super::centered_square(opts);
+    let cell = (square.w / n as f32).floor().max(1.0);
+    let x = ((width as f32 - cell * n as f32) / 2.0).round();
+    let y = ((height as f32 - cell * n as f32) / 2.0).round();
+
+    let outline = palette.primary.lerp(BLACK, OUTLINE_SHADE);
+    let cells = sprite
+        .iter()
+        .map(|p| match p {
+            Pixel::Body => Some(palette.primary),
+            Pixel::Accent => Some(palette.secondary),
+            Pixel::Outline => Some(outline),
+            Pixel::Empty | Pixel::Eye => None,
+        })
+        .collect();
+
+    let ops = vec![
+        DrawOp::Background {
+            color: palette.background,
+        },
+        DrawOp::Pixels {
+            x,
+            y,
+            cell,
+            cols: n,
+            cells,
+        },
+    ];
+
+    Ok(Scene {
+        width,
+        height,
+        ops,
+        palette,
+    })
+}
+
+/// Mirrored `n`×`n` sprite in row-major order. The outermost ring is kept
+/// free for the outline.
+fn sprite<R: Rng>(rng: &mut R, n: usize, opts: &RenderOptions) -> Vec<Pixel> {
+    let mut px = vec![Pixel::Empty; n * n];
+    let inner = n - 2;
+    // Columns up to and including the middle one on odd grids.
+    let half = n.div_ceil(2);
+
+    // Silhouette: each row spans a half-width that random-walks from the
+    // row above, so the outline reads as one creature rather than noise.
+    let mut half_width = rng.gen_range(1..=inner / 2);
+    let mut spans = Vec::with_capacity(inner);
+    for _ in 0..inner {
+        half_width =
+            (half_width as i32 + rng.gen_range(-1..=1)).clamp(1, (inner / 2) as i32) as usize;
+        spans.push(half_width);
+    }
+
+    // Only the left half is decided; `set` mirrors it.
+    let set = |px: &mut Vec<Pixel>, row: usize, col: usize, p: Pixel| {
+        px[row * n + col] = p;
+        px[row * n + (n - 1 - col)] = p;
+    };
+
+    for (i, &span) in spans.iter().enumerate() {
+        let row = i + 1;
+        for col in 1..half {
+            let inside = col >= half - span;
+            let above = row > 1 && px[(row - 1) * n + col] != Pixel::Empty;
+            let beside = col + 1 < half && px[row * n + col + 1] != Pixel::Empty;
+            let body = if inside {
+                !rng.gen_bool(HOLE_PROBABILITY.value(opts))
+            } else {
+                (above || beside) && rng.gen_bool(SPROUT_PROBABILITY.value(opts))
+            };
+            if body {
+                let accent = rng.gen_bool(ACCENT_PROBABILITY.value(opts));
+                set(
+                    &mut px,
+                    row,
+                    col,
+                    if accent { Pixel::Accent } else { Pixel::Body },
+                );
+            }
+        }
+    }
+
+    // Eyes in the upper half, on the widest rows there, with solid body
+    // around them so they read as eyes rather than holes.
+    let eye_row = (2..inner / 2)
+        .max_by_key(|&row| (spans[row - 1], std::cmp::Reverse(row)))
+        .unwrap_or(2);
+    let eye_col = (half - 1 - rng.gen_range(1..=2)).max(2);
+    for row in eye_row - 1..=eye_row + 1 {
+        for col in eye_col - 1..=(eye_col + 1).min(half - 1) {
+            set(&mut px, row, col, Pixel::Body);
+        }
+    }
+    set(&mut px, eye_row, eye_col, Pixel::Eye);
+
+    if rng.gen_bool(MOUTH_PROBABILITY.value(opts)) && eye_row + 2 <= inner {
+        let mouth_row = eye_row + 2;
+        for col in eye_col..half {
+            set(&mut px, mouth_row, col, Pixel::Accent);
+        }
+        for col in (eye_col - 1).max(1)..eye_col {
+            set(&mut px, mouth_row, col, Pixel::Body);
+        }
+    }
+
+    // Outline every empty pixel touching the creature.
+    let solid = |px: &[Pixel], row: usize, col: usize| {
+        matches!(px[row * n + col], Pixel::Body | Pixel::Accent)
+    };
+    let mut outlined = px.clone();
+    for row in 0..n {
+        for col in 0..n {
+            if px[row * n + col] != Pixel::Empty {
+                continue;
+            }
+            let touches = (row > 0 && solid(&px, row - 1, col))
+                || (row + 1 < n && solid(&px, row + 1, col))
+                || (col > 0 && solid(&px, row, col - 1))
+                || (col + 1 < n && solid(&px, row, col + 1));
+            if touches {
+                outlined[row * n + col] = Pixel::Outline;
+            }
+        }
+    }
+    outlined
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{build, sprite, Pixel};
+    use crate::algorithms::DrawOp;
+    use crate::RenderOptions;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn sprite_is_mirrored_and_has_eyes() {
+        for seed in 0..32 {
+            let mut rng = ChaCha8Rng::seed_from_u64(seed);
+            let n = 12;
+            let px = sprite(&mut rng, n, &RenderOptions::default());
+            for row in 0..n {
+                for col in 0..n {
+                    assert_eq!(px[row * n + col], px[row * n + n - 1 - col], "seed {seed}");
+                }
+            }
+            assert_eq!(px.iter().filter(|p| **p == Pixel::Eye).count(), 2);
+        }
+    }
+
+    #[test]
+    fn outline_separates_creature_from_canvas() {
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+        let n = 12;
+        let px = sprite(&mut rng, n, &RenderOptions::default());
+        // The border ring only ever holds outline pixels.
+        for i in 0..n {
+            for p in [px[i], px[(n - 1) * n + i], px[i * n], px[i * n + n - 1]] {
+                assert!(matches!(p, Pixel::Empty | Pixel::Outline), "{p:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn cells_are_whole_pixels() {
+        let mut rng = ChaCha8Rng::seed_from_u64(2);
+        let opts = RenderOptions {
+            size_px: 500,
+            ..Default::default()
+        };
+        let scene = build("Player One", &mut rng, &opts).expect("build failed");
+        match &scene.ops[1] {
+            DrawOp::Pixels { x, y, cell, .. } => {
+                assert_eq!(cell.fract(), 0.0);
+                assert_eq!((x.fract(), y.fract()), (0.0, 0.0));
+            }
+            other => panic!("expected pixels, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,447 @@
+use rand::Rng;
+
+use super::params::Param;
+use super::{DrawOp, Scene};
+use crate::core::geometry::{Circle, Path, Point, Rect, Shape};
+use crate::core::lsystem::{Drawing, LSystem, Turtle};
+use crate::core::palette::Rgb;
+use crate::{LoGenError, RenderOptions};
+
+/// An L-system the plant can grow from. `X` marks growing tips: on sprigs
+/// and trees the tips left after the last rewrite become leaves.
+struct Grammar {
+    axiom: &'static str,
+    rules: &'static [(char, &'static str)],
+    iterations: usize,
+    /// Range of the main turning angle in degrees.
+    angle: (f32, f32),
+}
+
+/// Branching trees; the tips get round foliage.
+const TREES: [Grammar; 4] = [
+    Grammar {
+        axiom: "X",
+        rules: &[('X', "F[+X][-X]FX"), ('F', "FF")],
+        iterations: 5,
+        angle: (20.0, 28.0),
+    },
+    Grammar {
+        axiom: "X",
+        rules: &[('X', "F[+X]F[-X]+X"), ('F', "FF")],
+        iterations: 5,
+        angle: (18.0, 25.0),
+    },
+    Grammar {
+        axiom: "X",
+        rules: &[('X', "F[+X][-X]>X")],
+        iterations: 4,
+        angle: (24.0, 36.0),
+    },
+    Grammar {
+        axiom: "X",
+        rules: &[('X', "F[+X]F[-X][X]"), ('F', "FF")],
+        iterations: 4,
+        angle: (20.0, 28.0),
+    },
+];
+
+/// Curving stems with leaves along them.
+const SPRIGS: [Grammar; 3] = [
+    Grammar {
+        axiom: "X",
+        rules: &[('X', "F[+L]F[-L]>(X")],
+        iterations: 5,
+        angle: (40.0, 55.0),
+    },
+    Grammar {
+        axiom: "FX",
+        rules: &[('X', "F[+L][-L]>(X")],
+        iterations: 6,
+        angle: (35.0, 50.0),
+    },
+    Grammar {
+        axiom: "X",
+        rules: &[('X', "F[+F[+L][-L]L]F[-F[+L][-L]L]>(X")],
+        iterations: 3,
+        angle: (35.0, 45.0),
+    },
+];
+
+/// Vein patterns of a single leaf: a midrib with side veins, which may
+/// fork towards the edge.
+const VEINS: [Grammar; 3] = [
+    Grammar {
+        axiom: "X",
+        rules: &[('X', "F[+FF][-FF]>X")],
+        iterations: 9,
+        angle: (40.0, 55.0),
+    },
+    Grammar {
+        axiom: "X",
+        rules: &[('X', "F[+F[+F]F][-F[-F]F]>X")],
+        iterations: 8,
+        angle: (35.0, 45.0),
+    },
+    Grammar {
+        axiom: "X",
+        rules: &[('X', "F[+F>F][-F>F]F>X")],
+        iterations: 8,
+        angle: (45.0, 60.0),
+    },
+];
+
+const CIRCLE_PROBABILITY: Param = Param::float(
+    "circle_probability",
+    0.0,
+    1.0,
+    0.6,
+    "Chance of a circular badge instead of a rounded square",
+);
+
+/// Corner radius of the rounded square badge as fraction of its side.
+const CORNER_RADIUS: f32 = 0.2;
+const BADGE_TINT: Param = Param::float(
+    "badge_tint",
+    0.0,
+    1.0,
+    0.85,
+    "How far the badge is tinted from the primary color towards the background",
+);
+
+/// Fade target on transparent canvases.
+const TINT_FALLBACK: Rgb = Rgb {
+    r: 255,
+    g: 255,
+    b: 255,
+};
+
+const PLANT_SIZE: Param = Param::float(
+    "plant_size",
+    0.3,
+    0.9,
+    0.6,
+    "Size of the box the plant is fitted into, as fraction of the badge side",
+);
+const MAX_TILT: Param = Param::float(
+    "max_tilt",
+    0.1,
+    60.0,
+    30.0,
+    "Largest lean of leaves and trees in degrees",
+);
+const MAX_BEND: Param = Param::float(
+    "max_bend",
+    0.1,
+    20.0,
+    8.0,
+    "Largest bend per step of a sprig stem in degrees",
+);
+
+/// Step scale applied by `>` in the grammars.
+const SHRINK: f32 = 0.85;
+
+const STEM_WIDTH: Param = Param::float(
+    "stem_width",
+    0.005,
+    0.06,
+    0.022,
+    "Trunk width as fraction of the badge side",
+);
+
+/// Parameters `RenderOptions::params` can override.
+pub const PARAMS: &[Param] = &[
+    CIRCLE_PROBABILITY,
+    BADGE_TINT,
+    PLANT_SIZE,
+    MAX_TILT,
+    MAX_BEND,
+    STEM_WIDTH,
+];
+
+const TAPER: f32 = 0.72;
+/// Thinnest stroke in pixels.
+const MIN_WIDTH: f32 = 1.0;
+
+/// Sprig leaf length as fraction of the badge side at full step length.
+const LEAF_LENGTH: f32 = 0.11;
+/// Foliage radius in steps at the tip, kept within a range given as
+/// fraction of the badge side.
+const FOLIAGE_RADIUS: f32 = 1.2;
+const MIN_FOLIAGE: f32 = 0.012;
+const MAX_FOLIAGE: f32 = 0.03;
+/// Leaf width as fraction of its length.
+const LEAF_WIDTH: f32 = 0.42;
+/// Stalk of a single leaf, as fraction of the leaf length.
+const STALK: f32 = 0.22;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Kind {
+    Sprig,
+    Leaf,
+    Tree,
+}
+
+/// L-system plant: a sprig, a single veined leaf or a small tree grown
+/// from a grammar picked by the seed, inside a pale badge.
+pub fn build<R: Rng>(
+    _normalized: &str,
+    rng: &mut R,
+    opts: &RenderOptions,
+) -> Result<Scene, LoGenError> {
+    let (width, height) = opts.canvas_size();
+    let square = super::centered_square(opts);
+    let palette = super::palette_for(rng, opts);
+    let side = square.w;
+    let center = Point::new(square.x + side / 2.0, square.y + side / 2.0);
+
+    let badge = if rng.gen_bool(CIRCLE_PROBABILITY.value(opts)) {
+        Shape::Circle(Circle {
+            cx: center.x,
+            cy: center.y,
+            r: side / 2.0,
+        })
+    } else {
+        Shape::Rect {
+            rect: square,
+            rx: side * CORNER_RADIUS,
+            ry: side * CORNER_RADIUS,
+        }
+    };
+
+    let kind = [Kind::Sprig, Kind::Leaf, Kind::Tree][rng.gen_range(0..3)];
+    let grammars: &[Grammar] = match kind {
+        Kind::Sprig => &SPRIGS,
+        Kind::Leaf => &VEINS,
+        Kind::Tree => &TREES,
+    };
+    let grammar = &grammars[rng.gen_range(0..grammars.len())];
+    let turtle = Turtle {
+        angle: rng.gen_range(grammar.angle.0..grammar.angle.1).to_radians(),
+        bend: match kind {
+            Kind::Sprig => rng
+                .gen_range(-MAX_BEND.value_f32(opts)..MAX_BEND.value_f32(opts))
+                .to_radians(),
+            _ => 0.0,
+        },
+        shrink: SHRINK,
+    };
+    let tilt = match kind {
+        Kind::Tree => 0.0,
+        _ => rng
+            .gen_range(-MAX_TILT.value_f32(opts)..MAX_TILT.value_f32(opts))
+            .to_radians(),
+    };
+
+    let mut program = LSystem::new(grammar.axiom, grammar.rules).expand(grammar.iterations);
+    if kind != Kind::Leaf {
+        program = program.replace('X', "L");
+    }
+    let mut drawing = turtle.draw(&program);
+    rotate(&mut drawing, tilt);
+
+    // Fit the turtle drawing, standing on its base, into the plant area.
+    let bounds = drawing_bounds(&drawing);
+    let area = side * PLANT_SIZE.value_f32(opts);
+    let scale = area / bounds.w.max(bounds.h).max(f32::EPSILON);
+    let offset = Point::new(
+        center.x - (bounds.x + bounds.w / 2.0) * scale,
+        center.y - (bounds.y + bounds.h / 2.0) * scale,
+    );
+    let place = |p: Point| Point::new(offset.x + p.x * scale, offset.y + p.y * scale);
+
+    let tint = palette.primary.lerp(
+        palette.background.unwrap_or(TINT_FALLBACK),
+        BADGE_TINT.value_f32(opts),
+    );
+    let stroke_width = |depth: usize| {
+        (side * STEM_WIDTH.value_f32(opts) * TAPER.powi(depth as i32)).max(MIN_WIDTH)
+    };
+    let mut ops = vec![
+        DrawOp::Background {
+            color: palette.background,
+        },
+        DrawOp::ShapeFill {
+            shape: badge,
+            color: tint,
+        },
+    ];
+
+    match kind {
+        Kind::Leaf => {
+            // The midrib runs from the turtle's start to its farthest point.
+            let base = place(Point::new(0.0, 0.0));
+            let tip = drawing
+                .branches
+                .iter()
+                .filter(|b| b.depth == 0)
+                .flat_map(|b| b.points.iter().map(|&p| place(p)))
+                .fold(base, |far, p| {
+                    if p.distance(base) > far.distance(base) {
+                        p
+                    } else {
+                        far
+                    }
+                });
+            let length = tip.distance(base);
+            let heading = (tip.y - base.y).atan2(tip.x - base.x);
+            let (sin, cos) = heading.sin_cos();
+            let mut stalk = Path::new();
+            stalk.move_to(Point::new(
+                base.x - cos * STALK * length,
+                base.y - sin * STALK * length,
+            ));
+            stalk.line_to(base);
+            ops.push(DrawOp::ShapeStroke {
+                shape: Shape::Path(stalk),
+                color: palette.primary,
+                width: stroke_width(0),
+            });
+            let blade = Shape::Path(leaf(base, heading, length, LEAF_WIDTH * 1.3));
+            ops.push(DrawOp::ShapeFill {
+                shape: blade.clone(),
+                color: palette.primary,
+            });
+            ops.push(DrawOp::Group {
+                clip: Some(blade),
+                ops: drawing
+                    .branches
+                    .iter()
+                    .map(|branch| DrawOp::ShapeStroke {
+                        shape: Shape::Path(branch.to_path().map(place)),
+                        color: tint,
+                        width: stroke_width(branch.depth + 1) * 0.6,
+                    })
+                    .collect(),
+            });
+        }
+        Kind::Sprig | Kind::Tree => {
+            ops.extend(drawing.branches.iter().map(|branch| DrawOp::ShapeStroke {
+                shape: Shape::Path(branch.to_path().map(place)),
+                color: palette.primary,
+                width: stroke_width(branch.depth),
+            }));
+            ops.extend(drawing.leaves.iter().map(|l| {
+                let at = place(l.at);
+                let shape = match kind {
+                    Kind::Tree => Shape::Circle(Circle {
+                        cx: at.x,
+                        cy: at.y,
+                        r: (FOLIAGE_RADIUS * scale * l.step)
+                            .clamp(MIN_FOLIAGE * side, MAX_FOLIAGE * side),
+                    }),
+                    _ => Shape::Path(leaf(at, l.heading, side * LEAF_LENGTH * l.step, LEAF_WIDTH)),
+                };
+                DrawOp::ShapeFill {
+                    shape,
+                    color: palette.secondary,
+                }
+            }));
+        }
+    }
+
+    Ok(Scene {
+        width,
+        height,
+        ops,
+        palette,
+    })
+}
+
+/// Pointed leaf from `base` along `heading`, `width` times as wide as it
+/// is long at its widest.
+fn leaf(base: Point, heading: f32, length: f32, width: f32) -> Path {
+    let (sin, cos) = heading.sin_cos();
+    let at = |along: f32, across: f32| {
+        Point::new(
+            base.x + (cos * along - sin * across) * length,
+            base.y + (sin * along + cos * across) * length,
+        )
+    };
+    // A cubic with both controls offset by `h` bulges by 3h/4.
+    let h = width / 2.0 / 0.75;
+    let mut path = Path::new();
+    path.move_to(base);
+    path.cubic_to(at(0.15, h), at(0.6, h), at(1.0, 0.0));
+    path.cubic_to(at(0.6, -h), at(0.15, -h), base);
+    path.close();
+    path
+}
+
+/// Rotate the drawing about the turtle's start.
+fn rotate(drawing: &mut Drawing, angle: f32) {
+    let (sin, cos) = angle.sin_cos();
+    let turn = |p: Point| Point::new(p.x * cos - p.y * sin, p.x * sin + p.y * cos);
+    for branch in &mut drawing.branches {
+        for p in &mut branch.points {
+            *p = turn(*p);
+        }
+    }
+    for leaf in &mut drawing.leaves {
+        leaf.at = turn(leaf.at);
+        leaf.heading += angle;
+    }
+}
+
+/// Bounds of the drawing's branches and leaf positions.
+fn drawing_bounds(drawing: &Drawing) -> Rect {
+    let points = drawing
+        .branches
+        .iter()
+        .flat_map(|b| b.points.iter().copied())
+        .chain(drawing.leaves.iter().map(|l| l.at));
+    let (mut min, mut max) = (Point::new(0.0, 0.0), Point::new(0.0, 0.0));
+    for p in points {
+        min = Point::new(min.x.min(p.x), min.y.min(p.y));
+        max = Point::new(max.x.max(p.x), max.y.max(p.y));
+    }
+    Rect {
+        x: min.x,
+        y: min.y,
+        w: max.x - min.x,
+        h: max.y - min.y,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{build, TREES};
+    use crate::algorithms::{DrawOp, Scene};
+    use crate::core::lsystem::LSystem;
+    use crate::RenderOptions;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn scene(seed: u64) -> Scene {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        build("Green Leaf", &mut rng, &RenderOptions::default()).expect("build failed")
+    }
+
+    #[test]
+    fn plants_stay_inside_the_badge() {
+        for seed in 0..24 {
+            let s = scene(seed);
+            assert!(s.ops.len() > 3, "seed {seed}");
+            for op in &s.ops[2..] {
+                let shape = match op {
+                    DrawOp::ShapeFill { shape, .. } | DrawOp::ShapeStroke { shape, .. } => shape,
+                    DrawOp::Group {
+                        clip: Some(shape), ..
+                    } => shape,
+                    other => panic!("seed {seed}: unexpected {:?}", other),
+                };
+                let b = shape.bounds();
+                // The badge spans 61..451 at the default size.
+                assert!(b.x > 61.0 && b.x + b.w < 451.0, "seed {seed}: {b:?}");
+                assert!(b.y > 61.0 && b.y + b.h < 451.0, "seed {seed}: {b:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn tree_grammars_stay_small() {
+        for grammar in &TREES {
+            let program = LSystem::new(grammar.axiom, grammar.rules).expand(grammar.iterations);
+            assert!(program.len() < 20_000, "{}", program.len());
+        }
+    }
+}
@@ -0,0 +1,302 @@
+use rand::Rng;
+
+use super::params::{self, Param};
+use super::{DrawOp, Scene};
+use crate::core::geometry::{cubic_point, Circle, Path, Point, Shape};
+use crate::{LoGenError, RenderOptions};
+
+const MIN_RIBBONS: Param = Param::int("min_ribbons", 1, 8, 2, "Fewest ribbons");
+const MAX_RIBBONS: Param = Param::int("max_ribbons", 1, 8, 4, "Most ribbons");
+
+/// Samples along each ribbon centerline.
+const SAMPLES: usize = 32;
+
+const MIN_WIDTH: Param = Param::float(
+    "min_width",
+    0.02,
+    0.5,
+    0.16,
+    "Narrowest ribbon as fraction of the badge radius",
+);
+const MAX_WIDTH: Param = Param::float(
+    "max_width",
+    0.02,
+    0.5,
+    0.24,
+    "Widest ribbon as fraction of the badge radius",
+);
+
+/// How far ribbon ends reach past the badge edge, as fraction of the radius.
+const OVERSHOOT: f32 = 1.2;
+const BEND: Param = Param::float(
+    "bend",
+    0.01,
+    1.5,
+    0.7,
+    "Largest sideways pull of the ribbon control points as fraction of the badge radius",
+);
+const SHIFT: Param = Param::float(
+    "shift",
+    0.01,
+    0.9,
+    0.3,
+    "Largest offset of a ribbon from the center as fraction of the badge radius",
+);
+
+const GAP_FRACTION: Param = Param::float(
+    "gap",
+    0.0,
+    0.05,
+    0.01,
+    "Gap cut around a ribbon where it passes over another, as fraction of the canvas",
+);
+
+/// Parameters `RenderOptions::params` can override.
+pub const PARAMS: &[Param] = &[
+    MIN_RIBBONS,
+    MAX_RIBBONS,
+    MIN_WIDTH,
+    MAX_WIDTH,
+    BEND,
+    SHIFT,
+    GAP_FRACTION,
+];
+
+/// Ribbon mark: two to four tapered Bezier ribbons clipped to a circular
+/// badge, woven over and under each other at their crossings.
+pub fn build<R: Rng>(
+    _normalized: &str,
+    rng: &mut R,
+    opts: &RenderOptions,
+) -> Result<Scene, LoGenError> {
+    let (width, height) = opts.canvas_size();
+    let square = super::centered_square(opts);
+    let center = Point::new(square.x + square.w / 2.0, square.y + square.h / 2.0);
+    let radius = square.w / 2.0;
+    let gap = GAP_FRACTION.value_f32(opts) * width.min(height) as f32;
+
+    let palette = super::palette_for(rng, opts);
+    let colors = [palette.primary, palette.secondary, palette.tertiary];
+
+    let count = rng.gen_range(MIN_RIBBONS.value_u32(opts)..=MAX_RIBBONS.value_u32(opts)) as usize;
+    let base_angle = rng.gen_range(0.0..std::f32::consts::PI);
+
+    let mut ribbons = Vec::with_capacity(count);
+    for i in 0..count {
+        // Spread directions evenly, with some jitter so ribbons never align.
+        let angle =
+            base_angle + i as f32 * std::f32::consts::PI / count as f32 + rng.gen_range(-0.2..0.2);
+        let width =
+            radius * params::gen_between(rng, MIN_WIDTH.value_f32(opts), MAX_WIDTH.value_f32(opts));
+        ribbons.push(Ribbon::new(rng, center, radius, angle, width, opts));
+    }
+
+    let badge = Shape::Circle(Circle {
+        cx: center.x,
+        cy: center.y,
+        r: radius,
+    });
+
+    let mut woven = Vec::new();
+    for (i, ribbon) in ribbons.iter().enumerate() {
+        woven.push(DrawOp::Knockout {
+            op: Box::new(DrawOp::ShapeStroke {
+                shape: ribbon.outline.clone(),
+                color: colors[0],
+                width: 2.0 * gap,
+            }),
+        });
+        woven.push(DrawOp::ShapeFill {
+            shape: ribbon.outline.clone(),
+            color: colors[i % colors.len()],
+        });
+    }
+
+    // Later ribbons are painted on top; alternate crossings so that every
+    // other one brings the earlier ribbon back over the later one. The patch
+    // is clipped to the lower ribbon so neighbouring crossings are untouched.
+    let mut over = rng.gen_bool(0.5);
+    for j in 1..count {
+        for i in 0..j {
+            for crossing in ribbons[i].crossings(&ribbons[j]) {
+                over = !over;
+                if !over {
+                    continue;
+                }
+                let patch = |op: DrawOp| DrawOp::Group {
+                    clip: Some(Shape::Circle(Circle {
+                        cx: crossing.x,
+                        cy: crossing.y,
+                        r: 2.0 * ribbons[i].width.max(ribbons[j].width),
+                    })),
+                    ops: vec![DrawOp::Group {
+                        clip: Some(ribbons[j].outline.clone()),
+                        ops: vec![op],
+                    }],
+                };
+                woven.push(DrawOp::Knockout {
+                    op: Box::new(patch(DrawOp::ShapeStroke {
+                        shape: ribbons[i].outline.clone(),
+                        color: colors[0],
+                        width: 2.0 * gap,
+                    })),
+                });
+                woven.push(patch(DrawOp::ShapeFill {
+                    shape: ribbons[i].outline.clone(),
+                    color: colors[i % colors.len()],
+                }));
+            }
+        }
+    }
+
+    let ops = vec![
+        DrawOp::Background {
+            color: palette.background,
+        },
+        DrawOp::Group {
+            clip: Some(badge),
+            ops: woven,
+        },
+    ];
+
+    Ok(Scene {
+        width,
+        height,
+        ops,
+        palette,
+    })
+}
+
+struct Ribbon {
+    centerline: Vec<Point>,
+    outline: Shape,
+    width: f32,
+}
+
+impl Ribbon {
+    /// A ribbon crossing the badge along `angle`, bent by two seeded control
+    /// points and tapering towards its ends.
+    fn new<R: Rng>(
+        rng: &mut R,
+        center: Point,
+        radius: f32,
+        angle: f32,
+        width: f32,
+        opts: &RenderOptions,
+    ) -> Self {
+        let (dx, dy) = (angle.cos(), angle.sin());
+        let (nx, ny) = (-dy, dx);
+        let reach = OVERSHOOT * radius;
+        let shift = rng.gen_range(-SHIFT.value_f32(opts)..SHIFT.value_f32(opts)) * radius;
+        let along = |t: f32, offset: f32| {
+            Point::new(
+                center.x + dx * reach * t + nx * (shift + offset),
+                center.y + dy * reach * t + ny * (shift + offset),
+            )
+        };
+        let p0 = along(-1.0, rng.gen_range(-0.3..0.3) * radius);
+        let p1 = along(
+            -0.35,
+            rng.gen_range(-BEND.value_f32(opts)..BEND.value_f32(opts)) * radius,
+        );
+        let p2 = along(
+            0.35,
+            rng.gen_range(-BEND.value_f32(opts)..BEND.value_f32(opts)) * radius,
+        );
+        let p3 = along(1.0, rng.gen_range(-0.3..0.3) * radius);
+
+        let centerline: Vec<Point> = (0..=SAMPLES)
+            .map(|k| cubic_point(p0, p1, p2, p3, k as f32 / SAMPLES as f32))
+            .collect();
+
+        let mut left = Vec::with_capacity(centerline.len());
+        let mut right = Vec::with_capacity(centerline.len());
+        for (k, p) in centerline.iter().enumerate() {
+            let prev = centerline[k.saturating_sub(1)];
+            let next = centerline[(k + 1).min(SAMPLES)];
+            let (tx, ty) = (next.x - prev.x, next.y - prev.y);
+            let len = (tx * tx + ty * ty).sqrt().max(f32::EPSILON);
+            let s = k as f32 / SAMPLES as f32;
+            let half = width / 2.0 * (0.6 + 0.4 * (std::f32::consts::PI * s).sin());
+            left.push(Point::new(p.x - ty / len * half, p.y + tx / len * half));
+            right.push(Point::new(p.x + ty / len * half, p.y - tx / len * half));
+        }
+        right.reverse();
+        left.extend(right);
+
+        Ribbon {
+            centerline,
+            outline: Shape::Path(Path::polygon(&left)),
+            width,
+        }
+    }
+
+    /// Points where the centerlines of `self` and `other` cross.
+    fn crossings(&self, other: &Ribbon) -> Vec<Point> {
+        let mut out = Vec::new();
+        for a in self.centerline.windows(2) {
+            for b in other.centerline.windows(2) {
+                if let Some(p) = segment_intersection(a[0], a[1], b[0], b[1]) {
+                    out.push(p);
+                }
+            }
+        }
+        out
+    }
+}
+
+fn segment_intersection(a0: Point, a1: Point, b0: Point, b1: Point) -> Option<Point> {
+    let (rx, ry) = (a1.x - a0.x, a1.y - a0.y);
+    let (sx, sy) = (b1.x - b0.x, b1.y - b0.y);
+    let denom = rx * sy - ry * sx;
+    if denom.abs() < f32::EPSILON {
+        return None;
+    }
+    let (qx, qy) = (b0.x - a0.x, b0.y - a0.y);
+    let t = (qx * sy - qy * sx) / denom;
+    let u = (qx * ry - qy * rx) / denom;
+    if (0.0..1.0).contains(&t) && (0.0..1.0).contains(&u) {
+        Some(a0.lerp(a1, t))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::build;
+    use crate::algorithms::DrawOp;
+    use crate::RenderOptions;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn woven_ops(seed: u64) -> Vec<DrawOp> {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        let scene = build("Ribbon", &mut rng, &RenderOptions::default()).expect("build failed");
+        match scene.ops.into_iter().nth(1) {
+            Some(DrawOp::Group { clip: Some(_), ops }) => ops,
+            other => panic!("expected clipped ribbon group, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn draws_two_to_four_ribbons() {
+        for seed in 0..32 {
+            let ribbons = woven_ops(seed)
+                .iter()
+                .filter(|op| matches!(op, DrawOp::ShapeFill { .. }))
+                .count();
+            assert!((2..=4).contains(&ribbons), "seed {seed}: {ribbons}");
+        }
+    }
+
+    #[test]
+    fn some_crossings_are_woven_under() {
+        let woven = (0..32).any(|seed| {
+            woven_ops(seed)
+                .iter()
+                .any(|op| matches!(op, DrawOp::Group { clip: Some(_), .. }))
+        });
+        assert!(woven, "no ribbon was brought back over a later one");
+    }
+}
@@ -0,0 +1,368 @@
+use rand::Rng;
+
+use super::params::Param;
+use super::{DrawOp, Scene};
+use crate::core::geometry::{Circle, Point, Rect, Shape};
+use crate::{LoGenError, RenderOptions};
+
+const MIN_RINGS: Param = Param::int("min_rings", 1, 6, 2, "Fewest rings");
+const MAX_RINGS: Param = Param::int("max_rings", 1, 6, 4, "Most rings");
+
+const SQUARE_PROBABILITY: Param = Param::float(
+    "square_probability",
+    0.0,
+    1.0,
+    0.3,
+    "Chance of rounded square rings instead of circles",
+);
+
+/// Corner radius of rounded square rings as fraction of their half side.
+const SQUARE_CORNER: f32 = 0.45;
+/// Vertical offset of alternate rounded squares in a row, as fraction of
+/// their half side, so neighbours cross at right angles on their straight
+/// sides instead of sharing top and bottom edges.
+const SQUARE_STAGGER: f32 = 0.3;
+const CLUSTER_PROBABILITY: Param = Param::float(
+    "cluster_probability",
+    0.0,
+    1.0,
+    0.5,
+    "Chance of arranging three circles in a triangle rather than in a row",
+);
+const MONO_PROBABILITY: Param = Param::float(
+    "mono_probability",
+    0.0,
+    1.0,
+    0.25,
+    "Chance of every ring in the primary color",
+);
+
+const BAND: Param = Param::float(
+    "band",
+    0.05,
+    0.5,
+    0.22,
+    "Ring band width as fraction of the ring radius",
+);
+const GAP: Param = Param::float(
+    "gap",
+    0.0,
+    0.15,
+    0.05,
+    "Gap cut around a band where it passes over another, as fraction of the ring radius",
+);
+
+/// Parameters `RenderOptions::params` can override.
+pub const PARAMS: &[Param] = &[
+    MIN_RINGS,
+    MAX_RINGS,
+    SQUARE_PROBABILITY,
+    CLUSTER_PROBABILITY,
+    MONO_PROBABILITY,
+    BAND,
+    GAP,
+];
+
+/// Distance between neighbouring ring centers in a row, as fraction of the
+/// ring radius.
+const ROW_SPACING: f32 = 1.45;
+/// Distance of clustered ring centers from the mark center, as fraction of
+/// the ring radius.
+const CLUSTER_SPREAD: f32 = 0.62;
+
+/// Radius of the patch redrawing a ring over another at a crossing, as
+/// multiple of the cased band width. Patches shrink where crossings are
+/// close together so they never reach a neighbouring crossing.
+const PATCH: f32 = 1.6;
+
+/// Interlocking rings: two to four outlined circles or rounded squares,
+/// woven so each pair alternates over and under at its two crossings.
+///
+/// Rings are drawn in order, each cutting a gap around its band into the
+/// rings below it; then at one crossing of every pair the earlier ring is
+/// redrawn, with its own gap, inside a small clip around the crossing.
+pub fn build<R: Rng>(
+    normalized: &str,
+    rng: &mut R,
+    opts: &RenderOptions,
+) -> Result<Scene, LoGenError> {
+    let (width, height) = opts.canvas_size();
+    let square = super::centered_square(opts);
+    let palette = super::palette_for(rng, opts);
+
+    // Always draw the seeded count so the rest of the mark does not depend
+    // on whether the input has several words.
+    let seeded = rng.gen_range(MIN_RINGS.value_usize(opts)..=MAX_RINGS.value_usize(opts));
+    let words = normalized.split(' ').filter(|w| !w.is_empty()).count();
+    let count = if words >= MIN_RINGS.value_usize(opts) {
+        words.min(MAX_RINGS.value_usize(opts))
+    } else {
+        seeded
+    };
+    let squares = rng.gen_bool(SQUARE_PROBABILITY.value(opts));
+    let cluster = count == 3 && !squares && rng.gen_bool(CLUSTER_PROBABILITY.value(opts));
+    let mono = rng.gen_bool(MONO_PROBABILITY.value(opts));
+    let start = rng.gen_range(0..3);
+
+    let center = Point::new(square.x + square.w / 2.0, square.y + square.h / 2.0);
+    // Outer extent of a ring beyond its center line, per unit of radius.
+    let outer = 1.0 + BAND.value_f32(opts) / 2.0 + GAP.value_f32(opts);
+    let (radius, centers): (f32, Vec<Point>) = if cluster {
+        let radius = square.w / 2.0 / (CLUSTER_SPREAD + outer);
+        let step = std::f32::consts::TAU / count as f32;
+        let centers = (0..count)
+            .map(|k| {
+                let (sin, cos) = (k as f32 * step - std::f32::consts::FRAC_PI_2).sin_cos();
+                let d = CLUSTER_SPREAD * radius;
+                Point::new(center.x + d * cos, center.y + d * sin)
+            })
+            .collect();
+        (radius, centers)
+    } else {
+        let span = 2.0 * outer + ROW_SPACING * (count - 1) as f32;
+        let radius = square.w / span;
+        let first = center.x - ROW_SPACING * radius * (count - 1) as f32 / 2.0;
+        let centers = (0..count)
+            .map(|k| {
+                let stagger = match (squares, k % 2) {
+                    (false, _) => 0.0,
+                    (true, 0) => SQUARE_STAGGER,
+                    (true, _) => -SQUARE_STAGGER,
+                };
+                Point::new(
+                    first + k as f32 * ROW_SPACING * radius,
+                    center.y + stagger * radius,
+                )
+            })
+            .collect();
+        (radius, centers)
+    };
+
+    // Ring `k` grown outwards by `d`, or shrunk for negative `d`.
+    let ring = |k: usize, d: f32| {
+        let (c, r) = (centers[k], radius + d);
+        if squares {
+            Shape::Rect {
+                rect: Rect {
+                    x: c.x - r,
+                    y: c.y - r,
+                    w: 2.0 * r,
+                    h: 2.0 * r,
+                },
+                rx: radius * SQUARE_CORNER + d,
+                ry: radius * SQUARE_CORNER + d,
+            }
+        } else {
+            Shape::Circle(Circle {
+                cx: c.x,
+                cy: c.y,
+                r,
+            })
+        }
+    };
+    let rings: Vec<Shape> = (0..count).map(|k| ring(k, 0.0)).collect();
+    let roles = [palette.primary, palette.secondary, palette.tertiary];
+    let colors: Vec<_> = (0..count)
+        .map(|k| {
+            if mono {
+                palette.primary
+            } else {
+                roles[(start + k) % roles.len()]
+            }
+        })
+        .collect();
+
+    let band = BAND.value_f32(opts) * radius;
+    let cased = band + 2.0 * GAP.value_f32(opts) * radius;
+    let stroke = |k: usize, width: f32| DrawOp::ShapeStroke {
+        shape: rings[k].clone(),
+        color: colors[k],
+        width,
+    };
+
+    let mut layer = Vec::new();
+    for k in 0..count {
+        if k > 0 {
+            layer.push(DrawOp::Knockout {
+                op: Box::new(stroke(k, cased)),
+            });
+        }
+        layer.push(stroke(k, band));
+    }
+
+    // Every later ring lies over every earlier one so far; flip one crossing
+    // per pair, on the same side of the line between their centers.
+    let pairs: Vec<(usize, usize, Vec<Point>)> = (0..count)
+        .flat_map(|i| (i + 1..count).map(move |j| (i, j)))
+        .map(|(i, j)| (i, j, crossings(&rings[i], &rings[j])))
+        .collect();
+    let all: Vec<Point> = pairs.iter().flat_map(|(_, _, at)| at.clone()).collect();
+    let flips = pairs.iter().flat_map(|(i, j, at)| {
+        let (a, b) = (centers[*i], centers[*j]);
+        at.iter()
+            .filter(move |p| (b.x - a.x) * (p.y - a.y) - (b.y - a.y) * (p.x - a.x) < 0.0)
+            .map(move |&p| (*i, p))
+    });
+    for (i, at) in flips {
+        let nearest = all
+            .iter()
+            .map(|p| p.distance(at))
+            .filter(|&d| d > cased)
+            .fold(f32::MAX, f32::min);
+        let patch = Shape::Circle(Circle {
+            cx: at.x,
+            cy: at.y,
+            r: (PATCH * cased).min(nearest / 2.0),
+        });
+        // Cut only the gaps beside the band: erasing and redrawing the band
+        // itself would leave a seam along the antialiased patch edge.
+        let gap = GAP.value_f32(opts) * radius;
+        let gaps = [-1.0, 1.0].map(|side| DrawOp::ShapeStroke {
+            shape: ring(i, side * (band + gap) / 2.0),
+            color: colors[i],
+            width: gap,
+        });
+        layer.push(DrawOp::Knockout {
+            op: Box::new(DrawOp::Group {
+                clip: Some(patch.clone()),
+                ops: gaps.to_vec(),
+            }),
+        });
+        layer.push(DrawOp::Group {
+            clip: Some(patch),
+            ops: vec![stroke(i, band)],
+        });
+    }
+
+    let ops = vec![
+        DrawOp::Background {
+            color: palette.background,
+        },
+        // Gaps only cut into the rings, not the background.
+        DrawOp::Group {
+            clip: None,
+            ops: layer,
+        },
+    ];
+
+    Ok(Scene {
+        width,
+        height,
+        ops,
+        palette,
+    })
+}
+
+/// Points where the center lines of rings `a` and `b` cross.
+fn crossings(a: &Shape, b: &Shape) -> Vec<Point> {
+    let (pa, pb) = (a.to_path().flatten(), b.to_path().flatten());
+    let edges = |polys: &[(Vec<Point>, bool)]| -> Vec<(Point, Point)> {
+        polys
+            .iter()
+            .flat_map(|(poly, _)| {
+                (0..poly.len()).map(move |i| (poly[i], poly[(i + 1) % poly.len()]))
+            })
+            .collect()
+    };
+    let (ea, eb) = (edges(&pa), edges(&pb));
+    let mut out: Vec<Point> = Vec::new();
+    for &(p, q) in &ea {
+        for &(s, t) in &eb {
+            let r = Point::new(q.x - p.x, q.y - p.y);
+            let d = Point::new(t.x - s.x, t.y - s.y);
+            let denom = r.x * d.y - r.y * d.x;
+            if denom.abs() <= f32::EPSILON {
+                continue;
+            }
+            let (wx, wy) = (s.x - p.x, s.y - p.y);
+            let u = (wx * d.y - wy * d.x) / denom;
+            let v = (wx * r.y - wy * r.x) / denom;
+            // Half-open, so a crossing at a shared vertex counts once.
+            if (0.0..1.0).contains(&u) && (0.0..1.0).contains(&v) {
+                out.push(p.lerp(q, u));
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{build, crossings};
+    use crate::algorithms::{DrawOp, Scene};
+    use crate::core::geometry::{Circle, Shape};
+    use crate::RenderOptions;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn scene(input: &str, seed: u64) -> Scene {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        build(input, &mut rng, &RenderOptions::default()).expect("build failed")
+    }
+
+    fn layer(scene: &Scene) -> &[DrawOp] {
+        match &scene.ops[1] {
+            DrawOp::Group { clip: None, ops } => ops,
+            other => panic!("expected the ring layer, got {:?}", other),
+        }
+    }
+
+    /// Rings, i.e. top-level strokes in the layer.
+    fn rings(scene: &Scene) -> usize {
+        layer(scene)
+            .iter()
+            .filter(|op| matches!(op, DrawOp::ShapeStroke { .. }))
+            .count()
+    }
+
+    #[test]
+    fn one_ring_per_word() {
+        for seed in 0..6 {
+            assert_eq!(rings(&scene("North Star", seed)), 2);
+            assert_eq!(rings(&scene("Blue Harbor Works", seed)), 3);
+            assert_eq!(rings(&scene("a b c d e f", seed)), 4);
+        }
+        let single: Vec<usize> = (0..16).map(|seed| rings(&scene("Orbit", seed))).collect();
+        assert!(single.iter().all(|n| (2..=4).contains(n)));
+        assert!(single.iter().any(|&n| n != single[0]));
+    }
+
+    #[test]
+    fn circles_cross_twice() {
+        let circle = |cx: f32| {
+            Shape::Circle(Circle {
+                cx,
+                cy: 0.0,
+                r: 10.0,
+            })
+        };
+        let points = crossings(&circle(0.0), &circle(12.0));
+        assert_eq!(points.len(), 2);
+        for p in points {
+            assert!(
+                (p.x - 6.0).abs() < 0.1 && (p.y.abs() - 8.0).abs() < 0.1,
+                "{p:?}"
+            );
+        }
+        assert!(crossings(&circle(0.0), &circle(30.0)).is_empty());
+    }
+
+    #[test]
+    fn each_crossing_pair_flips_once() {
+        for seed in 0..8 {
+            let s = scene("Twin Peaks", seed);
+            // Two rings cross twice; the second cuts its gap into the first,
+            // then one crossing is flipped with the first ring's gaps.
+            let patches: Vec<&DrawOp> = layer(&s)
+                .iter()
+                .filter(|op| matches!(op, DrawOp::Group { clip: Some(_), .. }))
+                .collect();
+            assert_eq!(patches.len(), 1, "seed {seed}");
+            let knockouts = layer(&s)
+                .iter()
+                .filter(|op| matches!(op, DrawOp::Knockout { .. }))
+                .count();
+            assert_eq!(knockouts, 2, "seed {seed}");
+        }
+    }
+}
@@ -0,0 +1,172 @@
+//! Orthogonal grid router for trace-like presets.
+//!
+//! Routes run through cell centers in four directions only. Every routed
+//! cell is blocked for later routes, so routes never cross or touch along
+//! a shared cell.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// Grid cell as `(col, row)`.
+pub(crate) type Cell = (usize, usize);
+
+/// Extra cost of a turn, in steps; routes take up to this many detour
+/// steps to save a bend.
+const TURN_COST: u32 = 3;
+
+/// Unit steps in the four directions, as `(dcol, drow)`.
+const STEPS: [(isize, isize); 4] = [(1, 0), (0, 1), (-1, 0), (0, -1)];
+
+/// Occupancy of a `cols` × `rows` grid.
+#[derive(Debug, Clone)]
+pub(crate) struct Grid {
+    cols: usize,
+    rows: usize,
+    blocked: Vec<bool>,
+}
+
+impl Grid {
+    pub(crate) fn new(cols: usize, rows: usize) -> Self {
+        Grid {
+            cols,
+            rows,
+            blocked: vec![false; cols * rows],
+        }
+    }
+
+    pub(crate) fn block(&mut self, (col, row): Cell) {
+        self.blocked[row * self.cols + col] = true;
+    }
+
+    pub(crate) fn unblock(&mut self, (col, row): Cell) {
+        self.blocked[row * self.cols + col] = false;
+    }
+
+    pub(crate) fn is_free(&self, (col, row): Cell) -> bool {
+        col < self.cols && row < self.rows && !self.blocked[row * self.cols + col]
+    }
+
+    fn step(&self, (col, row): Cell, dir: usize) -> Option<Cell> {
+        let (dc, dr) = STEPS[dir];
+        let next = (col.checked_add_signed(dc)?, row.checked_add_signed(dr)?);
+        self.is_free(next).then_some(next)
+    }
+
+    /// Cheapest route from `from` to `to` through free cells, both ends
+    /// included, with each turn costing `TURN_COST` extra steps. The route's
+    /// cells are blocked on success; `None` leaves the grid untouched.
+    ///
+    /// Ties are broken by direction order, so equal inputs always give
+    /// equal routes.
+    pub(crate) fn route(&mut self, from: Cell, to: Cell) -> Option<Vec<Cell>> {
+        if !self.is_free(from) || !self.is_free(to) {
+            return None;
+        }
+        // Search states are a cell plus the direction it was entered from;
+        // the start has none, so it gets a fifth slot.
+        let state = |(col, row): Cell, dir: usize| (row * self.cols + col) * 5 + dir;
+        let mut cost = vec![u32::MAX; self.cols * self.rows * 5];
+        let mut came_from = vec![usize::MAX; cost.len()];
+        let mut heap = BinaryHeap::new();
+        cost[state(from, 4)] = 0;
+        heap.push(Reverse((0, state(from, 4))));
+
+        while let Some(Reverse((c, s))) = heap.pop() {
+            if c > cost[s] {
+                continue;
+            }
+            let (index, dir) = (s / 5, s % 5);
+            let cell = (index % self.cols, index / self.cols);
+            if cell == to {
+                let mut cells = vec![cell];
+                let mut s = s;
+                while came_from[s] != usize::MAX {
+                    s = came_from[s];
+                    let index = s / 5;
+                    cells.push((index % self.cols, index / self.cols));
+                }
+                cells.reverse();
+                for &cell in &cells {
+                    self.block(cell);
+                }
+                return Some(cells);
+            }
+            for next_dir in 0..4 {
+                let Some(next) = self.step(cell, next_dir) else {
+                    continue;
+                };
+                let turn = if dir == 4 || dir == next_dir {
+                    0
+                } else {
+                    TURN_COST
+                };
+                let next_cost = c + 1 + turn;
+                let t = state(next, next_dir);
+                if next_cost < cost[t] {
+                    cost[t] = next_cost;
+                    came_from[t] = s;
+                    heap.push(Reverse((next_cost, t)));
+                }
+            }
+        }
+        None
+    }
+}
+
+/// The ends of `route` and the cells where it turns, i.e. the vertices of
+/// its polyline.
+pub(crate) fn corners(route: &[Cell]) -> Vec<Cell> {
+    let mut out: Vec<Cell> = route.first().copied().into_iter().collect();
+    for window in route.windows(3) {
+        let (a, b, c) = (window[0], window[1], window[2]);
+        // Collinear when both steps move along the same axis.
+        let straight = (a.0 == b.0 && b.0 == c.0) || (a.1 == b.1 && b.1 == c.1);
+        if !straight {
+            out.push(b);
+        }
+    }
+    if route.len() > 1 {
+        out.extend(route.last());
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_orthogonal(route: &[Cell]) -> bool {
+        route
+            .windows(2)
+            .all(|w| w[0].0.abs_diff(w[1].0) + w[0].1.abs_diff(w[1].1) == 1)
+    }
+
+    #[test]
+    fn open_grid_routes_with_one_turn() {
+        let mut grid = Grid::new(8, 8);
+        let route = grid.route((1, 1), (5, 4)).expect("route");
+        assert!(is_orthogonal(&route));
+        assert_eq!(route.len(), 4 + 3 + 1);
+        assert_eq!(corners(&route).len(), 3);
+        assert!(!grid.is_free((1, 1)) && !grid.is_free((5, 4)));
+
+        let straight = grid.route((0, 7), (7, 7)).expect("route");
+        assert_eq!(corners(&straight), vec![(0, 7), (7, 7)]);
+    }
+
+    #[test]
+    fn routes_detour_around_blocked_cells_and_never_share_them() {
+        let mut grid = Grid::new(7, 7);
+        for row in 0..6 {
+            grid.block((3, row));
+        }
+        let first = grid.route((0, 0), (6, 0)).expect("route");
+        assert!(is_orthogonal(&first));
+        assert!(first.contains(&(3, 6)));
+
+        // The only way across is now taken.
+        assert_eq!(grid.route((1, 1), (5, 1)), None);
+        assert!(grid.is_free((1, 1)) && grid.is_free((5, 1)));
+        assert_eq!(grid.route((0, 0), (0, 3)), None);
+    }
+}
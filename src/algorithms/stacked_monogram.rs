@@ -0,0 +1,402 @@
+use rand::Rng;
+
+use super::monogram_badge::initials_from_normalized;
+use super::params::Param;
+use super::{DrawOp, Scene};
+use crate::core::boolean;
+use crate::core::geometry::{Circle, Path, Point, Rect, Shape};
+use crate::core::glyphs;
+use crate::{LoGenError, RenderOptions};
+
+const RING_PROBABILITY: Param = Param::float(
+    "ring_probability",
+    0.0,
+    1.0,
+    0.5,
+    "Chance of a thin ring framing the letters",
+);
+
+/// Ring width as fraction of the square side.
+const RING_WIDTH: f32 = 0.025;
+/// Side of the box the letters are fitted into, as fraction of the square
+/// side, with and without the ring.
+const FRAMED_SIZE: f32 = 0.6;
+const OPEN_SIZE: f32 = 0.8;
+
+const MIN_OVERLAP: Param = Param::float(
+    "min_overlap",
+    0.0,
+    0.9,
+    0.4,
+    "Least overlap of neighbouring letters as fraction of the narrower one",
+);
+const MAX_OVERLAP: Param = Param::float(
+    "max_overlap",
+    0.0,
+    0.9,
+    0.6,
+    "Most overlap of neighbouring letters as fraction of the narrower one",
+);
+const MAX_OFFSET: Param = Param::float(
+    "max_offset",
+    0.0,
+    0.5,
+    0.22,
+    "Largest vertical offset of alternate letters as fraction of the cap height",
+);
+
+/// Drop of each letter below the previous one in the diagonal layout, as
+/// fraction of the cap height.
+const DIAGONAL_DROP: f32 = 0.45;
+const FLANK_SCALE: Param = Param::float(
+    "flank_scale",
+    0.3,
+    1.0,
+    0.68,
+    "Height of the letters beside a centerpiece as fraction of the middle one",
+);
+
+const GAP: Param = Param::float(
+    "gap",
+    0.0,
+    0.04,
+    0.012,
+    "Gap cut around a letter where it lies over another, as fraction of the square side",
+);
+
+/// Parameters `RenderOptions::params` can override.
+pub const PARAMS: &[Param] = &[
+    RING_PROBABILITY,
+    MIN_OVERLAP,
+    MAX_OVERLAP,
+    MAX_OFFSET,
+    FLANK_SCALE,
+    GAP,
+];
+
+/// How the letters are arranged relative to each other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Layout {
+    /// Side by side, overlapping, alternately raised and lowered.
+    Overlap,
+    /// Stepping down to the right, each overlapping the one before.
+    Diagonal,
+    /// A large middle letter with smaller letters across it on both sides;
+    /// three letters only.
+    Centerpiece,
+}
+
+/// Classic luxury monogram: each initial set on its own, overlapping its
+/// neighbours in a seeded arrangement, in its own color, with the letters
+/// interlocking where they cross.
+///
+/// Letters are drawn in order, each cutting a gap around its outline into
+/// the letters below. Then every other piece where two letters overlap is
+/// flipped: the earlier letter is redrawn over it, cutting its own gap.
+pub fn build<R: Rng>(
+    normalized: &str,
+    rng: &mut R,
+    opts: &RenderOptions,
+) -> Result<Scene, LoGenError> {
+    let (width, height) = opts.canvas_size();
+    let square = super::centered_square(opts);
+    let palette = super::palette_for(rng, opts);
+    let side = square.w;
+
+    let initials: Vec<char> = initials_from_normalized(normalized).chars().collect();
+    let framed = rng.gen_bool(RING_PROBABILITY.value(opts));
+    // Always draw every choice so the layouts do not shift the rest of the
+    // sequence.
+    let layout = match rng.gen_range(0..3) {
+        0 => Layout::Overlap,
+        1 => Layout::Diagonal,
+        _ if initials.len() == 3 => Layout::Centerpiece,
+        _ => Layout::Overlap,
+    };
+    let overlap = rng.gen_range(MIN_OVERLAP.value_f32(opts)..=MAX_OVERLAP.value_f32(opts));
+    let offset = rng.gen_range(-MAX_OFFSET.value_f32(opts)..=MAX_OFFSET.value_f32(opts));
+    let flip_first = rng.gen_bool(0.5);
+    let two_tone = rng.gen_bool(0.5);
+
+    // Set the letters around the origin at cap height 1, then fit them.
+    let font = glyphs::geometry_font();
+    let heights: Vec<f32> = (0..initials.len())
+        .map(|k| match layout {
+            Layout::Centerpiece if k != 1 => FLANK_SCALE.value_f32(opts),
+            _ => 1.0,
+        })
+        .collect();
+    let shapes: Vec<Path> = initials
+        .iter()
+        .zip(&heights)
+        .map(|(&ch, &h)| glyphs::set_letter(font, ch, h))
+        .collect();
+    let widths: Vec<f32> = shapes.iter().map(|s| s.bounds().w).collect();
+    let mut centers = vec![Point::new(0.0, 0.0); shapes.len()];
+    match layout {
+        Layout::Overlap | Layout::Diagonal => {
+            for k in 1..shapes.len() {
+                let (a, b) = (widths[k - 1], widths[k]);
+                let x = centers[k - 1].x + (a + b) / 2.0 - overlap * a.min(b);
+                let y = match layout {
+                    Layout::Diagonal => centers[k - 1].y + DIAGONAL_DROP,
+                    _ if k % 2 == 1 => offset,
+                    _ => 0.0,
+                };
+                centers[k] = Point::new(x, y);
+            }
+        }
+        Layout::Centerpiece => {
+            let reach = |k: usize| (widths[1] + widths[k]) / 2.0 - overlap * widths[k];
+            centers[0] = Point::new(-reach(0), 0.0);
+            centers[2] = Point::new(reach(2), 0.0);
+        }
+    }
+    let placed: Vec<Path> = shapes
+        .iter()
+        .zip(&centers)
+        .map(|(shape, c)| shape.map(|p| Point::new(p.x + c.x, p.y + c.y)))
+        .collect();
+    let size = side * if framed { FRAMED_SIZE } else { OPEN_SIZE };
+    let letters = glyphs::fit_in(
+        &placed,
+        Rect {
+            x: square.x + (side - size) / 2.0,
+            y: square.y + (side - size) / 2.0,
+            w: size,
+            h: size,
+        },
+    );
+
+    let colors: Vec<_> = (0..letters.len())
+        .map(|k| match (two_tone, k % 3) {
+            (true, _) if k % 2 == 0 => palette.primary,
+            (true, _) => palette.secondary,
+            (false, 0) => palette.primary,
+            (false, 1) => palette.secondary,
+            _ => palette.tertiary,
+        })
+        .collect();
+    let gap = GAP.value_f32(opts) * side;
+    let fill = |k: usize| DrawOp::ShapeFill {
+        shape: Shape::Path(letters[k].clone()),
+        color: colors[k],
+    };
+    // The casing around a letter's outline, outside the letter itself.
+    let casing = |k: usize| DrawOp::Group {
+        clip: None,
+        ops: vec![
+            DrawOp::ShapeStroke {
+                shape: Shape::Path(letters[k].clone()),
+                color: colors[k],
+                width: 2.0 * gap,
+            },
+            DrawOp::Knockout {
+                op: Box::new(fill(k)),
+            },
+        ],
+    };
+
+    let mut layer = Vec::new();
+    for k in 0..letters.len() {
+        if k > 0 {
+            layer.push(DrawOp::Knockout {
+                op: Box::new(casing(k)),
+            });
+        }
+        layer.push(fill(k));
+    }
+    for i in 0..letters.len() {
+        for j in i + 1..letters.len() {
+            let pieces = overlaps(&letters[i], &letters[j]);
+            for piece in pieces.iter().skip(flip_first as usize).step_by(2) {
+                let clip = Shape::Path(grown(piece, 2.0 * gap));
+                layer.push(DrawOp::Knockout {
+                    op: Box::new(DrawOp::Group {
+                        clip: Some(clip.clone()),
+                        ops: vec![casing(i)],
+                    }),
+                });
+                layer.push(DrawOp::Group {
+                    clip: Some(clip),
+                    ops: vec![fill(i)],
+                });
+            }
+        }
+    }
+
+    let mut ops = vec![DrawOp::Background {
+        color: palette.background,
+    }];
+    if framed {
+        let width = RING_WIDTH * side;
+        ops.push(DrawOp::ShapeStroke {
+            shape: Shape::Circle(Circle {
+                cx: square.x + side / 2.0,
+                cy: square.y + side / 2.0,
+                r: (side - width) / 2.0,
+            }),
+            color: palette.primary,
+            width,
+        });
+    }
+    // Gaps only cut into the letters, not the background or ring.
+    ops.push(DrawOp::Group {
+        clip: None,
+        ops: layer,
+    });
+
+    Ok(Scene {
+        width,
+        height,
+        ops,
+        palette,
+    })
+}
+
+/// Outer loops of the separate regions where `a` and `b` overlap, top to
+/// bottom. Holes in those regions are not pieces of their own.
+fn overlaps(a: &Path, b: &Path) -> Vec<Vec<Point>> {
+    let loops: Vec<(Vec<Point>, f32)> = boolean::intersection(a, b)
+        .flatten()
+        .into_iter()
+        .map(|(poly, _)| {
+            let area = signed_area(&poly);
+            (poly, area)
+        })
+        .collect();
+    // Outer loops all turn the same way as the largest one.
+    let Some(largest) = loops
+        .iter()
+        .map(|(_, area)| *area)
+        .max_by(|x, y| x.abs().total_cmp(&y.abs()))
+    else {
+        return Vec::new();
+    };
+    let mut pieces: Vec<(Vec<Point>, f32)> = loops
+        .into_iter()
+        .filter(|(_, area)| area.signum() == largest.signum())
+        .map(|(poly, _)| {
+            let mid = poly.iter().map(|p| p.y).sum::<f32>() / poly.len() as f32;
+            (poly, mid)
+        })
+        .collect();
+    pieces.sort_by(|p, q| p.1.total_cmp(&q.1));
+    pieces.into_iter().map(|(poly, _)| poly).collect()
+}
+
+fn signed_area(poly: &[Point]) -> f32 {
+    (0..poly.len())
+        .map(|i| {
+            let (p, q) = (poly[i], poly[(i + 1) % poly.len()]);
+            p.x * q.y - q.x * p.y
+        })
+        .sum::<f32>()
+        / 2.0
+}
+
+/// `poly` with every vertex pushed out by about `d`, mitred at corners.
+fn grown(poly: &[Point], d: f32) -> Path {
+    let outward = signed_area(poly).signum();
+    let normal = |p: Point, q: Point| {
+        let (ex, ey) = (q.x - p.x, q.y - p.y);
+        let len = ex.hypot(ey).max(f32::EPSILON);
+        (outward * ey / len, -outward * ex / len)
+    };
+    let n = poly.len();
+    let points: Vec<Point> = (0..n)
+        .map(|i| {
+            let (prev, p, next) = (poly[(i + n - 1) % n], poly[i], poly[(i + 1) % n]);
+            let (a, b) = (normal(prev, p), normal(p, next));
+            let (mx, my) = (a.0 + b.0, a.1 + b.1);
+            let len = mx.hypot(my);
+            if len < 1e-3 {
+                return Point::new(p.x + a.0 * d, p.y + a.1 * d);
+            }
+            // Sharp corners would mitre far out; cap at twice the distance.
+            let reach = d / (mx * a.0 + my * a.1).max(0.5 * len) * len;
+            Point::new(p.x + mx / len * reach, p.y + my / len * reach)
+        })
+        .collect();
+    Path::polygon(&points)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{build, overlaps};
+    use crate::algorithms::{DrawOp, Scene};
+    use crate::core::geometry::Shape;
+    use crate::RenderOptions;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn scene(input: &str, seed: u64) -> Scene {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        build(input, &mut rng, &RenderOptions::default()).expect("build failed")
+    }
+
+    fn layer(scene: &Scene) -> &[DrawOp] {
+        scene
+            .ops
+            .iter()
+            .find_map(|op| match op {
+                DrawOp::Group { clip: None, ops } => Some(ops.as_slice()),
+                _ => None,
+            })
+            .expect("letter layer")
+    }
+
+    #[test]
+    fn one_letter_per_initial_each_in_turn() {
+        for (input, count) in [("Acme Widgets", 2), ("abr", 3)] {
+            for seed in 0..6 {
+                let s = scene(input, seed);
+                let letters: Vec<_> = layer(&s)
+                    .iter()
+                    .filter_map(|op| match op {
+                        DrawOp::ShapeFill {
+                            shape: Shape::Path(path),
+                            color,
+                        } => Some((path.clone(), *color)),
+                        _ => None,
+                    })
+                    .collect();
+                assert_eq!(letters.len(), count, "{input} seed {seed}");
+                // Neighbours overlap and differ in color.
+                for pair in letters.windows(2) {
+                    assert!(!overlaps(&pair[0].0, &pair[1].0).is_empty());
+                    assert_ne!(pair[0].1, pair[1].1, "{input} seed {seed}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn every_other_overlap_is_flipped() {
+        for seed in 0..6 {
+            let s = scene("Acme Widgets", seed);
+            let letters: Vec<_> = layer(&s)
+                .iter()
+                .filter_map(|op| match op {
+                    DrawOp::ShapeFill {
+                        shape: Shape::Path(path),
+                        ..
+                    } => Some(path.clone()),
+                    _ => None,
+                })
+                .collect();
+            let pieces = overlaps(&letters[0], &letters[1]).len();
+            let patches = layer(&s)
+                .iter()
+                .filter(|op| matches!(op, DrawOp::Group { clip: Some(_), .. }))
+                .count();
+            assert!(patches == pieces / 2 || patches == pieces.div_ceil(2));
+            // One knockout for the second letter's gap, one per patch.
+            let knockouts = layer(&s)
+                .iter()
+                .filter(|op| matches!(op, DrawOp::Knockout { .. }))
+                .count();
+            assert_eq!(knockouts, 1 + patches, "seed {seed}");
+        }
+    }
+}
@@ -0,0 +1,276 @@
+use rand::Rng;
+
+use super::geometric_pattern::centered_lettermark;
+use super::params::{self, Param};
+use super::{DrawOp, Scene};
+use crate::core::geometry::{Path, Point, Shape};
+use crate::core::palette::Rgb;
+use crate::{LoGenError, RenderOptions};
+
+const MIN_RAYS: Param = Param::int("min_rays", 3, 64, 8, "Fewest rays");
+const MAX_RAYS: Param = Param::int("max_rays", 3, 64, 24, "Most rays");
+const MIN_RAY_FILL: Param = Param::float(
+    "min_ray_fill",
+    0.05,
+    0.95,
+    0.35,
+    "Narrowest ray as fraction of its slot",
+);
+const MAX_RAY_FILL: Param = Param::float(
+    "max_ray_fill",
+    0.05,
+    0.95,
+    0.65,
+    "Widest ray as fraction of its slot",
+);
+
+const MIN_RINGS: Param = Param::int("min_rings", 1, 8, 2, "Fewest rings");
+const MAX_RINGS: Param = Param::int("max_rings", 1, 8, 5, "Most rings");
+const RING_GAP: Param = Param::float(
+    "ring_gap",
+    0.0,
+    0.1,
+    0.035,
+    "Gap between rings as fraction of the radius",
+);
+
+/// Radius where rays hand over to rings in the combined layout, as fraction
+/// of the radius.
+const SPLIT_RADIUS: f32 = 0.62;
+
+const CENTER_PROBABILITY: Param = Param::float(
+    "center_probability",
+    0.0,
+    1.0,
+    0.6,
+    "Chance of a center disc with a lettermark instead of a small hub",
+);
+const CENTER_RADIUS: Param = Param::float(
+    "center_radius",
+    0.1,
+    0.5,
+    0.3,
+    "Center disc radius as fraction of the radius",
+);
+
+/// Parameters `RenderOptions::params` can override.
+pub const PARAMS: &[Param] = &[
+    MIN_RAYS,
+    MAX_RAYS,
+    MIN_RAY_FILL,
+    MAX_RAY_FILL,
+    MIN_RINGS,
+    MAX_RINGS,
+    RING_GAP,
+    CENTER_PROBABILITY,
+    CENTER_RADIUS,
+];
+
+/// Lettermark size as fraction of the center disc radius.
+const LETTERMARK_SIZE: f32 = 0.8;
+/// Radius left empty in the middle without a center disc, so rays do not
+/// converge into a blot at small sizes.
+const HUB_RADIUS: f32 = 0.08;
+
+/// Fallback center disc color on transparent canvases.
+const CENTER_FALLBACK: Rgb = Rgb {
+    r: 255,
+    g: 255,
+    b: 255,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Layout {
+    Rings,
+    Rays,
+    /// Rays in the middle, rings around them.
+    Both,
+}
+
+impl Layout {
+    const ALL: [Layout; 3] = [Layout::Rings, Layout::Rays, Layout::Both];
+}
+
+/// Radial mark: concentric rings and/or sunburst rays with seeded counts,
+/// thicknesses and angular offset, optionally around a center disc carrying
+/// the lettermark.
+pub fn build<R: Rng>(
+    normalized: &str,
+    rng: &mut R,
+    opts: &RenderOptions,
+) -> Result<Scene, LoGenError> {
+    let (width, height) = opts.canvas_size();
+    let square = super::centered_square(opts);
+    let center = Point::new(square.x + square.w / 2.0, square.y + square.h / 2.0);
+    let r = square.w / 2.0;
+
+    let palette = super::palette_for(rng, opts);
+    let layout = Layout::ALL[rng.gen_range(0..Layout::ALL.len())];
+    let has_center = rng.gen_bool(CENTER_PROBABILITY.value(opts));
+    let hub = if has_center {
+        CENTER_RADIUS.value_f32(opts) * r
+    } else {
+        HUB_RADIUS * r
+    };
+
+    let mut ops = vec![DrawOp::Background {
+        color: palette.background,
+    }];
+
+    let rays_outer = match layout {
+        Layout::Rays => r,
+        Layout::Both => SPLIT_RADIUS * r,
+        Layout::Rings => hub,
+    };
+    if rays_outer > hub {
+        let count = rng.gen_range(MIN_RAYS.value_u32(opts)..=MAX_RAYS.value_u32(opts));
+        let slot = std::f32::consts::TAU / count as f32;
+        let sweep = slot
+            * params::gen_between(
+                rng,
+                MIN_RAY_FILL.value_f32(opts),
+                MAX_RAY_FILL.value_f32(opts),
+            );
+        let offset = rng.gen_range(0.0..slot);
+        for i in 0..count {
+            ops.push(DrawOp::ShapeFill {
+                shape: Shape::Path(Path::sector(
+                    center,
+                    hub,
+                    rays_outer,
+                    offset + i as f32 * slot,
+                    sweep,
+                )),
+                color: palette.primary,
+            });
+        }
+    }
+
+    if layout != Layout::Rays {
+        let gap = RING_GAP.value_f32(opts) * r;
+        let inner = if layout == Layout::Both {
+            rays_outer + gap
+        } else {
+            hub + gap
+        };
+        let count = rng.gen_range(MIN_RINGS.value_u32(opts)..=MAX_RINGS.value_u32(opts)) as usize;
+        let weights: Vec<f32> = (0..count).map(|_| rng.gen_range(0.5..1.5)).collect();
+        let total: f32 = weights.iter().sum();
+        let band = r - inner - gap * (count - 1) as f32;
+        let colors = [palette.primary, palette.secondary, palette.tertiary];
+        let start = rng.gen_range(0..colors.len());
+        let mut outer = r;
+        for (i, weight) in weights.iter().enumerate() {
+            let thickness = band * weight / total;
+            ops.push(DrawOp::ShapeFill {
+                shape: Shape::Path(Path::sector(
+                    center,
+                    outer - thickness,
+                    outer,
+                    0.0,
+                    std::f32::consts::TAU,
+                )),
+                color: colors[(start + i) % colors.len()],
+            });
+            outer -= thickness + gap;
+        }
+    }
+
+    if has_center {
+        // A full-turn sector rather than a circle, so its edge is
+        // antialiased like the rings around it.
+        ops.push(DrawOp::ShapeFill {
+            shape: Shape::Path(Path::sector(center, 0.0, hub, 0.0, std::f32::consts::TAU)),
+            color: palette.secondary,
+        });
+        ops.push(centered_lettermark(
+            normalized,
+            center,
+            hub * LETTERMARK_SIZE,
+            palette.background.unwrap_or(CENTER_FALLBACK),
+        ));
+    }
+
+    Ok(Scene {
+        width,
+        height,
+        ops,
+        palette,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::build;
+    use crate::algorithms::{DrawOp, Scene};
+    use crate::core::geometry::{Point, Shape};
+    use crate::RenderOptions;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn scene(seed: u64) -> Scene {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        build("Sun Co", &mut rng, &RenderOptions::default()).expect("build failed")
+    }
+
+    #[test]
+    fn stays_inside_the_padded_square() {
+        for seed in 0..16 {
+            for op in &scene(seed).ops {
+                if let DrawOp::ShapeFill {
+                    shape: Shape::Path(path),
+                    ..
+                } = op
+                {
+                    for (poly, _) in path.flatten() {
+                        for p in poly {
+                            // The padded square spans 61..451.
+                            let d = p.distance(Point::new(256.0, 256.0));
+                            assert!(d <= 195.1, "seed {seed}: {p:?}");
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn lettermark_sits_on_the_center_disc() {
+        let mut seen = false;
+        for seed in 0..16 {
+            let s = scene(seed);
+            if let Some(DrawOp::Text { x, y, text, .. }) = s.ops.last() {
+                assert_eq!(text, "SU");
+                assert_eq!((*x, *y), (256.0, 256.0));
+                match &s.ops[s.ops.len() - 2] {
+                    DrawOp::ShapeFill { shape, .. } => {
+                        assert!(shape.contains(Point::new(256.0, 256.0)))
+                    }
+                    other => panic!("expected the center disc, got {:?}", other),
+                }
+                seen = true;
+            }
+        }
+        assert!(seen, "no seed drew the center");
+    }
+
+    #[test]
+    fn draws_rings_or_rays() {
+        for seed in 0..16 {
+            let shapes = scene(seed)
+                .ops
+                .iter()
+                .filter(|op| {
+                    matches!(
+                        op,
+                        DrawOp::ShapeFill {
+                            shape: Shape::Path(_),
+                            ..
+                        }
+                    )
+                })
+                .count();
+            assert!(shapes >= 2, "seed {seed}: {shapes}");
+        }
+    }
+}
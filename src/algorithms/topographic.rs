@@ -0,0 +1,418 @@
+use rand::Rng;
+
+use super::params::{self, Param};
+use super::{DrawOp, Scene};
+use crate::core::geometry::{Circle, Path, Point, Shape};
+use crate::core::noise::ValueNoise;
+use crate::core::palette::Rgb;
+use crate::{LoGenError, RenderOptions};
+
+const CIRCLE_PROBABILITY: Param = Param::float(
+    "circle_probability",
+    0.0,
+    1.0,
+    0.5,
+    "Chance of a circular badge instead of a rounded square",
+);
+const CORNER_RADIUS: Param = Param::float(
+    "corner_radius",
+    0.0,
+    0.5,
+    0.2,
+    "Corner radius of the rounded square badge as fraction of its side",
+);
+
+const MIN_NOISE_CELLS: Param = Param::int(
+    "min_noise_cells",
+    1,
+    8,
+    2,
+    "Fewest noise lattice cells across the badge; fewer gives broader hills",
+);
+const MAX_NOISE_CELLS: Param = Param::int(
+    "max_noise_cells",
+    1,
+    8,
+    3,
+    "Most noise lattice cells across the badge",
+);
+const NOISE_OCTAVES: Param = Param::int(
+    "noise_octaves",
+    1,
+    6,
+    3,
+    "Noise octaves; more add finer detail",
+);
+
+/// Marching squares samples across the badge, fine enough for the contours
+/// to stay smooth once splined.
+const SAMPLES: usize = 64;
+
+const MIN_LEVELS: Param = Param::int("min_levels", 2, 40, 8, "Fewest contour levels");
+const MAX_LEVELS: Param = Param::int("max_levels", 2, 40, 13, "Most contour levels");
+const INDEX_EVERY: Param = Param::int(
+    "index_every",
+    1,
+    10,
+    4,
+    "Every this many contours is a heavier index contour",
+);
+
+const MIN_LINE_WIDTH: Param = Param::float(
+    "min_line_width",
+    0.001,
+    0.03,
+    0.004,
+    "Thinnest contour as fraction of the badge side",
+);
+const MAX_LINE_WIDTH: Param = Param::float(
+    "max_line_width",
+    0.001,
+    0.03,
+    0.008,
+    "Thickest contour as fraction of the badge side",
+);
+
+/// Parameters `RenderOptions::params` can override.
+pub const PARAMS: &[Param] = &[
+    CIRCLE_PROBABILITY,
+    CORNER_RADIUS,
+    MIN_NOISE_CELLS,
+    MAX_NOISE_CELLS,
+    NOISE_OCTAVES,
+    MIN_LEVELS,
+    MAX_LEVELS,
+    INDEX_EVERY,
+    MIN_LINE_WIDTH,
+    MAX_LINE_WIDTH,
+];
+
+/// Width of index contours relative to the others.
+const INDEX_WEIGHT: f32 = 2.2;
+/// How far the lowest contours are tinted towards the secondary color; the
+/// tint fades out towards the highest.
+const LOW_TINT: f32 = 0.55;
+
+/// Chains with fewer points are specks and dropped.
+const MIN_CHAIN_POINTS: usize = 4;
+
+/// Line color on transparent canvases.
+const LINE_FALLBACK: Rgb = Rgb {
+    r: 255,
+    g: 255,
+    b: 255,
+};
+
+/// Topographic mark: iso-lines of a seeded smooth noise field, traced with
+/// marching squares and splined into contour paths, stroked over a badge
+/// with heavier index contours and a tint that climbs with the terrain.
+pub fn build<R: Rng>(
+    _normalized: &str,
+    rng: &mut R,
+    opts: &RenderOptions,
+) -> Result<Scene, LoGenError> {
+    let (width, height) = opts.canvas_size();
+    let square = super::centered_square(opts);
+    let palette = super::palette_for(rng, opts);
+
+    let badge = if rng.gen_bool(CIRCLE_PROBABILITY.value(opts)) {
+        Shape::Circle(Circle {
+            cx: square.x + square.w / 2.0,
+            cy: square.y + square.h / 2.0,
+            r: square.w / 2.0,
+        })
+    } else {
+        Shape::Rect {
+            rect: square,
+            rx: square.w * CORNER_RADIUS.value_f32(opts),
+            ry: square.w * CORNER_RADIUS.value_f32(opts),
+        }
+    };
+
+    let cells =
+        rng.gen_range(MIN_NOISE_CELLS.value_usize(opts)..=MAX_NOISE_CELLS.value_usize(opts));
+    let noise = ValueNoise::new(rng, cells, NOISE_OCTAVES.value_usize(opts));
+    let field: Vec<f32> = (0..=SAMPLES)
+        .flat_map(|row| (0..=SAMPLES).map(move |col| (col, row)))
+        .map(|(col, row)| noise.sample(col as f32 / SAMPLES as f32, row as f32 / SAMPLES as f32))
+        .collect();
+    let (low, high) = field
+        .iter()
+        .fold((f32::MAX, f32::MIN), |(lo, hi), &v| (lo.min(v), hi.max(v)));
+
+    let levels = rng.gen_range(MIN_LEVELS.value_usize(opts)..=MAX_LEVELS.value_usize(opts));
+    let index_offset = rng.gen_range(0..INDEX_EVERY.value_usize(opts));
+    let line_width = square.w
+        * params::gen_between(
+            rng,
+            MIN_LINE_WIDTH.value_f32(opts),
+            MAX_LINE_WIDTH.value_f32(opts),
+        );
+    let light = palette.background.unwrap_or(LINE_FALLBACK);
+
+    let cell = square.w / SAMPLES as f32;
+    let mut lines = Vec::with_capacity(levels);
+    for i in 0..levels {
+        // Levels evenly split the sampled range, never touching its ends.
+        let t = (i + 1) as f32 / (levels + 1) as f32;
+        let level = low + (high - low) * t;
+        let mut path = Path::new();
+        for (points, closed) in contours(&field, SAMPLES, level) {
+            if points.len() < MIN_CHAIN_POINTS {
+                continue;
+            }
+            let placed: Vec<Point> = points
+                .iter()
+                .map(|p| Point::new(square.x + p.x * cell, square.y + p.y * cell))
+                .collect();
+            path.cmds.extend(Path::catmull_rom(&placed, closed).cmds);
+        }
+        if path.cmds.is_empty() {
+            continue;
+        }
+        let index = (i + index_offset) % INDEX_EVERY.value_usize(opts) == 0;
+        lines.push(DrawOp::ShapeStroke {
+            shape: Shape::Path(path),
+            color: light.lerp(palette.secondary, LOW_TINT * (1.0 - t)),
+            width: if index {
+                line_width * INDEX_WEIGHT
+            } else {
+                line_width
+            },
+        });
+    }
+
+    let ops = vec![
+        DrawOp::Background {
+            color: palette.background,
+        },
+        DrawOp::ShapeFill {
+            shape: badge.clone(),
+            color: palette.primary,
+        },
+        DrawOp::Group {
+            clip: Some(badge),
+            ops: lines,
+        },
+    ];
+
+    Ok(Scene {
+        width,
+        height,
+        ops,
+        palette,
+    })
+}
+
+/// Iso-lines at `level` of `field`, a row-major grid of `(n + 1)²` samples,
+/// in grid units. Each line is its points and whether it closes on itself;
+/// lines that are not closed end on the grid's border.
+///
+/// Marching squares: every cell whose corners straddle `level` gets one
+/// segment (two at saddles, split by the cell's center value) between
+/// crossings linearly interpolated along its edges. Segments are chained
+/// through the edges they share, so crossings are computed once and
+/// neighbouring segments meet exactly.
+fn contours(field: &[f32], n: usize, level: f32) -> Vec<(Vec<Point>, bool)> {
+    let at = |col: usize, row: usize| field[row * (n + 1) + col];
+    // Horizontal edges (col, row)-(col + 1, row) first, then vertical ones
+    // (col, row)-(col, row + 1).
+    let horizontal = |col: usize, row: usize| row * n + col;
+    let vertical = |col: usize, row: usize| n * (n + 1) + row * (n + 1) + col;
+    let crossing = |edge: usize| {
+        let (a, b, from) = if edge < n * (n + 1) {
+            let (col, row) = (edge % n, edge / n);
+            (
+                at(col, row),
+                at(col + 1, row),
+                Point::new(col as f32, row as f32),
+            )
+        } else {
+            let e = edge - n * (n + 1);
+            let (col, row) = (e % (n + 1), e / (n + 1));
+            (
+                at(col, row),
+                at(col, row + 1),
+                Point::new(col as f32, row as f32),
+            )
+        };
+        let t = ((level - a) / (b - a)).clamp(0.0, 1.0);
+        if edge < n * (n + 1) {
+            Point::new(from.x + t, from.y)
+        } else {
+            Point::new(from.x, from.y + t)
+        }
+    };
+
+    let mut segments: Vec<[usize; 2]> = Vec::new();
+    for row in 0..n {
+        for col in 0..n {
+            let corners = [
+                at(col, row),
+                at(col + 1, row),
+                at(col + 1, row + 1),
+                at(col, row + 1),
+            ];
+            let above = corners.map(|v| v > level);
+            // Edges clockwise from the top, each between corners i and i + 1.
+            let edges = [
+                horizontal(col, row),
+                vertical(col + 1, row),
+                horizontal(col, row + 1),
+                vertical(col, row),
+            ];
+            let crossed: Vec<usize> = (0..4)
+                .filter(|&i| above[i] != above[(i + 1) % 4])
+                .map(|i| edges[i])
+                .collect();
+            match crossed[..] {
+                [a, b] => segments.push([a, b]),
+                [top, right, bottom, left] => {
+                    // Saddle: cut off the two corners on the other side of
+                    // `level` from the cell's center.
+                    let center = corners.iter().sum::<f32>() / 4.0 > level;
+                    if above[0] != center {
+                        segments.push([left, top]);
+                        segments.push([right, bottom]);
+                    } else {
+                        segments.push([top, right]);
+                        segments.push([bottom, left]);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    // Segments meeting at each edge; at most two.
+    let mut meeting: Vec<Vec<usize>> = vec![Vec::new(); 2 * n * (n + 1)];
+    for (i, seg) in segments.iter().enumerate() {
+        for &edge in seg {
+            meeting[edge].push(i);
+        }
+    }
+    let mut used = vec![false; segments.len()];
+    let walk = |start: usize, first: usize, used: &mut Vec<bool>| {
+        let mut edges = vec![start];
+        let (mut edge, mut seg) = (start, first);
+        loop {
+            used[seg] = true;
+            edge = if segments[seg][0] == edge {
+                segments[seg][1]
+            } else {
+                segments[seg][0]
+            };
+            if edge == start {
+                return (edges, true);
+            }
+            edges.push(edge);
+            match meeting[edge].iter().copied().find(|&s| !used[s]) {
+                Some(next) => seg = next,
+                None => return (edges, false),
+            }
+        }
+    };
+
+    let mut lines = Vec::new();
+    // Open lines start from a border edge, so each is traced end to end.
+    for (edge, segs) in meeting.iter().enumerate() {
+        if let [seg] = segs[..] {
+            if !used[seg] {
+                lines.push(walk(edge, seg, &mut used));
+            }
+        }
+    }
+    for seg in 0..segments.len() {
+        if !used[seg] {
+            lines.push(walk(segments[seg][0], seg, &mut used));
+        }
+    }
+    lines
+        .into_iter()
+        .map(|(edges, closed)| (edges.into_iter().map(crossing).collect(), closed))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{build, contours};
+    use crate::algorithms::{DrawOp, Scene};
+    use crate::core::geometry::Point;
+    use crate::RenderOptions;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn scene(seed: u64) -> Scene {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        build("Ridge Line", &mut rng, &RenderOptions::default()).expect("build failed")
+    }
+
+    /// Distance from the center of an `n` by `n` grid.
+    fn cone(n: usize) -> Vec<f32> {
+        let c = n as f32 / 2.0;
+        (0..=n)
+            .flat_map(|row| (0..=n).map(move |col| (col, row)))
+            .map(|(col, row)| Point::new(col as f32, row as f32).distance(Point::new(c, c)))
+            .collect()
+    }
+
+    #[test]
+    fn cone_contours_are_closed_circles() {
+        let lines = contours(&cone(20), 20, 6.0);
+        assert_eq!(lines.len(), 1);
+        let (points, closed) = &lines[0];
+        assert!(closed);
+        for p in points {
+            let r = p.distance(Point::new(10.0, 10.0));
+            assert!((r - 6.0).abs() < 0.3, "{p:?}: {r}");
+        }
+    }
+
+    #[test]
+    fn lines_cut_by_the_border_stay_open() {
+        // Around a corner the circle of radius 14 leaves the grid.
+        let lines = contours(&cone(20), 20, 14.0);
+        assert_eq!(lines.len(), 4);
+        for (points, closed) in &lines {
+            assert!(!closed);
+            for end in [points[0], points[points.len() - 1]] {
+                let on_border = [end.x, end.y].iter().any(|&v| v == 0.0 || v == 20.0);
+                assert!(on_border, "{end:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn saddles_follow_the_center_value() {
+        // High corners top-left and bottom-right.
+        let field = [1.0, 0.0, 0.0, 1.0];
+        let lines = contours(&field, 1, 0.4);
+        assert_eq!(lines.len(), 2);
+        // The center (0.5) is above 0.4, so the low corners are cut off:
+        // each line stays on one side of the anti-diagonal x = y.
+        for (points, _) in &lines {
+            let side = points[0].x - points[0].y;
+            assert!(points.iter().all(|p| (p.x - p.y) * side > 0.0));
+        }
+    }
+
+    #[test]
+    fn contours_are_stroked_inside_the_badge() {
+        for seed in 0..8 {
+            let s = scene(seed);
+            let DrawOp::Group { clip: Some(_), ops } = &s.ops[2] else {
+                panic!("expected the clipped contours");
+            };
+            assert!(ops.len() >= 6, "seed {seed}: {}", ops.len());
+            let widths: Vec<f32> = ops
+                .iter()
+                .map(|op| match op {
+                    DrawOp::ShapeStroke { width, .. } => *width,
+                    other => panic!("expected a contour, got {:?}", other),
+                })
+                .collect();
+            let thinnest = widths.iter().copied().fold(f32::MAX, f32::min);
+            assert!(widths.iter().any(|&w| w > thinnest * 2.0), "seed {seed}");
+        }
+    }
+}
@@ -0,0 +1,310 @@
+use std::str::FromStr;
+
+use rand::Rng;
+
+use super::params::Param;
+use super::{DrawOp, Scene};
+use crate::core::geometry::{Circle, Path, Point, Rect, Shape};
+use crate::core::palette::Rgb;
+use crate::{LoGenError, RenderOptions};
+
+const GRID_SIZE: Param = Param::int("grid_size", 2, 64, 6, "Tiles per row and column");
+const TILE_SET: Param = Param::int(
+    "tile_set",
+    0,
+    1,
+    0,
+    "Tile family, 0 for quarter arcs and 1 for diagonals; picked by the seed when not set",
+);
+
+const CIRCLE_PROBABILITY: Param = Param::float(
+    "circle_probability",
+    0.0,
+    1.0,
+    0.4,
+    "Chance of a circular badge instead of a rounded square",
+);
+const CORNER_RADIUS: Param = Param::float(
+    "corner_radius",
+    0.0,
+    0.5,
+    0.2,
+    "Corner radius of the rounded square badge as fraction of its side",
+);
+
+const LINE_WIDTH: Param = Param::float(
+    "line_width",
+    0.05,
+    0.5,
+    0.22,
+    "Line width as fraction of a tile",
+);
+
+/// Parameters `RenderOptions::params` can override.
+pub const PARAMS: &[Param] = &[
+    GRID_SIZE,
+    TILE_SET,
+    CIRCLE_PROBABILITY,
+    CORNER_RADIUS,
+    LINE_WIDTH,
+];
+
+/// Cubic Bezier handle length approximating a quarter circle, as fraction of
+/// the radius. Used instead of trigonometry so every coordinate is exact
+/// arithmetic on the tile grid and output stays byte-stable across
+/// platforms.
+const KAPPA: f32 = 0.552_284_8;
+
+/// Fallback line color on transparent canvases.
+const LINE_FALLBACK: Rgb = Rgb {
+    r: 255,
+    g: 255,
+    b: 255,
+};
+
+/// Truchet tile families.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TileSet {
+    /// Smith tiles: two quarter arcs joining the midpoints of neighbouring
+    /// edges, forming meandering loops.
+    Arcs,
+    /// A single corner-to-corner diagonal, forming a maze.
+    Diagonals,
+}
+
+impl TileSet {
+    /// In the order of their `tile_set` parameter values.
+    pub const ALL: [TileSet; 2] = [TileSet::Arcs, TileSet::Diagonals];
+
+    /// Identifier accepted by `FromStr`.
+    pub fn id(&self) -> &'static str {
+        match self {
+            TileSet::Arcs => "arcs",
+            TileSet::Diagonals => "diagonals",
+        }
+    }
+
+    /// Value of the `tile_set` parameter that selects this family.
+    pub fn param_value(&self) -> f64 {
+        f64::from(*self as u8)
+    }
+}
+
+impl FromStr for TileSet {
+    type Err = LoGenError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "arcs" | "arc" => Ok(TileSet::Arcs),
+            "diagonals" | "diagonal" => Ok(TileSet::Diagonals),
+            _ => Err(LoGenError::InvalidOptions(format!(
+                "tile_set must be one of [arcs, diagonals], got {s}"
+            ))),
+        }
+    }
+}
+
+/// Truchet mark: an N×N grid of seeded, randomly rotated Truchet tiles drawn
+/// as one continuous line pattern and clipped to a badge.
+pub fn build<R: Rng>(
+    _normalized: &str,
+    rng: &mut R,
+    opts: &RenderOptions,
+) -> Result<Scene, LoGenError> {
+    let (width, height) = opts.canvas_size();
+    let square = super::centered_square(opts);
+    let palette = super::palette_for(rng, opts);
+
+    // Always draw the seeded choice so a forced tile set keeps the rest of
+    // the mark (badge, tile orientations) unchanged.
+    let seeded = TileSet::ALL[rng.gen_range(0..TileSet::ALL.len())];
+    let tiles = TILE_SET
+        .overridden(opts)
+        .map_or(seeded, |k| TileSet::ALL[k as usize]);
+
+    let badge = if rng.gen_bool(CIRCLE_PROBABILITY.value(opts)) {
+        Shape::Circle(Circle {
+            cx: square.x + square.w / 2.0,
+            cy: square.y + square.h / 2.0,
+            r: square.w / 2.0,
+        })
+    } else {
+        Shape::Rect {
+            rect: square,
+            rx: square.w * CORNER_RADIUS.value_f32(opts),
+            ry: square.w * CORNER_RADIUS.value_f32(opts),
+        }
+    };
+
+    let n = GRID_SIZE.value_usize(opts);
+    let t = square.w / n as f32;
+    let mut lines = Path::new();
+    for row in 0..n {
+        for col in 0..n {
+            let tile = Rect {
+                x: square.x + col as f32 * t,
+                y: square.y + row as f32 * t,
+                w: t,
+                h: t,
+            };
+            let flipped = rng.gen_bool(0.5);
+            match tiles {
+                TileSet::Arcs => arc_tile(&mut lines, tile, flipped),
+                TileSet::Diagonals => diagonal_tile(&mut lines, tile, flipped),
+            }
+        }
+    }
+
+    let ops = vec![
+        DrawOp::Background {
+            color: palette.background,
+        },
+        DrawOp::ShapeFill {
+            shape: badge.clone(),
+            color: palette.primary,
+        },
+        DrawOp::Group {
+            clip: Some(badge),
+            ops: vec![DrawOp::ShapeStroke {
+                shape: Shape::Path(lines),
+                color: palette.background.unwrap_or(LINE_FALLBACK),
+                width: t * LINE_WIDTH.value_f32(opts),
+            }],
+        },
+    ];
+
+    Ok(Scene {
+        width,
+        height,
+        ops,
+        palette,
+    })
+}
+
+/// Two quarter arcs around opposite corners of `tile`: top-left and
+/// bottom-right, or top-right and bottom-left when `flipped`.
+fn arc_tile(path: &mut Path, tile: Rect, flipped: bool) {
+    let r = tile.w / 2.0;
+    let (left, top) = (tile.x, tile.y);
+    let (right, bottom) = (tile.x + tile.w, tile.y + tile.h);
+    let (mid_x, mid_y) = (tile.x + r, tile.y + r);
+    let k = KAPPA * r;
+
+    if flipped {
+        // Around the top-right corner, then the bottom-left one.
+        path.move_to(Point::new(mid_x, top));
+        path.cubic_to(
+            Point::new(mid_x, top + k),
+            Point::new(right - k, mid_y),
+            Point::new(right, mid_y),
+        );
+        path.move_to(Point::new(left, mid_y));
+        path.cubic_to(
+            Point::new(left + k, mid_y),
+            Point::new(mid_x, bottom - k),
+            Point::new(mid_x, bottom),
+        );
+    } else {
+        // Around the top-left corner, then the bottom-right one.
+        path.move_to(Point::new(mid_x, top));
+        path.cubic_to(
+            Point::new(mid_x, top + k),
+            Point::new(left + k, mid_y),
+            Point::new(left, mid_y),
+        );
+        path.move_to(Point::new(right, mid_y));
+        path.cubic_to(
+            Point::new(right - k, mid_y),
+            Point::new(mid_x, bottom - k),
+            Point::new(mid_x, bottom),
+        );
+    }
+}
+
+/// The `\` diagonal of `tile`, or `/` when `flipped`.
+fn diagonal_tile(path: &mut Path, tile: Rect, flipped: bool) {
+    let (right, bottom) = (tile.x + tile.w, tile.y + tile.h);
+    if flipped {
+        path.move_to(Point::new(right, tile.y));
+        path.line_to(Point::new(tile.x, bottom));
+    } else {
+        path.move_to(Point::new(tile.x, tile.y));
+        path.line_to(Point::new(right, bottom));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{build, TileSet};
+    use crate::algorithms::{DrawOp, Scene};
+    use crate::core::geometry::{PathCmd, Shape};
+    use crate::RenderOptions;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn scene(opts: &RenderOptions) -> Scene {
+        let mut rng = ChaCha8Rng::seed_from_u64(4);
+        build("Truchet", &mut rng, opts).expect("build failed")
+    }
+
+    fn line_cmds(scene: &Scene) -> Vec<PathCmd> {
+        match &scene.ops[2] {
+            DrawOp::Group { ops, .. } => match &ops[0] {
+                DrawOp::ShapeStroke {
+                    shape: Shape::Path(path),
+                    ..
+                } => path.cmds.clone(),
+                other => panic!("expected the tile lines, got {:?}", other),
+            },
+            other => panic!("expected clipped group, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn one_subpath_per_line_segment() {
+        let count = |tile_set: TileSet, grid_size: u32| {
+            let opts = RenderOptions {
+                params: [
+                    ("grid_size".to_string(), grid_size as f64),
+                    ("tile_set".to_string(), tile_set.param_value()),
+                ]
+                .into(),
+                ..Default::default()
+            };
+            line_cmds(&scene(&opts))
+                .iter()
+                .filter(|cmd| matches!(cmd, PathCmd::MoveTo(_)))
+                .count()
+        };
+        assert_eq!(count(TileSet::Arcs, 6), 2 * 36);
+        assert_eq!(count(TileSet::Diagonals, 6), 36);
+        assert_eq!(count(TileSet::Diagonals, 10), 100);
+    }
+
+    #[test]
+    fn forcing_the_tile_set_keeps_the_badge() {
+        let arcs = scene(&RenderOptions {
+            params: [("tile_set".to_string(), 0.0)].into(),
+            ..Default::default()
+        });
+        let diagonals = scene(&RenderOptions {
+            params: [("tile_set".to_string(), 1.0)].into(),
+            ..Default::default()
+        });
+        assert_eq!(
+            format!("{:?}", arcs.ops[1]),
+            format!("{:?}", diagonals.ops[1])
+        );
+    }
+
+    #[test]
+    fn parses_tile_sets() {
+        assert_eq!("arcs".parse::<TileSet>().unwrap(), TileSet::Arcs);
+        assert_eq!(" Diagonal ".parse::<TileSet>().unwrap(), TileSet::Diagonals);
+        assert!("hexagons".parse::<TileSet>().is_err());
+        for (i, tiles) in TileSet::ALL.into_iter().enumerate() {
+            assert_eq!(tiles.id().parse::<TileSet>().unwrap(), tiles);
+            assert_eq!(tiles.param_value(), i as f64);
+        }
+    }
+}
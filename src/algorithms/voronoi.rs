@@ -0,0 +1,338 @@
+use rand::Rng;
+
+use super::monogram_badge::initials_from_normalized;
+use super::params::Param;
+use super::{DrawOp, Scene};
+use crate::core::geometry::{self, Circle, Path, Point, Rect, Shape};
+use crate::core::palette::Rgb;
+use crate::core::{glyphs, triangulation};
+use crate::{LoGenError, RenderOptions};
+
+/// The default is fine enough for the highlighted initials to stay legible.
+const GRID_SIZE: Param = Param::int(
+    "grid_size",
+    2,
+    64,
+    14,
+    "Sites per row and column along the shorter side",
+);
+
+const JITTER: Param = Param::float(
+    "jitter",
+    0.01,
+    0.5,
+    0.45,
+    "Largest offset of a site from its cell center, as fraction of a cell",
+);
+
+const BADGE_PROBABILITY: Param = Param::float(
+    "badge_probability",
+    0.0,
+    1.0,
+    0.5,
+    "Chance of clipping the mosaic to a badge",
+);
+const CIRCLE_PROBABILITY: Param = Param::float(
+    "circle_probability",
+    0.0,
+    1.0,
+    0.5,
+    "Chance of a circular badge instead of a rounded square",
+);
+const CORNER_RADIUS: Param = Param::float(
+    "corner_radius",
+    0.0,
+    0.5,
+    0.2,
+    "Corner radius of the rounded square badge as fraction of its side",
+);
+
+const LIGHTNESS_JITTER: Param = Param::float(
+    "lightness_jitter",
+    0.001,
+    0.2,
+    0.06,
+    "Largest random lightness shift of a cell",
+);
+
+const HIGHLIGHT_PROBABILITY: Param = Param::float(
+    "highlight_probability",
+    0.0,
+    1.0,
+    0.5,
+    "Chance of highlighting the cells under the initials",
+);
+
+/// Size of the initials' block as fraction of the mosaic's shorter side.
+const INITIALS_SIZE: f32 = 0.8;
+/// Gap between the initials as fraction of the font size.
+const TRACKING: f32 = 0.1;
+/// HSL lightness shift of highlighted cells.
+const HIGHLIGHT_DARKEN: f32 = 0.12;
+const MUTE: Param = Param::float(
+    "mute",
+    0.0,
+    1.0,
+    0.6,
+    "How far cells outside the initials fade towards the background",
+);
+
+/// Parameters `RenderOptions::params` can override.
+pub const PARAMS: &[Param] = &[
+    GRID_SIZE,
+    JITTER,
+    BADGE_PROBABILITY,
+    CIRCLE_PROBABILITY,
+    CORNER_RADIUS,
+    LIGHTNESS_JITTER,
+    HIGHLIGHT_PROBABILITY,
+    MUTE,
+];
+
+/// Fade target on transparent canvases.
+const MUTE_FALLBACK: Rgb = Rgb {
+    r: 255,
+    g: 255,
+    b: 255,
+};
+
+/// How far each cell is grown outwards in pixels, so antialiased edges of
+/// neighbouring cells overlap instead of letting the background show
+/// through as hairline seams.
+const SEAM_OVERLAP: f32 = 0.5;
+
+/// Voronoi mosaic: seeded sites on a jittered lattice partition the padded
+/// canvas (or a badge clipped out of it) into cells, each filled from the
+/// palette with a little lightness jitter. Optionally the cells covering the
+/// initials are picked out in a darker primary.
+pub fn build<R: Rng>(
+    normalized: &str,
+    rng: &mut R,
+    opts: &RenderOptions,
+) -> Result<Scene, LoGenError> {
+    let (width, height) = opts.canvas_size();
+    let palette = super::palette_for(rng, opts);
+
+    let badge = if rng.gen_bool(BADGE_PROBABILITY.value(opts)) {
+        let square = super::centered_square(opts);
+        Some(if rng.gen_bool(CIRCLE_PROBABILITY.value(opts)) {
+            Shape::Circle(Circle {
+                cx: square.x + square.w / 2.0,
+                cy: square.y + square.h / 2.0,
+                r: square.w / 2.0,
+            })
+        } else {
+            Shape::Rect {
+                rect: square,
+                rx: square.w * CORNER_RADIUS.value_f32(opts),
+                ry: square.w * CORNER_RADIUS.value_f32(opts),
+            }
+        })
+    } else {
+        None
+    };
+    let area = match &badge {
+        Some(shape) => shape.bounds(),
+        None => {
+            let (pad_x, pad_y) = opts.padding_px();
+            Rect {
+                x: pad_x,
+                y: pad_y,
+                w: width as f32 - 2.0 * pad_x,
+                h: height as f32 - 2.0 * pad_y,
+            }
+        }
+    };
+
+    let n = GRID_SIZE.value_usize(opts);
+    let cell = area.w.min(area.h) / n as f32;
+    let cols = ((area.w / cell).round() as usize).max(1);
+    let rows = ((area.h / cell).round() as usize).max(1);
+    let (cell_w, cell_h) = (area.w / cols as f32, area.h / rows as f32);
+    let mut sites = Vec::with_capacity(cols * rows);
+    for row in 0..rows {
+        for col in 0..cols {
+            sites.push(Point::new(
+                area.x
+                    + (col as f32
+                        + 0.5
+                        + rng.gen_range(-JITTER.value_f32(opts)..JITTER.value_f32(opts)))
+                        * cell_w,
+                area.y
+                    + (row as f32
+                        + 0.5
+                        + rng.gen_range(-JITTER.value_f32(opts)..JITTER.value_f32(opts)))
+                        * cell_h,
+            ));
+        }
+    }
+
+    let initials = if rng.gen_bool(HIGHLIGHT_PROBABILITY.value(opts)) {
+        initials_outline(&initials_from_normalized(normalized).to_uppercase(), area)
+    } else {
+        Vec::new()
+    };
+    // With the initials picked out in primary, the rest of the mosaic keeps
+    // to the other roles, faded, so the letters stay readable.
+    let fill_roles: Vec<Rgb> = if initials.is_empty() {
+        vec![palette.primary, palette.secondary, palette.tertiary]
+    } else {
+        let faded = palette.background.unwrap_or(MUTE_FALLBACK);
+        vec![
+            palette.secondary.lerp(faded, MUTE.value_f32(opts)),
+            palette.tertiary.lerp(faded, MUTE.value_f32(opts)),
+        ]
+    };
+    let highlight = palette.primary.with_lightness_offset(-HIGHLIGHT_DARKEN);
+
+    let mut cells = Vec::with_capacity(sites.len());
+    for polygon in triangulation::voronoi(&sites, area) {
+        let jitter =
+            rng.gen_range(-LIGHTNESS_JITTER.value_f32(opts)..LIGHTNESS_JITTER.value_f32(opts));
+        let role = fill_roles[rng.gen_range(0..fill_roles.len())];
+        if polygon.len() < 3 {
+            continue;
+        }
+        let n = polygon.len() as f32;
+        let centroid = Point::new(
+            polygon.iter().map(|p| p.x).sum::<f32>() / n,
+            polygon.iter().map(|p| p.y).sum::<f32>() / n,
+        );
+        let covered = initials.iter().any(|glyph| glyph.winding(centroid) != 0);
+        cells.push(DrawOp::ShapeFill {
+            shape: Shape::polygon(&geometry::grow_from_centroid(&polygon, SEAM_OVERLAP)),
+            color: if covered { highlight } else { role }.with_lightness_offset(jitter),
+        });
+    }
+
+    let mut ops = vec![DrawOp::Background {
+        color: palette.background,
+    }];
+    match badge {
+        Some(clip) => ops.push(DrawOp::Group {
+            clip: Some(clip),
+            ops: cells,
+        }),
+        None => ops.extend(cells),
+    }
+
+    Ok(Scene {
+        width,
+        height,
+        ops,
+        palette,
+    })
+}
+
+/// Glyph outlines of `initials`, fitted and centered in `area`.
+fn initials_outline(initials: &str, area: Rect) -> Vec<Path> {
+    let font = glyphs::geometry_font();
+    let size = 100.0;
+    let letters = glyphs::lay_out(font, initials, size, TRACKING * size);
+
+    let mut min = Point::new(f32::MAX, f32::MAX);
+    let mut max = Point::new(f32::MIN, f32::MIN);
+    for (poly, _) in letters.iter().flat_map(|glyph| glyph.flatten()) {
+        for p in poly {
+            min = Point::new(min.x.min(p.x), min.y.min(p.y));
+            max = Point::new(max.x.max(p.x), max.y.max(p.y));
+        }
+    }
+    if min.x >= max.x || min.y >= max.y {
+        return Vec::new();
+    }
+
+    let target = INITIALS_SIZE * area.w.min(area.h);
+    let scale = target / (max.x - min.x).max(max.y - min.y);
+    let mid = Point::new((min.x + max.x) / 2.0, (min.y + max.y) / 2.0);
+    let center = Point::new(area.x + area.w / 2.0, area.y + area.h / 2.0);
+    letters
+        .iter()
+        .map(|glyph| {
+            glyph.map(|p| {
+                Point::new(
+                    center.x + (p.x - mid.x) * scale,
+                    center.y + (p.y - mid.y) * scale,
+                )
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::build;
+    use crate::algorithms::{DrawOp, Scene};
+    use crate::core::geometry::Shape;
+    use crate::RenderOptions;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn scene(seed: u64, opts: &RenderOptions) -> Scene {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        build("Vor Onoi", &mut rng, opts).expect("build failed")
+    }
+
+    fn cells(scene: &Scene) -> Vec<&DrawOp> {
+        scene
+            .ops
+            .iter()
+            .flat_map(|op| match op {
+                DrawOp::Group { ops, .. } => ops.iter().collect(),
+                DrawOp::ShapeFill { .. } => vec![op],
+                _ => Vec::new(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn one_cell_per_site() {
+        for seed in 0..8 {
+            let opts = RenderOptions {
+                params: [("grid_size".to_string(), 6.0)].into(),
+                ..Default::default()
+            };
+            assert_eq!(cells(&scene(seed, &opts)).len(), 36, "seed {seed}");
+        }
+    }
+
+    #[test]
+    fn cells_are_polygons() {
+        for op in cells(&scene(1, &RenderOptions::default())) {
+            match op {
+                DrawOp::ShapeFill {
+                    shape: Shape::Path(path),
+                    ..
+                } => assert!(path.flatten().iter().all(|(_, closed)| *closed)),
+                other => panic!("expected a cell, got {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn badge_and_highlight_are_seeded() {
+        let (mut badges, mut highlights) = (0, 0);
+        for seed in 0..24 {
+            let s = scene(seed, &RenderOptions::default());
+            if matches!(s.ops[1], DrawOp::Group { clip: Some(_), .. }) {
+                badges += 1;
+            }
+            let primary = s.palette.primary;
+            // Highlighted cells are darkened primary; without a highlight
+            // primary cells are only jittered.
+            let darkened = cells(&s).iter().any(|op| match op {
+                DrawOp::ShapeFill { color, .. } => {
+                    let (_, _, l) = crate::core::palette::rgb_to_hsl(*color);
+                    let (_, _, p) = crate::core::palette::rgb_to_hsl(primary);
+                    l < p - 0.07
+                }
+                _ => false,
+            });
+            if darkened {
+                highlights += 1;
+            }
+        }
+        assert!(badges > 0 && badges < 24, "{badges}");
+        assert!(highlights > 0, "{highlights}");
+    }
+}
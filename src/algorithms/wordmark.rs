@@ -0,0 +1,337 @@
+use rand::Rng;
+
+use super::params::{self, Param};
+use super::{DrawOp, Scene, MAX_SIZE_PX, MIN_SIZE_PX};
+use crate::core::geometry::{self, Point};
+use crate::core::typography::{self, CAP_HEIGHT, DESCENDER, X_HEIGHT};
+use crate::{LoGenError, RenderOptions};
+
+/// Font size as fraction of `size_px`; the canvas is sized around the text.
+const FONT_SIZE: f32 = 0.25;
+
+const MIN_LETTER_SPACING: Param = Param::float(
+    "min_letter_spacing",
+    -0.1,
+    0.5,
+    -0.02,
+    "Tightest letter spacing as fraction of the font size",
+);
+const MAX_LETTER_SPACING: Param = Param::float(
+    "max_letter_spacing",
+    -0.1,
+    0.5,
+    0.12,
+    "Loosest letter spacing as fraction of the font size",
+);
+
+/// Parameters `RenderOptions::params` can override.
+pub const PARAMS: &[Param] = &[MIN_LETTER_SPACING, MAX_LETTER_SPACING];
+
+/// Distance from the baseline to underline accents, as fraction of the font
+/// size.
+const ACCENT_GAP: f32 = 0.28;
+/// Thickness of underline accents as fraction of the font size.
+const ACCENT_THICKNESS: f32 = 0.1;
+/// Dot accent radius as fraction of the font size.
+const DOT_RADIUS: f32 = 0.11;
+/// Space between the text and the dot accent, as fraction of the font size.
+const DOT_GAP: f32 = 0.06;
+
+/// Samples along the swoosh accent.
+const SWOOSH_SAMPLES: usize = 24;
+
+#[derive(Debug, Clone, Copy)]
+enum CaseStyle {
+    Upper,
+    Lower,
+    Title,
+    AsIs,
+}
+
+impl CaseStyle {
+    const ALL: [CaseStyle; 4] = [
+        CaseStyle::Upper,
+        CaseStyle::Lower,
+        CaseStyle::Title,
+        CaseStyle::AsIs,
+    ];
+
+    fn apply(self, s: &str) -> String {
+        match self {
+            CaseStyle::Upper => s.to_uppercase(),
+            CaseStyle::Lower => s.to_lowercase(),
+            CaseStyle::Title => s
+                .split(' ')
+                .map(|word| {
+                    let mut chars = word.chars();
+                    match chars.next() {
+                        Some(first) => first
+                            .to_uppercase()
+                            .chain(chars.flat_map(char::to_lowercase))
+                            .collect(),
+                        None => String::new(),
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(" "),
+            CaseStyle::AsIs => s.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Accent {
+    Swoosh,
+    Dot,
+    Bar,
+}
+
+impl Accent {
+    const ALL: [Accent; 3] = [Accent::Swoosh, Accent::Dot, Accent::Bar];
+
+    /// Depth below the baseline the accent needs, as fraction of the font
+    /// size.
+    fn depth(self) -> f32 {
+        match self {
+            Accent::Swoosh => ACCENT_GAP + 2.0 * ACCENT_THICKNESS,
+            Accent::Bar => ACCENT_GAP + ACCENT_THICKNESS,
+            Accent::Dot => DESCENDER,
+        }
+    }
+
+    /// Width the accent adds after the text, as fraction of the font size.
+    fn trailing(self) -> f32 {
+        match self {
+            Accent::Dot => DOT_GAP + 2.0 * DOT_RADIUS,
+            Accent::Swoosh | Accent::Bar => 0.0,
+        }
+    }
+}
+
+/// Horizontal wordmark: the whole name in a seeded typeface, case style and
+/// letter spacing, with an accent shape. The canvas is sized from the
+/// measured text, with `size_px` setting the scale, unless the caller fixes
+/// the canvas size, in which case the text is fitted and centered in it.
+pub fn build<R: Rng>(
+    normalized: &str,
+    rng: &mut R,
+    opts: &RenderOptions,
+) -> Result<Scene, LoGenError> {
+    let palette = super::palette_for(rng, opts);
+    let typo = typography::FONT_STACKS[rng.gen_range(0..typography::FONT_STACKS.len())].clone();
+    let case = CaseStyle::ALL[rng.gen_range(0..CaseStyle::ALL.len())];
+    let spacing_frac = params::gen_between(
+        rng,
+        MIN_LETTER_SPACING.value_f32(opts),
+        MAX_LETTER_SPACING.value_f32(opts),
+    );
+    let accent = Accent::ALL[rng.gen_range(0..Accent::ALL.len())];
+    let accent_color = if rng.gen_bool(0.5) {
+        palette.secondary
+    } else {
+        palette.tertiary
+    };
+
+    let text = case.apply(normalized);
+
+    // Everything scales with the font size: measure the text block (text
+    // plus accent) at unit size first, with the picked stack's metrics.
+    // The text is set to exactly this width, so the accents placed from it
+    // meet the text whichever font renders it. SVG adds spacing after the
+    // last glyph too; it is not ink.
+    let unit_text_w = typo.text_width(&text, 1.0, spacing_frac) - spacing_frac;
+    let unit_block_w = unit_text_w + accent.trailing();
+    let unit_block_h = CAP_HEIGHT + accent.depth();
+
+    let (width, height, font_size) = if opts.has_explicit_canvas() {
+        let (width, height) = opts.canvas_size();
+        let (pad_x, pad_y) = opts.padding_px();
+        let font_size = ((width as f32 - 2.0 * pad_x) / unit_block_w)
+            .min((height as f32 - 2.0 * pad_y) / unit_block_h)
+            .max(1.0);
+        (width, height, font_size)
+    } else {
+        // Padding scales with the text; long names shrink to stay within
+        // the maximum canvas width.
+        let unit_pad = opts.padding_frac / FONT_SIZE;
+        let font_size = (opts.size_px as f32 * FONT_SIZE)
+            .min((MAX_SIZE_PX - 1) as f32 / (unit_block_w + 2.0 * unit_pad));
+        let pad = unit_pad * font_size;
+        (
+            ((unit_block_w * font_size + 2.0 * pad).ceil() as u32).max(MIN_SIZE_PX),
+            ((unit_block_h * font_size + 2.0 * pad).ceil() as u32).max(MIN_SIZE_PX),
+            font_size,
+        )
+    };
+
+    let spacing = spacing_frac * font_size;
+    let text_w = unit_text_w * font_size;
+    let text_left = (width as f32 - unit_block_w * font_size) / 2.0;
+    let text_right = text_left + text_w;
+    let baseline = (height as f32 - unit_block_h * font_size) / 2.0 + CAP_HEIGHT * font_size;
+
+    let mut ops = vec![
+        DrawOp::Background {
+            color: palette.background,
+        },
+        DrawOp::Text {
+            text,
+            x: (text_left + text_right) / 2.0,
+            y: baseline - X_HEIGHT / 2.0 * font_size,
+            font_family: typo.family.to_string(),
+            font_weight: typo.weight,
+            font_size,
+            color: palette.primary,
+            anchor_middle: true,
+            letter_spacing: spacing,
+            text_length: Some(text_w),
+            path: None,
+        },
+    ];
+
+    let thickness = ACCENT_THICKNESS * font_size;
+    let accent_top = baseline + ACCENT_GAP * font_size;
+    let shape = match accent {
+        Accent::Bar => geometry::Shape::Rect {
+            rect: geometry::Rect {
+                x: text_left,
+                y: accent_top,
+                w: text_w,
+                h: thickness,
+            },
+            rx: thickness / 2.0,
+            ry: thickness / 2.0,
+        },
+        Accent::Dot => {
+            let r = DOT_RADIUS * font_size;
+            geometry::Shape::Circle(geometry::Circle {
+                cx: text_right + DOT_GAP * font_size + r,
+                cy: baseline - r,
+                r,
+            })
+        }
+        Accent::Swoosh => swoosh(text_left, text_right, accent_top, thickness),
+    };
+    ops.push(DrawOp::ShapeFill {
+        shape,
+        color: accent_color,
+    });
+
+    Ok(Scene {
+        width,
+        height,
+        ops,
+        palette,
+    })
+}
+
+/// Crescent under the text that sags, then sweeps up to the right while
+/// growing from a point to full `thickness`.
+fn swoosh(left: f32, right: f32, top: f32, thickness: f32) -> geometry::Shape {
+    let len = right - left;
+    let p0 = Point::new(left, top + thickness);
+    let p1 = Point::new(left + len / 3.0, top + 2.0 * thickness);
+    let p2 = Point::new(left + 2.0 * len / 3.0, top + 1.5 * thickness);
+    let p3 = Point::new(right, top);
+
+    let mut upper = Vec::with_capacity(SWOOSH_SAMPLES + 1);
+    let mut lower = Vec::with_capacity(SWOOSH_SAMPLES + 1);
+    for k in 0..=SWOOSH_SAMPLES {
+        let t = k as f32 / SWOOSH_SAMPLES as f32;
+        let p = geometry::cubic_point(p0, p1, p2, p3, t);
+        let half = thickness / 2.0 * t.sqrt();
+        upper.push(Point::new(p.x, p.y - half));
+        lower.push(Point::new(p.x, p.y + half));
+    }
+    lower.reverse();
+    upper.extend(lower);
+    geometry::Shape::Path(geometry::Path::polygon(&upper))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{build, CaseStyle, FONT_SIZE};
+    use crate::algorithms::{DrawOp, Scene, MAX_SIZE_PX};
+    use crate::core::typography::FONT_STACKS;
+    use crate::RenderOptions;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn scene(input: &str) -> Scene {
+        let mut rng = ChaCha8Rng::seed_from_u64(9);
+        build(input, &mut rng, &RenderOptions::default()).expect("build failed")
+    }
+
+    #[test]
+    fn canvas_is_sized_from_the_text() {
+        let short = scene("Acme");
+        let long = scene("Acme Power and Light");
+        assert!(short.width > short.height);
+        assert!(long.width > short.width);
+        assert_eq!(long.height, short.height);
+    }
+
+    #[test]
+    fn sets_the_whole_name() {
+        let s = scene("Acme Power");
+        let text = s.ops.iter().find_map(|op| match op {
+            DrawOp::Text { text, .. } => Some(text.to_lowercase()),
+            _ => None,
+        });
+        assert_eq!(text.as_deref(), Some("acme power"));
+    }
+
+    #[test]
+    fn very_long_names_stay_within_max_width() {
+        let s = scene(&"Wide ".repeat(200));
+        assert!(s.width <= MAX_SIZE_PX, "{}", s.width);
+    }
+
+    #[test]
+    fn longest_name_fits_the_padded_canvas_in_every_stack() {
+        let opts = RenderOptions::default();
+        let mut seen = vec![false; FONT_STACKS.len()];
+        for seed in 0..64 {
+            let mut rng = ChaCha8Rng::seed_from_u64(seed);
+            let s = build("Mill Hill Illumination Institute", &mut rng, &opts).unwrap();
+            let Some(DrawOp::Text {
+                text,
+                x,
+                font_family,
+                font_size,
+                letter_spacing,
+                text_length,
+                ..
+            }) = s.ops.iter().find(|op| matches!(op, DrawOp::Text { .. }))
+            else {
+                panic!("seed {seed}: no text");
+            };
+            let k = FONT_STACKS
+                .iter()
+                .position(|typo| typo.family == font_family)
+                .expect("seeded stack");
+            seen[k] = true;
+            let ink = FONT_STACKS[k].text_width(text, *font_size, *letter_spacing) - letter_spacing;
+            assert!(
+                text_length.is_some_and(|len| (len - ink).abs() < 1e-2),
+                "seed {seed}"
+            );
+            let center = *x;
+            let pad = opts.padding_frac / FONT_SIZE * font_size;
+            assert!(
+                center - ink / 2.0 >= pad - 1e-2,
+                "seed {seed}: {font_family}"
+            );
+            assert!(
+                center + ink / 2.0 <= s.width as f32 - pad + 1e-2,
+                "seed {seed}: {font_family}"
+            );
+        }
+        assert!(seen.iter().all(|&s| s), "{seen:?}");
+    }
+
+    #[test]
+    fn title_case() {
+        assert_eq!(CaseStyle::Title.apply("acme POWER co"), "Acme Power Co");
+    }
+}
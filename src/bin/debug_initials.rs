@@ -7,7 +7,7 @@ fn main() {
         size_px: 512,
         padding_frac: 0.08,
         variant: Some(1),
-        transparent_background: false,
+        ..Default::default()
     };
 
     match debug_initials_svg(input, &opts) {
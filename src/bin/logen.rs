@@ -1,9 +1,12 @@
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 use clap::{Parser, ValueEnum};
 
-use logen::cli::write_logo_file;
-use logen::{OutputFormat, Preset, RenderOptions};
+use logen::cli::{write_logo_file, write_palette_file};
+use logen::core::palette::Rgb;
+use logen::core::palette_export::PaletteFormat;
+use logen::{MaskShape, OutputFormat, Preset, RenderOptions, TileSet};
 
 #[derive(Debug, Clone, ValueEnum)]
 enum FormatArg {
@@ -20,6 +23,65 @@ impl From<FormatArg> for OutputFormat {
     }
 }
 
+#[derive(Debug, Clone, ValueEnum)]
+enum PaletteFormatArg {
+    Css,
+    Scss,
+    Json,
+    Gpl,
+    Ase,
+}
+
+impl From<PaletteFormatArg> for PaletteFormat {
+    fn from(v: PaletteFormatArg) -> Self {
+        match v {
+            PaletteFormatArg::Css => PaletteFormat::Css,
+            PaletteFormatArg::Scss => PaletteFormat::Scss,
+            PaletteFormatArg::Json => PaletteFormat::Json,
+            PaletteFormatArg::Gpl => PaletteFormat::Gpl,
+            PaletteFormatArg::Ase => PaletteFormat::Ase,
+        }
+    }
+}
+
+#[derive(Debug, Clone, ValueEnum)]
+enum TileSetArg {
+    Arcs,
+    Diagonals,
+}
+
+impl From<TileSetArg> for TileSet {
+    fn from(v: TileSetArg) -> Self {
+        match v {
+            TileSetArg::Arcs => TileSet::Arcs,
+            TileSetArg::Diagonals => TileSet::Diagonals,
+        }
+    }
+}
+
+#[derive(Debug, Clone, ValueEnum)]
+enum MaskArg {
+    Squircle,
+    Superellipse,
+    Hexagon,
+    Shield,
+    Octagon,
+    AdaptiveIcon,
+}
+
+impl From<MaskArg> for MaskShape {
+    fn from(v: MaskArg) -> Self {
+        match v {
+            MaskArg::Squircle => MaskShape::Squircle,
+            MaskArg::Superellipse => MaskShape::Superellipse,
+            MaskArg::Hexagon => MaskShape::Hexagon,
+            MaskArg::Shield => MaskShape::Shield,
+            MaskArg::Octagon => MaskShape::Octagon,
+            MaskArg::AdaptiveIcon => MaskShape::AdaptiveIcon,
+        }
+    }
+}
+
 #[derive(Parser, Debug)]
 #[command(name = "LoGen")]
 #[command(about = "Deterministic logo generator (PNG + SVG)", long_about = None)]
@@ -44,11 +106,27 @@ struct Args {
     #[arg(long)]
     out: Option<PathBuf>,
 
+    /// Export the generated palette in this format (requires --palette-out).
+    #[arg(long, value_enum, requires = "palette_out")]
+    palette_format: Option<PaletteFormatArg>,
+
+    /// Palette export file path (requires --palette-format).
+    #[arg(long, requires = "palette_format")]
+    palette_out: Option<PathBuf>,
+
     /// Output size in pixels (PNG; also used as SVG dimensions).
     #[arg(long, default_value_t = 512)]
     size: u32,
 
-    /// Padding as fraction of canvas size.
+    /// Canvas width in pixels for non-square output (defaults to --size).
+    #[arg(long)]
+    width: Option<u32>,
+
+    /// Canvas height in pixels for non-square output (defaults to --size).
+    #[arg(long)]
+    height: Option<u32>,
+
+    /// Padding as fraction of canvas size, applied per axis.
     #[arg(long, default_value_t = 0.12)]
     padding: f32,
 
@@ -59,6 +137,59 @@ struct Args {
     /// Transparent background (PNG and SVG).
     #[arg(long, default_value_t = false)]
     transparent: bool,
+
+    /// Keep palette colors distinguishable for color-vision deficiencies.
+    #[arg(long, default_value_t = false)]
+    cvd_safe: bool,
+
+    /// Single-ink output; optionally takes the ink color as #RRGGBB.
+    #[arg(long, num_args = 0..=1, default_missing_value = "#000000")]
+    monochrome: Option<String>,
+
+    /// Cells per row/column for grid-based presets (e.g. identicon,
+    /// glyph-grid-pattern); shorthand for --param grid_size=N.
+    #[arg(long)]
+    grid_size: Option<u32>,
+
+    /// Fraction of populated cells in [0.0..1.0] for pattern presets;
+    /// shorthand for --param density=X.
+    #[arg(long)]
+    density: Option<f32>,
+
+    /// Tile family for the truchet preset (seeded when omitted); shorthand
+    /// for --param tile_set=0 (arcs) or 1 (diagonals).
+    #[arg(long, value_enum)]
+    tile_set: Option<TileSetArg>,
+
+    /// Halftone dot spacing as fraction of the mark size in [0.01..0.2];
+    /// shorthand for --param dot_pitch=X.
+    #[arg(long)]
+    dot_pitch: Option<f32>,
+
+    /// Halftone screen angle in degrees within [0..90] (seeded when omitted);
+    /// shorthand for --param dot_angle=X.
+    #[arg(long)]
+    dot_angle: Option<f32>,
+
+    /// Clip the whole logo to an app icon outline.
+    #[arg(long, value_enum)]
+    mask: Option<MaskArg>,
+
+    /// Override a preset parameter as NAME=VALUE; repeatable. See
+    /// --list-presets for each preset's parameters.
+    #[arg(long = "param", value_name = "NAME=VALUE", value_parser = parse_param)]
+    params: Vec<(String, f64)>,
+}
+
+fn parse_param(s: &str) -> Result<(String, f64), String> {
+    let (name, value) = s
+        .split_once('=')
+        .ok_or_else(|| format!("expected NAME=VALUE, got {s}"))?;
+    let value = value
+        .trim()
+        .parse()
+        .map_err(|_| format!("invalid value for {name}: {value}"))?;
+    Ok((name.trim().to_string(), value))
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -69,6 +200,17 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         for preset in Preset::all() {
             println!("  {} [{}]", preset.id(), preset.category());
             println!("    {}", preset.description());
+            for param in preset.params() {
+                println!(
+                    "      --param {}=<{}> in [{}..{}], default {}: {}",
+                    param.name,
+                    param.kind.as_str(),
+                    param.min,
+                    param.max,
+                    param.default,
+                    param.description
+                );
+            }
             println!();
         }
         return Ok(());
@@ -77,17 +219,59 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let input = args
         .input
         .ok_or("--input is required (or use --list-presets)")?;
-    let out = args.out.ok_or("--out is required")?;
+    if args.out.is_none() && args.palette_out.is_none() {
+        return Err("--out is required (or use --palette-out)".into());
+    }
     let preset: Preset = args.preset.parse()?;
+    let monochrome = match args.monochrome.as_deref() {
+        Some(hex) => Some(Rgb::from_hex(hex).ok_or(format!("invalid --monochrome ink: {hex}"))?),
+        None => None,
+    };
+
+    // The per-preset flags are sugar for their --param; an explicit --param
+    // wins.
+    let flags = [
+        ("grid_size", args.grid_size.map(f64::from)),
+        ("density", args.density.map(f64::from)),
+        (
+            "tile_set",
+            args.tile_set.map(|t| TileSet::from(t).param_value()),
+        ),
+        ("dot_pitch", args.dot_pitch.map(f64::from)),
+        ("dot_angle", args.dot_angle.map(f64::from)),
+    ];
+    let mut params: BTreeMap<String, f64> = flags
+        .into_iter()
+        .filter_map(|(name, value)| Some((name.to_string(), value?)))
+        .collect();
+    params.extend(args.params);
 
     let opts = RenderOptions {
         size_px: args.size,
+        width_px: args.width,
+        height_px: args.height,
         padding_frac: args.padding,
         variant: args.variant,
         transparent_background: args.transparent,
+        cvd_safe: args.cvd_safe,
+        monochrome,
+        mask: args.mask.map(MaskShape::from),
+        params,
     };
 
-    write_logo_file(&input, preset, OutputFormat::from(args.format), &out, &opts)?;
+    if let (Some(format), Some(palette_out)) = (args.palette_format, args.palette_out) {
+        write_palette_file(
+            &input,
+            preset,
+            PaletteFormat::from(format),
+            &palette_out,
+            &opts,
+        )?;
+    }
+
+    if let Some(out) = args.out {
+        write_logo_file(&input, preset, OutputFormat::from(args.format), &out, &opts)?;
+    }
 
     Ok(())
 }
@@ -1,6 +1,7 @@
 use std::fs;
 use std::path::Path;
 
+use crate::core::palette_export::PaletteFormat;
 use crate::{LoGen, LoGenError, OutputFormat, Preset, RenderOptions};
 
 /// Write a generated logo to `out_path` using provided format and options.
@@ -24,6 +25,19 @@ pub fn write_logo_file(
     Ok(())
 }
 
+/// Write the palette picked for `input` to `out_path` in the given format.
+pub fn write_palette_file(
+    input: &str,
+    preset: Preset,
+    format: PaletteFormat,
+    out_path: &Path,
+    opts: &RenderOptions,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let bytes = LoGen::export_palette(input, preset, opts, format)?;
+    fs::write(out_path, bytes)?;
+    Ok(())
+}
+
 /// Produce the SVG string used by the `debug_initials` binary.
 pub fn debug_initials_svg(input: &str, opts: &RenderOptions) -> Result<String, LoGenError> {
     let scene = crate::algorithms::build_scene(input, Preset::MonogramBadge, opts)?;
@@ -54,6 +68,24 @@ mod tests {
         let _ = fs::remove_file(&out);
     }
 
+    #[test]
+    fn write_palette_file_creates_file() {
+        let opts = RenderOptions::default();
+        let out = env::temp_dir().join("logen_test_palette.gpl");
+        let _ = fs::remove_file(&out);
+        write_palette_file(
+            "TestPalette",
+            Preset::MonogramBadge,
+            PaletteFormat::Gpl,
+            &out,
+            &opts,
+        )
+        .expect("write palette");
+        let s = fs::read_to_string(&out).expect("read palette");
+        assert!(s.starts_with("GIMP Palette"));
+        let _ = fs::remove_file(&out);
+    }
+
     #[test]
     fn debug_initials_returns_svg() {
         let opts = RenderOptions::default();
@@ -0,0 +1,359 @@
+//! Boolean operations (union, intersection, difference) on filled paths.
+//!
+//! Both operands are flattened to polygons and read with the non-zero fill
+//! rule. Every edge is split where it crosses any other edge or another
+//! edge's vertex touches it; a piece is kept when the result's inside lies
+//! on exactly one side of it, turned so the inside is on its left, and the
+//! kept pieces are chained back into closed loops. Holes therefore run
+//! against their outer loops and the result fills correctly under the
+//! non-zero rule. Overlapping collinear edges split into identical pieces,
+//! which are kept once.
+//!
+//! Curves come back as polylines, so operands should be built at the size
+//! they are drawn at.
+
+use std::collections::{HashMap, HashSet};
+
+use super::geometry::{Path, Point};
+
+/// How far either side of an edge piece is probed to classify it, in
+/// canvas pixels.
+const PROBE: f32 = 0.01;
+/// Crossings this close to an edge's ends (as fraction of the edge) count
+/// as meeting at the shared vertex instead.
+const END_TOLERANCE: f32 = 1e-6;
+/// Vertices this close to another edge, in canvas pixels, split it.
+const TOUCH_DISTANCE: f32 = 1e-3;
+/// Input vertices are rounded to multiples of this, in canvas pixels, so
+/// vertices computed separately (e.g. corners of neighbouring cells) but
+/// differing only by rounding error coincide exactly.
+const SNAP: f32 = 1.0 / 1024.0;
+
+/// Which regions a boolean operation keeps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BooleanOp {
+    Union,
+    Intersection,
+    /// The first operand minus the second.
+    Difference,
+}
+
+impl BooleanOp {
+    fn keeps(self, in_a: bool, in_b: bool) -> bool {
+        match self {
+            BooleanOp::Union => in_a || in_b,
+            BooleanOp::Intersection => in_a && in_b,
+            BooleanOp::Difference => in_a && !in_b,
+        }
+    }
+}
+
+/// Area covered by `a` or `b`.
+pub fn union(a: &Path, b: &Path) -> Path {
+    boolean(a, b, BooleanOp::Union)
+}
+
+/// Area covered by both `a` and `b`.
+pub fn intersection(a: &Path, b: &Path) -> Path {
+    boolean(a, b, BooleanOp::Intersection)
+}
+
+/// Area covered by `a` but not `b`.
+pub fn difference(a: &Path, b: &Path) -> Path {
+    boolean(a, b, BooleanOp::Difference)
+}
+
+/// Combine the filled areas of `a` and `b` with `op`.
+pub fn boolean(a: &Path, b: &Path, op: BooleanOp) -> Path {
+    let polys_a = polygons(a);
+    let polys_b = polygons(b);
+    let edges: Vec<(Point, Point)> = polys_a
+        .iter()
+        .chain(&polys_b)
+        .flat_map(|poly| (0..poly.len()).map(move |i| (poly[i], poly[(i + 1) % poly.len()])))
+        .filter(|(p, q)| p != q)
+        .collect();
+
+    // Crossing points, computed once per pair so both edges are split at
+    // bit-identical points and the pieces chain up exactly. Touching
+    // vertices split the edge at the vertex itself for the same reason.
+    let mut splits: Vec<Vec<(f32, Point)>> = vec![Vec::new(); edges.len()];
+    for i in 0..edges.len() {
+        for j in i + 1..edges.len() {
+            if let Some((t, u, p)) = crossing(edges[i], edges[j]) {
+                splits[i].push((t, p));
+                splits[j].push((u, p));
+                continue;
+            }
+            for (edge, other) in [(i, j), (j, i)] {
+                for v in [edges[other].0, edges[other].1] {
+                    if let Some(t) = touch(edges[edge], v) {
+                        splits[edge].push((t, v));
+                    }
+                }
+            }
+        }
+    }
+
+    let key = |p: Point| (p.x.to_bits(), p.y.to_bits());
+    let mut seen = HashSet::new();
+    let mut pieces: Vec<(Point, Point)> = Vec::new();
+    for (&(p, q), cuts) in edges.iter().zip(splits.iter_mut()) {
+        cuts.sort_by(|x, y| x.0.total_cmp(&y.0));
+        let mut points = vec![p];
+        points.extend(cuts.iter().map(|&(_, c)| c));
+        points.push(q);
+        for w in points.windows(2) {
+            let (s, e) = (w[0], w[1]);
+            let len = s.distance(e);
+            if len <= f32::EPSILON {
+                continue;
+            }
+            // Collinear edges of both operands split into the same pieces.
+            let undirected = if (key(s), key(e)) <= (key(e), key(s)) {
+                (key(s), key(e))
+            } else {
+                (key(e), key(s))
+            };
+            if !seen.insert(undirected) {
+                continue;
+            }
+            // Left of the direction of travel, in y-down screen space.
+            let n = Point::new((e.y - s.y) / len, (s.x - e.x) / len);
+            let mid = s.lerp(e, 0.5);
+            let inside = |d: f32| {
+                let probe = Point::new(mid.x + n.x * d, mid.y + n.y * d);
+                op.keeps(winding(&polys_a, probe) != 0, winding(&polys_b, probe) != 0)
+            };
+            match (inside(PROBE), inside(-PROBE)) {
+                (true, false) => pieces.push((s, e)),
+                (false, true) => pieces.push((e, s)),
+                _ => {}
+            }
+        }
+    }
+
+    chain(&pieces)
+}
+
+/// Flattened subpaths of `path`, snapped and implicitly closed as for
+/// filling.
+fn polygons(path: &Path) -> Vec<Vec<Point>> {
+    let snap = |v: f32| (v / SNAP).round() * SNAP;
+    path.flatten()
+        .into_iter()
+        .map(|(poly, _)| {
+            let mut poly: Vec<Point> = poly
+                .into_iter()
+                .map(|p| Point::new(snap(p.x), snap(p.y)))
+                .collect();
+            poly.dedup();
+            if poly.len() > 1 && poly.first() == poly.last() {
+                poly.pop();
+            }
+            poly
+        })
+        .filter(|poly| poly.len() > 2)
+        .collect()
+}
+
+/// Non-zero winding number of `polys` around `p`.
+fn winding(polys: &[Vec<Point>], p: Point) -> i32 {
+    let side = |a: Point, b: Point| (b.x - a.x) * (p.y - a.y) - (p.x - a.x) * (b.y - a.y);
+    let mut winding = 0;
+    for poly in polys {
+        for i in 0..poly.len() {
+            let (a, b) = (poly[i], poly[(i + 1) % poly.len()]);
+            if a.y <= p.y {
+                if b.y > p.y && side(a, b) > 0.0 {
+                    winding += 1;
+                }
+            } else if b.y <= p.y && side(a, b) < 0.0 {
+                winding -= 1;
+            }
+        }
+    }
+    winding
+}
+
+/// Where segments `a` and `b` cross strictly inside both, as the position
+/// along each and the point itself.
+fn crossing(a: (Point, Point), b: (Point, Point)) -> Option<(f32, f32, Point)> {
+    let (p, r) = (a.0, Point::new(a.1.x - a.0.x, a.1.y - a.0.y));
+    let (q, s) = (b.0, Point::new(b.1.x - b.0.x, b.1.y - b.0.y));
+    let denom = r.x * s.y - r.y * s.x;
+    if denom.abs() <= f32::EPSILON * (r.x.abs() + r.y.abs()) * (s.x.abs() + s.y.abs()) {
+        return None;
+    }
+    let (qx, qy) = (q.x - p.x, q.y - p.y);
+    let t = (qx * s.y - qy * s.x) / denom;
+    let u = (qx * r.y - qy * r.x) / denom;
+    let inner = END_TOLERANCE..1.0 - END_TOLERANCE;
+    if inner.contains(&t) && inner.contains(&u) {
+        Some((t, u, p.lerp(a.1, t)))
+    } else {
+        None
+    }
+}
+
+/// Where vertex `v` lies on segment `edge` strictly between its ends, as
+/// the position along it.
+fn touch(edge: (Point, Point), v: Point) -> Option<f32> {
+    let (p, q) = edge;
+    let r = Point::new(q.x - p.x, q.y - p.y);
+    let len2 = r.x * r.x + r.y * r.y;
+    if len2 <= f32::EPSILON || v == p || v == q {
+        return None;
+    }
+    let (dx, dy) = (v.x - p.x, v.y - p.y);
+    let t = (dx * r.x + dy * r.y) / len2;
+    let off = (dx * r.y - dy * r.x).abs() / len2.sqrt();
+    if (END_TOLERANCE..1.0 - END_TOLERANCE).contains(&t) && off <= TOUCH_DISTANCE {
+        Some(t)
+    } else {
+        None
+    }
+}
+
+/// Chain directed `pieces` into closed loops by matching end points.
+fn chain(pieces: &[(Point, Point)]) -> Path {
+    let key = |p: Point| (p.x.to_bits(), p.y.to_bits());
+    let mut outgoing: HashMap<(u32, u32), Vec<usize>> = HashMap::new();
+    for (i, &(s, _)) in pieces.iter().enumerate() {
+        outgoing.entry(key(s)).or_default().push(i);
+    }
+
+    let mut used = vec![false; pieces.len()];
+    let mut path = Path::new();
+    for start in 0..pieces.len() {
+        if used[start] {
+            continue;
+        }
+        used[start] = true;
+        let origin = pieces[start].0;
+        path.move_to(origin);
+        let mut at = pieces[start].1;
+        while at != origin {
+            let next = outgoing
+                .get(&key(at))
+                .and_then(|list| list.iter().copied().find(|&i| !used[i]));
+            let Some(next) = next else {
+                // Dangling end from an unsplit touching point; the implicit
+                // close for filling finishes the loop.
+                path.line_to(at);
+                break;
+            };
+            used[next] = true;
+            path.line_to(at);
+            at = pieces[next].1;
+        }
+        path.close();
+    }
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(x: f32, y: f32, side: f32) -> Path {
+        Path::polygon(&[
+            Point::new(x, y),
+            Point::new(x + side, y),
+            Point::new(x + side, y + side),
+            Point::new(x, y + side),
+        ])
+    }
+
+    /// Covered area, sampled on a grid of unit cells over 0..100.
+    fn area(path: &Path) -> f32 {
+        let polys = polygons(path);
+        let mut covered = 0;
+        for y in 0..100 {
+            for x in 0..100 {
+                if winding(&polys, Point::new(x as f32 + 0.5, y as f32 + 0.5)) != 0 {
+                    covered += 1;
+                }
+            }
+        }
+        covered as f32
+    }
+
+    /// Signed shoelace area of every loop; loops keep the inside on their
+    /// left, so holes subtract.
+    fn signed_area(path: &Path) -> f32 {
+        polygons(path)
+            .iter()
+            .map(|poly| {
+                (0..poly.len())
+                    .map(|i| {
+                        let (a, b) = (poly[i], poly[(i + 1) % poly.len()]);
+                        a.x * b.y - b.x * a.y
+                    })
+                    .sum::<f32>()
+                    / 2.0
+            })
+            .sum()
+    }
+
+    #[test]
+    fn overlapping_squares() {
+        let a = square(10.0, 10.0, 40.0);
+        let b = square(30.0, 30.0, 40.0);
+        assert_eq!(area(&union(&a, &b)), 2.0 * 1600.0 - 400.0);
+        assert_eq!(area(&intersection(&a, &b)), 400.0);
+        assert_eq!(area(&difference(&a, &b)), 1200.0);
+        assert!((signed_area(&union(&a, &b)).abs() - 2800.0).abs() < 1e-2);
+    }
+
+    #[test]
+    fn difference_punches_a_hole_that_fills_correctly() {
+        let outer = square(10.0, 10.0, 80.0);
+        let inner = square(30.0, 30.0, 40.0);
+        let ring = difference(&outer, &inner);
+        assert_eq!(area(&ring), 6400.0 - 1600.0);
+        // Two loops running opposite ways.
+        assert_eq!(polygons(&ring).len(), 2);
+        assert!((signed_area(&ring).abs() - 4800.0).abs() < 1e-2);
+    }
+
+    #[test]
+    fn result_does_not_depend_on_operand_winding() {
+        let a = square(10.0, 10.0, 40.0);
+        let mut reversed: Vec<Point> = vec![
+            Point::new(30.0, 30.0),
+            Point::new(70.0, 30.0),
+            Point::new(70.0, 70.0),
+            Point::new(30.0, 70.0),
+        ];
+        reversed.reverse();
+        let b = Path::polygon(&reversed);
+        assert_eq!(area(&difference(&a, &b)), 1200.0);
+    }
+
+    #[test]
+    fn shared_edges_and_touching_vertices() {
+        // Side by side, and a smaller square whose corners touch the middle
+        // of the first one's right edge.
+        let a = square(10.0, 10.0, 40.0);
+        let b = square(50.0, 10.0, 40.0);
+        let c = square(50.0, 20.0, 20.0);
+        let ab = union(&a, &b);
+        assert_eq!(area(&ab), 3200.0);
+        assert_eq!(polygons(&ab).len(), 1);
+        assert_eq!(area(&union(&a, &c)), 2000.0);
+        assert!((signed_area(&union(&a, &c)).abs() - 2000.0).abs() < 1e-2);
+        assert_eq!(area(&difference(&ab, &c)), 2800.0);
+        assert!(intersection(&a, &c).cmds.is_empty());
+    }
+
+    #[test]
+    fn disjoint_and_empty_operands() {
+        let a = square(0.0, 0.0, 20.0);
+        let b = square(50.0, 50.0, 20.0);
+        assert_eq!(area(&union(&a, &b)), 800.0);
+        assert!(intersection(&a, &b).cmds.is_empty());
+        assert_eq!(area(&difference(&a, &Path::new())), 400.0);
+        assert_eq!(area(&union(&Path::new(), &b)), 400.0);
+    }
+}
@@ -1,3 +1,7 @@
+use std::str::FromStr;
+
+use crate::LoGenError;
+
 #[derive(Debug, Clone, Copy)]
 pub struct Rect {
     pub x: f32,
@@ -13,14 +17,993 @@ pub struct Circle {
     pub r: f32,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub enum Shape {
     Rect { rect: Rect, rx: f32, ry: f32 }, // rounded rect
     Circle(Circle),
+    Path(Path),
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Point {
     pub x: f32,
     pub y: f32,
 }
+
+impl Point {
+    pub fn new(x: f32, y: f32) -> Self {
+        Self { x, y }
+    }
+
+    pub fn lerp(self, other: Point, t: f32) -> Point {
+        Point::new(
+            self.x + (other.x - self.x) * t,
+            self.y + (other.y - self.y) * t,
+        )
+    }
+
+    pub fn distance(self, other: Point) -> f32 {
+        ((other.x - self.x).powi(2) + (other.y - self.y).powi(2)).sqrt()
+    }
+}
+
+/// A single path command in absolute canvas coordinates.
+#[derive(Debug, Clone, Copy)]
+pub enum PathCmd {
+    MoveTo(Point),
+    LineTo(Point),
+    QuadTo {
+        ctrl: Point,
+        to: Point,
+    },
+    CubicTo {
+        ctrl1: Point,
+        ctrl2: Point,
+        to: Point,
+    },
+    Close,
+}
+
+/// Vector path made of lines and quadratic/cubic Bezier segments.
+///
+/// Filled paths use the non-zero winding rule; subpaths are implicitly
+/// closed for filling.
+#[derive(Debug, Clone, Default)]
+pub struct Path {
+    pub cmds: Vec<PathCmd>,
+}
+
+/// Segments per curve when flattening, scaled by the curve's control-polygon
+/// length so large curves stay smooth.
+const FLATTEN_MIN_SEGMENTS: usize = 4;
+const FLATTEN_MAX_SEGMENTS: usize = 64;
+/// Approximate length in pixels of each flattened curve segment.
+const FLATTEN_SEGMENT_LENGTH: f32 = 4.0;
+
+/// Bounds on the number of points sampled around a superellipse.
+const SUPERELLIPSE_MIN_SEGMENTS: usize = 64;
+const SUPERELLIPSE_MAX_SEGMENTS: usize = 1024;
+
+/// Exponent of [`Shape::squircle`]: close to the continuous-curvature app
+/// icon outline used by iOS.
+pub const SQUIRCLE_EXPONENT: f32 = 5.0;
+/// Exponent of Piet Hein's superellipse, rounder than the squircle.
+pub const PIET_HEIN_EXPONENT: f32 = 2.5;
+
+/// Height, as fraction of the shield, at which its straight sides start
+/// curving in towards the point.
+const SHIELD_SHOULDER: f32 = 0.4;
+
+fn curve_segments(polygon_len: f32) -> usize {
+    ((polygon_len / FLATTEN_SEGMENT_LENGTH).ceil() as usize)
+        .clamp(FLATTEN_MIN_SEGMENTS, FLATTEN_MAX_SEGMENTS)
+}
+
+impl Path {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn move_to(&mut self, p: Point) {
+        self.cmds.push(PathCmd::MoveTo(p));
+    }
+
+    pub fn line_to(&mut self, p: Point) {
+        self.cmds.push(PathCmd::LineTo(p));
+    }
+
+    pub fn quad_to(&mut self, ctrl: Point, to: Point) {
+        self.cmds.push(PathCmd::QuadTo { ctrl, to });
+    }
+
+    pub fn cubic_to(&mut self, ctrl1: Point, ctrl2: Point, to: Point) {
+        self.cmds.push(PathCmd::CubicTo { ctrl1, ctrl2, to });
+    }
+
+    pub fn close(&mut self) {
+        self.cmds.push(PathCmd::Close);
+    }
+
+    /// Smooth path through `points` using Catmull-Rom splines converted to
+    /// cubic Bezier segments.
+    pub fn catmull_rom(points: &[Point], closed: bool) -> Path {
+        let mut path = Path::new();
+        let n = points.len();
+        if n == 0 {
+            return path;
+        }
+        path.move_to(points[0]);
+        let at = |i: isize| -> Point {
+            if closed {
+                points[i.rem_euclid(n as isize) as usize]
+            } else {
+                points[i.clamp(0, n as isize - 1) as usize]
+            }
+        };
+        let segments = if closed { n } else { n - 1 };
+        for i in 0..segments as isize {
+            let (p0, p1, p2, p3) = (at(i - 1), at(i), at(i + 1), at(i + 2));
+            path.cubic_to(
+                Point::new(p1.x + (p2.x - p0.x) / 6.0, p1.y + (p2.y - p0.y) / 6.0),
+                Point::new(p2.x - (p3.x - p1.x) / 6.0, p2.y - (p3.y - p1.y) / 6.0),
+                p2,
+            );
+        }
+        if closed {
+            path.close();
+        }
+        path
+    }
+
+    /// Circular arc around `center` starting at angle `start` (radians, y
+    /// down, so positive `sweep` runs clockwise on screen).
+    pub fn arc(center: Point, r: f32, start: f32, sweep: f32) -> Path {
+        let mut path = Path::new();
+        path.move_to(Point::new(
+            center.x + r * start.cos(),
+            center.y + r * start.sin(),
+        ));
+        path.arc_to(center, r, start, sweep);
+        path
+    }
+
+    /// Append a circular arc, assuming the current point is already at its
+    /// start. Each quarter turn becomes one cubic segment.
+    pub fn arc_to(&mut self, center: Point, r: f32, start: f32, sweep: f32) {
+        let segments = (sweep.abs() / std::f32::consts::FRAC_PI_2).ceil().max(1.0) as usize;
+        let step = sweep / segments as f32;
+        let k = 4.0 / 3.0 * (step / 4.0).tan() * r;
+        for i in 0..segments {
+            let a0 = start + step * i as f32;
+            let a1 = a0 + step;
+            let (s0, c0) = a0.sin_cos();
+            let (s1, c1) = a1.sin_cos();
+            self.cubic_to(
+                Point::new(center.x + r * c0 - k * s0, center.y + r * s0 + k * c0),
+                Point::new(center.x + r * c1 + k * s1, center.y + r * s1 - k * c1),
+                Point::new(center.x + r * c1, center.y + r * s1),
+            );
+        }
+    }
+
+    /// Closed annular sector between radii `r_inner` and `r_outer` around
+    /// `center`, from angle `start` over `sweep` (see [`Path::arc`]). An
+    /// inner radius of zero gives a pie wedge; a full-turn sweep gives a
+    /// ring, whose hole comes from the inner arc running the other way.
+    pub fn sector(center: Point, r_inner: f32, r_outer: f32, start: f32, sweep: f32) -> Path {
+        let mut path = Path::arc(center, r_outer, start, sweep);
+        let end = start + sweep;
+        if r_inner > 0.0 {
+            path.line_to(Point::new(
+                center.x + r_inner * end.cos(),
+                center.y + r_inner * end.sin(),
+            ));
+            path.arc_to(center, r_inner, end, -sweep);
+        } else {
+            path.line_to(center);
+        }
+        path.close();
+        path
+    }
+
+    /// Closed polygon path through `points`.
+    pub fn polygon(points: &[Point]) -> Path {
+        let mut path = Path::new();
+        for (i, p) in points.iter().enumerate() {
+            if i == 0 {
+                path.move_to(*p);
+            } else {
+                path.line_to(*p);
+            }
+        }
+        if !points.is_empty() {
+            path.close();
+        }
+        path
+    }
+
+    /// The path with `f` applied to every path and control point. Exact for
+    /// affine maps.
+    pub fn map(&self, f: impl Fn(Point) -> Point) -> Path {
+        let cmds = self
+            .cmds
+            .iter()
+            .map(|cmd| match *cmd {
+                PathCmd::MoveTo(p) => PathCmd::MoveTo(f(p)),
+                PathCmd::LineTo(p) => PathCmd::LineTo(f(p)),
+                PathCmd::QuadTo { ctrl, to } => PathCmd::QuadTo {
+                    ctrl: f(ctrl),
+                    to: f(to),
+                },
+                PathCmd::CubicTo { ctrl1, ctrl2, to } => PathCmd::CubicTo {
+                    ctrl1: f(ctrl1),
+                    ctrl2: f(ctrl2),
+                    to: f(to),
+                },
+                PathCmd::Close => PathCmd::Close,
+            })
+            .collect();
+        Path { cmds }
+    }
+
+    /// Bounding box of all path and control points (a conservative bound).
+    pub fn bounds(&self) -> Rect {
+        let mut min = Point::new(f32::MAX, f32::MAX);
+        let mut max = Point::new(f32::MIN, f32::MIN);
+        let mut add = |p: &Point| {
+            min.x = min.x.min(p.x);
+            min.y = min.y.min(p.y);
+            max.x = max.x.max(p.x);
+            max.y = max.y.max(p.y);
+        };
+        for cmd in &self.cmds {
+            match cmd {
+                PathCmd::MoveTo(p) | PathCmd::LineTo(p) => add(p),
+                PathCmd::QuadTo { ctrl, to } => {
+                    add(ctrl);
+                    add(to);
+                }
+                PathCmd::CubicTo { ctrl1, ctrl2, to } => {
+                    add(ctrl1);
+                    add(ctrl2);
+                    add(to);
+                }
+                PathCmd::Close => {}
+            }
+        }
+        if min.x > max.x {
+            return Rect {
+                x: 0.0,
+                y: 0.0,
+                w: 0.0,
+                h: 0.0,
+            };
+        }
+        Rect {
+            x: min.x,
+            y: min.y,
+            w: max.x - min.x,
+            h: max.y - min.y,
+        }
+    }
+
+    /// Flatten curves into polylines, one per subpath. The flag tells
+    /// whether the subpath was explicitly closed.
+    pub fn flatten(&self) -> Vec<(Vec<Point>, bool)> {
+        let mut out = Vec::new();
+        let mut current: Vec<Point> = Vec::new();
+        for cmd in &self.cmds {
+            match *cmd {
+                PathCmd::MoveTo(p) => {
+                    if current.len() > 1 {
+                        out.push((std::mem::take(&mut current), false));
+                    }
+                    current.clear();
+                    current.push(p);
+                }
+                PathCmd::LineTo(p) => current.push(p),
+                PathCmd::QuadTo { ctrl, to } => {
+                    let from = current.last().copied().unwrap_or(ctrl);
+                    let n = curve_segments(from.distance(ctrl) + ctrl.distance(to));
+                    for i in 1..=n {
+                        let t = i as f32 / n as f32;
+                        current.push(from.lerp(ctrl, t).lerp(ctrl.lerp(to, t), t));
+                    }
+                }
+                PathCmd::CubicTo { ctrl1, ctrl2, to } => {
+                    let from = current.last().copied().unwrap_or(ctrl1);
+                    let n = curve_segments(
+                        from.distance(ctrl1) + ctrl1.distance(ctrl2) + ctrl2.distance(to),
+                    );
+                    for i in 1..=n {
+                        let t = i as f32 / n as f32;
+                        let a = from.lerp(ctrl1, t);
+                        let b = ctrl1.lerp(ctrl2, t);
+                        let c = ctrl2.lerp(to, t);
+                        current.push(a.lerp(b, t).lerp(b.lerp(c, t), t));
+                    }
+                }
+                PathCmd::Close => {
+                    if current.len() > 1 {
+                        let start = current[0];
+                        out.push((std::mem::take(&mut current), true));
+                        current.push(start);
+                    }
+                }
+            }
+        }
+        if current.len() > 1 {
+            out.push((current, false));
+        }
+        out
+    }
+
+    /// Total length of the flattened path, including closing edges.
+    pub fn length(&self) -> f32 {
+        self.polyline_edges().map(|(a, b)| a.distance(b)).sum()
+    }
+
+    /// Point at arc length `distance` along the path, with the direction of
+    /// travel there in radians. Distances past either end are clamped.
+    pub fn point_at_length(&self, distance: f32) -> Option<(Point, f32)> {
+        let mut remaining = distance.max(0.0);
+        let mut last = None;
+        for (a, b) in self.polyline_edges() {
+            let len = a.distance(b);
+            if len <= f32::EPSILON {
+                continue;
+            }
+            let angle = (b.y - a.y).atan2(b.x - a.x);
+            if remaining <= len {
+                return Some((a.lerp(b, remaining / len), angle));
+            }
+            remaining -= len;
+            last = Some((b, angle));
+        }
+        last
+    }
+
+    /// Consecutive point pairs of the flattened path, in drawing order.
+    fn polyline_edges(&self) -> impl Iterator<Item = (Point, Point)> {
+        self.flatten().into_iter().flat_map(|(poly, closed)| {
+            let n = poly.len();
+            let edges = if closed { n } else { n - 1 };
+            (0..edges).map(move |i| (poly[i], poly[(i + 1) % n]))
+        })
+    }
+
+    /// Non-zero winding number of the (implicitly closed) path around `p`.
+    pub fn winding(&self, p: Point) -> i32 {
+        let mut winding = 0;
+        for (poly, _) in self.flatten() {
+            for i in 0..poly.len() {
+                let a = poly[i];
+                let b = poly[(i + 1) % poly.len()];
+                if a.y <= p.y {
+                    if b.y > p.y && cross(a, b, p) > 0.0 {
+                        winding += 1;
+                    }
+                } else if b.y <= p.y && cross(a, b, p) < 0.0 {
+                    winding -= 1;
+                }
+            }
+        }
+        winding
+    }
+
+    /// Distance from `p` to the closest point on the path outline. Open
+    /// subpaths are measured as polylines (round ends), closed ones include
+    /// their closing edge.
+    pub fn outline_distance(&self, p: Point) -> f32 {
+        let mut best = f32::MAX;
+        for (poly, closed) in self.flatten() {
+            let n = poly.len();
+            let edges = if closed { n } else { n - 1 };
+            for i in 0..edges {
+                best = best.min(segment_distance(p, poly[i], poly[(i + 1) % n]));
+            }
+        }
+        best
+    }
+
+    /// SVG path data (`d` attribute).
+    pub fn to_svg_data(&self) -> String {
+        let mut parts = Vec::with_capacity(self.cmds.len());
+        for cmd in &self.cmds {
+            parts.push(match cmd {
+                PathCmd::MoveTo(p) => format!("M{:.2} {:.2}", p.x, p.y),
+                PathCmd::LineTo(p) => format!("L{:.2} {:.2}", p.x, p.y),
+                PathCmd::QuadTo { ctrl, to } => {
+                    format!("Q{:.2} {:.2} {:.2} {:.2}", ctrl.x, ctrl.y, to.x, to.y)
+                }
+                PathCmd::CubicTo { ctrl1, ctrl2, to } => format!(
+                    "C{:.2} {:.2} {:.2} {:.2} {:.2} {:.2}",
+                    ctrl1.x, ctrl1.y, ctrl2.x, ctrl2.y, to.x, to.y
+                ),
+                PathCmd::Close => "Z".to_string(),
+            });
+        }
+        parts.join(" ")
+    }
+}
+
+/// Point at parameter `t` on the cubic Bezier curve p0..p3.
+pub fn cubic_point(p0: Point, p1: Point, p2: Point, p3: Point, t: f32) -> Point {
+    let u = 1.0 - t;
+    let (a, b, c, d) = (u * u * u, 3.0 * u * u * t, 3.0 * u * t * t, t * t * t);
+    Point::new(
+        a * p0.x + b * p1.x + c * p2.x + d * p3.x,
+        a * p0.y + b * p1.y + c * p2.y + d * p3.y,
+    )
+}
+
+/// Twice the signed area of triangle (a, b, p); positive when `p` lies left
+/// of the directed edge a->b.
+fn cross(a: Point, b: Point, p: Point) -> f32 {
+    (b.x - a.x) * (p.y - a.y) - (p.x - a.x) * (b.y - a.y)
+}
+
+/// Distance from `p` to the segment a-b.
+pub fn segment_distance(p: Point, a: Point, b: Point) -> f32 {
+    let dx = b.x - a.x;
+    let dy = b.y - a.y;
+    let len2 = dx * dx + dy * dy;
+    if len2 == 0.0 {
+        return p.distance(a);
+    }
+    let t = (((p.x - a.x) * dx + (p.y - a.y) * dy) / len2).clamp(0.0, 1.0);
+    p.distance(Point::new(a.x + t * dx, a.y + t * dy))
+}
+
+/// `points` (a convex polygon) pushed outwards from their centroid by `by`.
+/// Neighbouring fills grown this way overlap by a hair, which keeps their
+/// antialiased shared edges from letting the background show through.
+pub fn grow_from_centroid(points: &[Point], by: f32) -> Vec<Point> {
+    let n = points.len().max(1) as f32;
+    let centroid = Point::new(
+        points.iter().map(|p| p.x).sum::<f32>() / n,
+        points.iter().map(|p| p.y).sum::<f32>() / n,
+    );
+    points
+        .iter()
+        .map(|&p| {
+            let d = p.distance(centroid).max(f32::EPSILON);
+            Point::new(
+                p.x + (p.x - centroid.x) / d * by,
+                p.y + (p.y - centroid.y) / d * by,
+            )
+        })
+        .collect()
+}
+
+impl Shape {
+    /// Closed polygon through `points`, e.g. a triangle.
+    pub fn polygon(points: &[Point]) -> Shape {
+        Shape::Path(Path::polygon(points))
+    }
+
+    /// Superellipse `|x/a|^n + |y/b|^n = 1` inscribed in `rect`, with `n` =
+    /// `exponent`: 2 is an ellipse, larger exponents approach the rect.
+    pub fn superellipse(rect: Rect, exponent: f32) -> Shape {
+        use std::f32::consts::TAU;
+        let (a, b) = (rect.w / 2.0, rect.h / 2.0);
+        let center = Point::new(rect.x + a, rect.y + b);
+        let power = 2.0 / exponent.max(f32::EPSILON);
+        // A multiple of four keeps the samples symmetric about both axes.
+        let segments = (((rect.w + rect.h) * 2.0 / FLATTEN_SEGMENT_LENGTH).ceil() as usize)
+            .clamp(SUPERELLIPSE_MIN_SEGMENTS, SUPERELLIPSE_MAX_SEGMENTS)
+            .div_ceil(4)
+            * 4;
+        let points: Vec<Point> = (0..segments)
+            .map(|i| {
+                let t = i as f32 / segments as f32 * TAU;
+                let (sin, cos) = t.sin_cos();
+                Point::new(
+                    center.x + a * cos.signum() * cos.abs().powf(power),
+                    center.y + b * sin.signum() * sin.abs().powf(power),
+                )
+            })
+            .collect();
+        Shape::polygon(&points)
+    }
+
+    /// App icon squircle filling `rect`: a superellipse with
+    /// [`SQUIRCLE_EXPONENT`], whose corners bend with continuous curvature
+    /// instead of the kink where a rounded rect's arcs meet its sides.
+    pub fn squircle(rect: Rect) -> Shape {
+        Shape::superellipse(rect, SQUIRCLE_EXPONENT)
+    }
+
+    /// Largest regular hexagon centered in `rect`, with a vertex at the top
+    /// and bottom.
+    pub fn hexagon(rect: Rect) -> Shape {
+        // Circumradius; the hexagon is sqrt(3) radii wide.
+        let r = (rect.h / 2.0).min(rect.w / 3f32.sqrt());
+        let half_w = r * 3f32.sqrt() / 2.0;
+        let (cx, cy) = (rect.x + rect.w / 2.0, rect.y + rect.h / 2.0);
+        Shape::polygon(&[
+            Point::new(cx, cy - r),
+            Point::new(cx + half_w, cy - r / 2.0),
+            Point::new(cx + half_w, cy + r / 2.0),
+            Point::new(cx, cy + r),
+            Point::new(cx - half_w, cy + r / 2.0),
+            Point::new(cx - half_w, cy - r / 2.0),
+        ])
+    }
+
+    /// Octagon filling `rect` with a flat side along each of its edges;
+    /// regular when `rect` is square.
+    pub fn octagon(rect: Rect) -> Shape {
+        let cut = rect.w.min(rect.h) / (2.0 + 2f32.sqrt());
+        let (left, top) = (rect.x, rect.y);
+        let (right, bottom) = (rect.x + rect.w, rect.y + rect.h);
+        Shape::polygon(&[
+            Point::new(left + cut, top),
+            Point::new(right - cut, top),
+            Point::new(right, top + cut),
+            Point::new(right, bottom - cut),
+            Point::new(right - cut, bottom),
+            Point::new(left + cut, bottom),
+            Point::new(left, bottom - cut),
+            Point::new(left, top + cut),
+        ])
+    }
+
+    /// Heraldic shield filling `rect`: a flat top and straight sides that
+    /// curve in to a point at the bottom center.
+    pub fn shield(rect: Rect) -> Shape {
+        let (left, top) = (rect.x, rect.y);
+        let (right, bottom) = (rect.x + rect.w, rect.y + rect.h);
+        let cx = rect.x + rect.w / 2.0;
+        let shoulder = top + rect.h * SHIELD_SHOULDER;
+        let mut path = Path::new();
+        path.move_to(Point::new(left, top));
+        path.line_to(Point::new(right, top));
+        path.line_to(Point::new(right, shoulder));
+        path.cubic_to(
+            Point::new(right, top + rect.h * 0.75),
+            Point::new(cx + rect.w * 0.25, bottom - rect.h * 0.08),
+            Point::new(cx, bottom),
+        );
+        path.cubic_to(
+            Point::new(cx - rect.w * 0.25, bottom - rect.h * 0.08),
+            Point::new(left, top + rect.h * 0.75),
+            Point::new(left, shoulder),
+        );
+        path.close();
+        Shape::Path(path)
+    }
+
+    /// Outline of the shape as a closed path, running clockwise on screen.
+    /// Rounded rects use the larger of `rx`/`ry` as a circular corner radius,
+    /// like [`Shape::signed_distance`].
+    pub fn to_path(&self) -> Path {
+        use std::f32::consts::{FRAC_PI_2, PI, TAU};
+        match self {
+            Shape::Circle(c) => {
+                let mut path = Path::arc(Point::new(c.cx, c.cy), c.r, 0.0, TAU);
+                path.close();
+                path
+            }
+            Shape::Rect { rect, rx, ry } => {
+                let r = rx.max(*ry).min(rect.w / 2.0).min(rect.h / 2.0).max(0.0);
+                let (left, top) = (rect.x, rect.y);
+                let (right, bottom) = (rect.x + rect.w, rect.y + rect.h);
+                if r == 0.0 {
+                    return Path::polygon(&[
+                        Point::new(left, top),
+                        Point::new(right, top),
+                        Point::new(right, bottom),
+                        Point::new(left, bottom),
+                    ]);
+                }
+                let mut path = Path::new();
+                path.move_to(Point::new(left + r, top));
+                path.line_to(Point::new(right - r, top));
+                path.arc_to(Point::new(right - r, top + r), r, -FRAC_PI_2, FRAC_PI_2);
+                path.line_to(Point::new(right, bottom - r));
+                path.arc_to(Point::new(right - r, bottom - r), r, 0.0, FRAC_PI_2);
+                path.line_to(Point::new(left + r, bottom));
+                path.arc_to(Point::new(left + r, bottom - r), r, FRAC_PI_2, FRAC_PI_2);
+                path.line_to(Point::new(left, top + r));
+                path.arc_to(Point::new(left + r, top + r), r, PI, FRAC_PI_2);
+                path.close();
+                path
+            }
+            Shape::Path(path) => path.clone(),
+        }
+    }
+
+    /// Axis-aligned bounding box of the shape.
+    pub fn bounds(&self) -> Rect {
+        match self {
+            Shape::Rect { rect, .. } => *rect,
+            Shape::Circle(c) => Rect {
+                x: c.cx - c.r,
+                y: c.cy - c.r,
+                w: 2.0 * c.r,
+                h: 2.0 * c.r,
+            },
+            Shape::Path(path) => path.bounds(),
+        }
+    }
+
+    /// Signed distance from `p` to the shape outline (negative inside).
+    ///
+    /// Rounded rects use the larger of `rx`/`ry` as a circular corner radius,
+    /// matching how the PNG renderer draws them.
+    pub fn signed_distance(&self, p: Point) -> f32 {
+        match self {
+            Shape::Circle(c) => ((p.x - c.cx).powi(2) + (p.y - c.cy).powi(2)).sqrt() - c.r,
+            Shape::Rect { rect, rx, ry } => {
+                let hw = rect.w / 2.0;
+                let hh = rect.h / 2.0;
+                let r = rx.max(*ry).min(hw).min(hh).max(0.0);
+                let qx = (p.x - (rect.x + hw)).abs() - hw + r;
+                let qy = (p.y - (rect.y + hh)).abs() - hh + r;
+                let outside = (qx.max(0.0).powi(2) + qy.max(0.0).powi(2)).sqrt();
+                outside + qx.max(qy).min(0.0) - r
+            }
+            Shape::Path(path) => {
+                let d = path.outline_distance(p);
+                if path.winding(p) != 0 {
+                    -d
+                } else {
+                    d
+                }
+            }
+        }
+    }
+
+    /// Whether `p` lies inside (or on) the shape.
+    pub fn contains(&self, p: Point) -> bool {
+        self.signed_distance(p) <= 0.0
+    }
+}
+
+/// Diameter of the Android adaptive icon safe zone as fraction of the icon
+/// size: launchers may crop anything outside the central 66 of 108 dp.
+pub const ADAPTIVE_ICON_SAFE_ZONE: f32 = 66.0 / 108.0;
+
+/// App icon outlines the whole output can be masked to, see
+/// `RenderOptions::mask`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MaskShape {
+    /// iOS-style continuous-curvature squircle.
+    Squircle,
+    /// Piet Hein's rounder superellipse.
+    Superellipse,
+    Hexagon,
+    Shield,
+    Octagon,
+    /// Android adaptive icon layer: the background stays full bleed for the
+    /// launcher to mask, everything else is kept inside the safe zone.
+    AdaptiveIcon,
+}
+
+impl MaskShape {
+    pub const ALL: [MaskShape; 6] = [
+        MaskShape::Squircle,
+        MaskShape::Superellipse,
+        MaskShape::Hexagon,
+        MaskShape::Shield,
+        MaskShape::Octagon,
+        MaskShape::AdaptiveIcon,
+    ];
+
+    /// Identifier accepted by `FromStr`.
+    pub fn id(&self) -> &'static str {
+        match self {
+            MaskShape::Squircle => "squircle",
+            MaskShape::Superellipse => "superellipse",
+            MaskShape::Hexagon => "hexagon",
+            MaskShape::Shield => "shield",
+            MaskShape::Octagon => "octagon",
+            MaskShape::AdaptiveIcon => "adaptive-icon",
+        }
+    }
+
+    /// Smallest padding, as fraction of the canvas, that keeps the mark's
+    /// square inside the outline's safe area.
+    pub fn min_padding_frac(&self) -> f32 {
+        match self {
+            MaskShape::AdaptiveIcon => (1.0 - ADAPTIVE_ICON_SAFE_ZONE) / 2.0,
+            _ => 0.0,
+        }
+    }
+
+    /// The outline fitted to `rect`; for `AdaptiveIcon` the safe-zone
+    /// circle centered in it.
+    pub fn shape(&self, rect: Rect) -> Shape {
+        match self {
+            MaskShape::Squircle => Shape::squircle(rect),
+            MaskShape::Superellipse => Shape::superellipse(rect, PIET_HEIN_EXPONENT),
+            MaskShape::Hexagon => Shape::hexagon(rect),
+            MaskShape::Shield => Shape::shield(rect),
+            MaskShape::Octagon => Shape::octagon(rect),
+            MaskShape::AdaptiveIcon => Shape::Circle(Circle {
+                cx: rect.x + rect.w / 2.0,
+                cy: rect.y + rect.h / 2.0,
+                r: rect.w.min(rect.h) * ADAPTIVE_ICON_SAFE_ZONE / 2.0,
+            }),
+        }
+    }
+}
+
+impl FromStr for MaskShape {
+    type Err = LoGenError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "squircle" => Ok(MaskShape::Squircle),
+            "superellipse" => Ok(MaskShape::Superellipse),
+            "hexagon" | "hex" => Ok(MaskShape::Hexagon),
+            "shield" => Ok(MaskShape::Shield),
+            "octagon" => Ok(MaskShape::Octagon),
+            "adaptive-icon" | "adaptive" => Ok(MaskShape::AdaptiveIcon),
+            _ => Err(LoGenError::InvalidOptions(format!(
+                "mask must be one of [squircle, superellipse, hexagon, shield, octagon, adaptive-icon], got {s}"
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn circle_signed_distance() {
+        let c = Shape::Circle(Circle {
+            cx: 10.0,
+            cy: 10.0,
+            r: 5.0,
+        });
+        assert!((c.signed_distance(Point { x: 10.0, y: 10.0 }) + 5.0).abs() < 1e-5);
+        assert!((c.signed_distance(Point { x: 18.0, y: 10.0 }) - 3.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn rounded_rect_contains() {
+        let r = Shape::Rect {
+            rect: Rect {
+                x: 0.0,
+                y: 0.0,
+                w: 20.0,
+                h: 10.0,
+            },
+            rx: 4.0,
+            ry: 4.0,
+        };
+        assert!(r.contains(Point { x: 10.0, y: 5.0 }));
+        // The very corner is cut off by the radius.
+        assert!(!r.contains(Point { x: 0.2, y: 0.2 }));
+        assert!(!r.contains(Point { x: 25.0, y: 5.0 }));
+    }
+
+    #[test]
+    fn path_polygon_contains_and_distance() {
+        let square = Shape::Path(Path::polygon(&[
+            Point::new(0.0, 0.0),
+            Point::new(10.0, 0.0),
+            Point::new(10.0, 10.0),
+            Point::new(0.0, 10.0),
+        ]));
+        assert!(square.contains(Point::new(5.0, 5.0)));
+        assert!(!square.contains(Point::new(15.0, 5.0)));
+        assert!((square.signed_distance(Point::new(5.0, 2.0)) + 2.0).abs() < 1e-5);
+        assert!((square.signed_distance(Point::new(13.0, 5.0)) - 3.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn flatten_cubic_ends_at_endpoint() {
+        let mut path = Path::new();
+        path.move_to(Point::new(0.0, 0.0));
+        path.cubic_to(
+            Point::new(10.0, 20.0),
+            Point::new(30.0, 20.0),
+            Point::new(40.0, 0.0),
+        );
+        let flat = path.flatten();
+        assert_eq!(flat.len(), 1);
+        let (poly, closed) = &flat[0];
+        assert!(!closed);
+        assert_eq!(*poly.last().unwrap(), Point::new(40.0, 0.0));
+    }
+
+    #[test]
+    fn catmull_rom_passes_through_points() {
+        let pts = [
+            Point::new(0.0, 0.0),
+            Point::new(10.0, 5.0),
+            Point::new(20.0, 0.0),
+        ];
+        let path = Path::catmull_rom(&pts, false);
+        assert_eq!(path.cmds.len(), 3);
+        match path.cmds[1] {
+            PathCmd::CubicTo { to, .. } => assert_eq!(to, pts[1]),
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn svg_data_formatting() {
+        let mut path = Path::new();
+        path.move_to(Point::new(1.0, 2.0));
+        path.quad_to(Point::new(3.0, 4.0), Point::new(5.0, 6.0));
+        path.close();
+        assert_eq!(path.to_svg_data(), "M1.00 2.00 Q3.00 4.00 5.00 6.00 Z");
+    }
+
+    #[test]
+    fn arc_length_matches_circumference() {
+        let center = Point::new(50.0, 50.0);
+        let path = Path::arc(center, 20.0, 0.0, 2.0 * std::f32::consts::PI);
+        let expected = 2.0 * std::f32::consts::PI * 20.0;
+        assert!((path.length() - expected).abs() < 0.5, "{}", path.length());
+    }
+
+    #[test]
+    fn to_path_matches_the_shape() {
+        let shapes = [
+            Shape::Circle(Circle {
+                cx: 50.0,
+                cy: 50.0,
+                r: 30.0,
+            }),
+            Shape::Rect {
+                rect: Rect {
+                    x: 10.0,
+                    y: 20.0,
+                    w: 80.0,
+                    h: 40.0,
+                },
+                rx: 10.0,
+                ry: 10.0,
+            },
+        ];
+        for shape in shapes {
+            let path = Shape::Path(shape.to_path());
+            for y in 0..20 {
+                for x in 0..20 {
+                    let p = Point::new(x as f32 * 5.0 + 2.5, y as f32 * 5.0 + 2.5);
+                    if shape.signed_distance(p).abs() > 0.5 {
+                        assert_eq!(path.contains(p), shape.contains(p), "{p:?}");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn map_moves_control_points_too() {
+        let mut path = Path::new();
+        path.move_to(Point::new(0.0, 0.0));
+        path.quad_to(Point::new(5.0, 10.0), Point::new(10.0, 0.0));
+        let moved = path.map(|p| Point::new(p.x * 2.0, p.y + 1.0));
+        let b = moved.bounds();
+        assert_eq!((b.x, b.y, b.w, b.h), (0.0, 1.0, 20.0, 10.0));
+        assert_eq!(moved.cmds.len(), path.cmds.len());
+    }
+
+    #[test]
+    fn sector_covers_its_wedge_only() {
+        let center = Point::new(0.0, 0.0);
+        let quarter = Path::sector(center, 20.0, 50.0, 0.0, std::f32::consts::FRAC_PI_2);
+        // Clockwise on screen from +x: the wedge is in the lower right.
+        assert_ne!(quarter.winding(Point::new(25.0, 25.0)), 0);
+        assert_eq!(quarter.winding(Point::new(10.0, 10.0)), 0);
+        assert_eq!(quarter.winding(Point::new(40.0, 40.0)), 0);
+        assert_eq!(quarter.winding(Point::new(-25.0, 25.0)), 0);
+
+        let ring = Path::sector(center, 20.0, 50.0, 0.0, 2.0 * std::f32::consts::PI);
+        assert_ne!(ring.winding(Point::new(0.0, -35.0)), 0);
+        assert_ne!(ring.winding(Point::new(-35.0, 0.0)), 0);
+        assert_eq!(ring.winding(Point::new(5.0, 5.0)), 0);
+
+        let wedge = Path::sector(center, 0.0, 50.0, 0.0, std::f32::consts::FRAC_PI_2);
+        assert_ne!(wedge.winding(Point::new(5.0, 5.0)), 0);
+    }
+
+    #[test]
+    fn point_at_length_follows_arc() {
+        let center = Point::new(0.0, 0.0);
+        // Half circle from the left, clockwise over the top.
+        let path = Path::arc(center, 100.0, std::f32::consts::PI, std::f32::consts::PI);
+        let (top, angle) = path.point_at_length(path.length() / 2.0).unwrap();
+        assert!(top.distance(Point::new(0.0, -100.0)) < 0.2, "{:?}", top);
+        // Heading right at the top.
+        assert!(angle.abs() < 0.1, "{angle}");
+    }
+
+    #[test]
+    fn mask_shapes_fill_their_rect() {
+        let rect = Rect {
+            x: 0.0,
+            y: 0.0,
+            w: 100.0,
+            h: 100.0,
+        };
+        for mask in MaskShape::ALL {
+            let shape = mask.shape(rect);
+            assert!(shape.contains(Point::new(50.0, 50.0)), "{mask:?}");
+            // Every outline cuts the bottom-left corner.
+            assert!(!shape.contains(Point::new(1.0, 99.0)), "{mask:?}");
+            let b = shape.bounds();
+            assert!(b.x >= -0.01 && b.y >= -0.01, "{mask:?}: {b:?}");
+            assert!(
+                b.x + b.w <= 100.01 && b.y + b.h <= 100.01,
+                "{mask:?}: {b:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn adaptive_icon_keeps_the_padded_square_near_the_safe_zone() {
+        let rect = Rect {
+            x: 0.0,
+            y: 0.0,
+            w: 108.0,
+            h: 108.0,
+        };
+        let mask = MaskShape::AdaptiveIcon;
+        assert!((mask.shape(rect).bounds().w - 66.0).abs() < 1e-3);
+        let inset = mask.min_padding_frac() * 108.0;
+        assert!((inset - 21.0).abs() < 1e-3, "{inset}");
+        assert_eq!(MaskShape::Squircle.min_padding_frac(), 0.0);
+    }
+
+    #[test]
+    fn squircle_sits_between_ellipse_and_square() {
+        let rect = Rect {
+            x: 0.0,
+            y: 0.0,
+            w: 100.0,
+            h: 100.0,
+        };
+        // On the diagonal an ellipse reaches 50 * (1 - 1/sqrt 2) ~ 14.6 from
+        // the corner, the squircle 50 * (1 - 2^(-1/5)) ~ 6.5.
+        let squircle = Shape::squircle(rect);
+        assert!(squircle.contains(Point::new(10.0, 10.0)));
+        assert!(!squircle.contains(Point::new(5.0, 5.0)));
+        let rounder = Shape::superellipse(rect, PIET_HEIN_EXPONENT);
+        assert!(!rounder.contains(Point::new(10.0, 10.0)));
+        // The sides stay flat along most of their length.
+        assert!(squircle.contains(Point::new(1.0, 30.0)));
+    }
+
+    #[test]
+    fn hexagon_is_regular() {
+        let rect = Rect {
+            x: 0.0,
+            y: 0.0,
+            w: 200.0,
+            h: 100.0,
+        };
+        let Shape::Path(path) = Shape::hexagon(rect) else {
+            panic!("expected a path");
+        };
+        let (poly, _) = &path.flatten()[0];
+        for i in 0..6 {
+            let side = poly[i].distance(poly[(i + 1) % 6]);
+            assert!((side - 50.0).abs() < 1e-3, "{side}");
+        }
+    }
+
+    #[test]
+    fn parses_mask_shapes() {
+        assert_eq!("hex".parse::<MaskShape>().unwrap(), MaskShape::Hexagon);
+        assert_eq!(
+            " Squircle ".parse::<MaskShape>().unwrap(),
+            MaskShape::Squircle
+        );
+        assert_eq!(
+            "Adaptive".parse::<MaskShape>().unwrap(),
+            MaskShape::AdaptiveIcon
+        );
+        assert!("circle".parse::<MaskShape>().is_err());
+        for mask in MaskShape::ALL {
+            assert_eq!(mask.id().parse::<MaskShape>().unwrap(), mask);
+        }
+    }
+}
@@ -0,0 +1,434 @@
+//! Glyph outlines for presets that build geometry out of letters.
+//!
+//! Presets build letters from the font compiled into the crate, see
+//! [`geometry_font`], so their output depends on the input and options
+//! alone. Without the `embed-font` feature, or for characters the font
+//! lacks, they fall back to a built-in 5×7 block font.
+
+use std::sync::OnceLock;
+
+use ab_glyph::{Font, FontRef, Outline, OutlineCurve, PxScale, ScaleFont};
+
+use super::geometry::{Path, Point, Rect};
+use super::typography;
+
+/// Location of the runtime font, relative to the crate root.
+const RUNTIME_FONT_PATH: &str = "assets/fonts/LiberationSans-Bold.ttf";
+
+/// Font compiled in with the `embed-font` Cargo feature (DejaVu Sans Bold,
+/// committed to `assets/fonts/`).
+#[cfg(feature = "embed-font")]
+const EMBED_FONT_BYTES: Option<&[u8]> = Some(include_bytes!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/assets/fonts/DejaVuSans-Bold.ttf"
+)));
+#[cfg(not(feature = "embed-font"))]
+const EMBED_FONT_BYTES: Option<&[u8]> = None;
+
+/// Block font rows per glyph; the cap height is split into this many cells.
+const BLOCK_ROWS: usize = 7;
+/// Block font columns per glyph.
+const BLOCK_COLS: usize = 5;
+/// Block font advance in cells, leaving one empty column between letters.
+const BLOCK_ADVANCE: f32 = (BLOCK_COLS + 1) as f32;
+
+/// The runtime font from `assets/fonts/`, falling back to the embedded
+/// bytes. Loaded once per process.
+pub fn runtime_font() -> Option<&'static FontRef<'static>> {
+    static FONT: OnceLock<Option<FontRef<'static>>> = OnceLock::new();
+    FONT.get_or_init(|| {
+        let runtime_path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join(RUNTIME_FONT_PATH);
+        std::fs::read(runtime_path)
+            .ok()
+            .and_then(|bytes| {
+                // Leak the bytes so we can obtain a &'static slice for FontRef.
+                let leaked: &'static [u8] = Box::leak(bytes.into_boxed_slice());
+                FontRef::try_from_slice(leaked).ok()
+            })
+            .or_else(embedded_font)
+    })
+    .as_ref()
+}
+
+/// The font compiled into the crate, if the `embed-font` feature is on.
+fn embedded_font() -> Option<FontRef<'static>> {
+    EMBED_FONT_BYTES.and_then(|b| FontRef::try_from_slice(b).ok())
+}
+
+/// Font presets build letter geometry from: the embedded font, or the
+/// built-in block font without it. Never the runtime font, whose presence
+/// depends on the machine, so the same input always draws the same mark.
+pub fn geometry_font() -> Option<&'static FontRef<'static>> {
+    static FONT: OnceLock<Option<FontRef<'static>>> = OnceLock::new();
+    FONT.get_or_init(embedded_font).as_ref()
+}
+
+/// A glyph outline positioned with its baseline start at the origin, y
+/// pointing down.
+#[derive(Debug, Clone)]
+pub struct Glyph {
+    pub outline: Path,
+    pub advance: f32,
+}
+
+/// Outline of `ch` at `font_size`, from `font` when it has the glyph,
+/// otherwise from the built-in block font.
+pub fn glyph(font: Option<&FontRef>, ch: char, font_size: f32) -> Glyph {
+    if let Some(font) = font {
+        let id = font.glyph_id(ch);
+        if let Some(outline) = font.outline(id) {
+            let scaled = font.as_scaled(PxScale::from(font_size));
+            let (sx, sy) = (scaled.h_scale_factor(), scaled.v_scale_factor());
+            let mut path = Path::new();
+            append_outline(&mut path, &outline, |p| Point::new(p.x * sx, -p.y * sy));
+            return Glyph {
+                outline: path,
+                advance: scaled.h_advance(id),
+            };
+        }
+    }
+    block_glyph(ch, font_size)
+}
+
+/// Outlines of `text`'s glyphs set side by side on a baseline at y = 0,
+/// starting at x = 0, with `tracking` pixels between letters.
+pub fn lay_out(font: Option<&FontRef>, text: &str, font_size: f32, tracking: f32) -> Vec<Path> {
+    let mut caret = 0.0;
+    text.chars()
+        .map(|ch| {
+            let glyph = glyph(font, ch, font_size);
+            let x = caret;
+            caret += glyph.advance + tracking;
+            glyph.outline.map(|p| Point::new(p.x + x, p.y))
+        })
+        .collect()
+}
+
+/// Outline of `ch` with its ink box centered on the origin, set so that
+/// capitals are `cap_height` tall. Unlike `lay_out`, letters set this way
+/// can each be placed, scaled and colored on their own.
+pub fn set_letter(font: Option<&FontRef>, ch: char, cap_height: f32) -> Path {
+    let outline = glyph(font, ch, cap_height / typography::CAP_HEIGHT).outline;
+    let b = outline.bounds();
+    let (cx, cy) = (b.x + b.w / 2.0, b.y + b.h / 2.0);
+    outline.map(|p| Point::new(p.x - cx, p.y - cy))
+}
+
+/// `letters` scaled uniformly to just fit `target` and centered in it,
+/// measured on their flattened outlines. Empty outlines come back unchanged.
+pub fn fit_in(letters: &[Path], target: Rect) -> Vec<Path> {
+    let mut min = Point::new(f32::MAX, f32::MAX);
+    let mut max = Point::new(f32::MIN, f32::MIN);
+    for (poly, _) in letters.iter().flat_map(|glyph| glyph.flatten()) {
+        for p in poly {
+            min = Point::new(min.x.min(p.x), min.y.min(p.y));
+            max = Point::new(max.x.max(p.x), max.y.max(p.y));
+        }
+    }
+    if min.x >= max.x || min.y >= max.y {
+        return letters.to_vec();
+    }
+    let scale = (target.w / (max.x - min.x)).min(target.h / (max.y - min.y));
+    let mid = Point::new((min.x + max.x) / 2.0, (min.y + max.y) / 2.0);
+    let center = Point::new(target.x + target.w / 2.0, target.y + target.h / 2.0);
+    letters
+        .iter()
+        .map(|glyph| {
+            glyph.map(|p| {
+                Point::new(
+                    center.x + (p.x - mid.x) * scale,
+                    center.y + (p.y - mid.y) * scale,
+                )
+            })
+        })
+        .collect()
+}
+
+/// Append the curves of a font `outline` to `path`, mapping font units to
+/// canvas coordinates with `place`.
+pub fn append_outline(
+    path: &mut Path,
+    outline: &Outline,
+    place: impl Fn(ab_glyph::Point) -> Point,
+) {
+    let mut last: Option<ab_glyph::Point> = None;
+    for curve in &outline.curves {
+        let (start, end) = match *curve {
+            OutlineCurve::Line(a, b) => (a, b),
+            OutlineCurve::Quad(a, _, b) => (a, b),
+            OutlineCurve::Cubic(a, _, _, b) => (a, b),
+        };
+        if last != Some(start) {
+            if last.is_some() {
+                path.close();
+            }
+            path.move_to(place(start));
+        }
+        match *curve {
+            OutlineCurve::Line(_, b) => path.line_to(place(b)),
+            OutlineCurve::Quad(_, c, b) => path.quad_to(place(c), place(b)),
+            OutlineCurve::Cubic(_, c1, c2, b) => path.cubic_to(place(c1), place(c2), place(b)),
+        }
+        last = Some(end);
+    }
+    if last.is_some() {
+        path.close();
+    }
+}
+
+/// Block font outline: one square subpath per set cell, all wound the same
+/// way so they fill as one shape.
+fn block_glyph(ch: char, font_size: f32) -> Glyph {
+    let cell = typography::CAP_HEIGHT * font_size / BLOCK_ROWS as f32;
+    let mut path = Path::new();
+    for (row, line) in block_rows(ch.to_ascii_uppercase()).iter().enumerate() {
+        for (col, _) in line.bytes().enumerate().filter(|(_, b)| *b == b'#') {
+            let x = col as f32 * cell;
+            let y = (row as f32 - BLOCK_ROWS as f32) * cell;
+            path.move_to(Point::new(x, y));
+            path.line_to(Point::new(x + cell, y));
+            path.line_to(Point::new(x + cell, y + cell));
+            path.line_to(Point::new(x, y + cell));
+            path.close();
+        }
+    }
+    Glyph {
+        outline: path,
+        advance: BLOCK_ADVANCE * cell,
+    }
+}
+
+/// Rows of the block font glyph for `ch`; characters outside A-Z and 0-9
+/// get a question mark.
+fn block_rows(ch: char) -> [&'static str; BLOCK_ROWS] {
+    match ch {
+        'A' => [
+            " ### ", "#   #", "#   #", "#####", "#   #", "#   #", "#   #",
+        ],
+        'B' => [
+            "#### ", "#   #", "#   #", "#### ", "#   #", "#   #", "#### ",
+        ],
+        'C' => [
+            " ####", "#    ", "#    ", "#    ", "#    ", "#    ", " ####",
+        ],
+        'D' => [
+            "#### ", "#   #", "#   #", "#   #", "#   #", "#   #", "#### ",
+        ],
+        'E' => [
+            "#####", "#    ", "#    ", "#### ", "#    ", "#    ", "#####",
+        ],
+        'F' => [
+            "#####", "#    ", "#    ", "#### ", "#    ", "#    ", "#    ",
+        ],
+        'G' => [
+            " ####", "#    ", "#    ", "#  ##", "#   #", "#   #", " ####",
+        ],
+        'H' => [
+            "#   #", "#   #", "#   #", "#####", "#   #", "#   #", "#   #",
+        ],
+        'I' => [
+            "#####", "  #  ", "  #  ", "  #  ", "  #  ", "  #  ", "#####",
+        ],
+        'J' => [
+            "  ###", "   # ", "   # ", "   # ", "   # ", "#  # ", " ##  ",
+        ],
+        'K' => [
+            "#   #", "#  # ", "# #  ", "##   ", "# #  ", "#  # ", "#   #",
+        ],
+        'L' => [
+            "#    ", "#    ", "#    ", "#    ", "#    ", "#    ", "#####",
+        ],
+        'M' => [
+            "#   #", "## ##", "# # #", "# # #", "#   #", "#   #", "#   #",
+        ],
+        'N' => [
+            "#   #", "##  #", "# # #", "#  ##", "#   #", "#   #", "#   #",
+        ],
+        'O' => [
+            " ### ", "#   #", "#   #", "#   #", "#   #", "#   #", " ### ",
+        ],
+        'P' => [
+            "#### ", "#   #", "#   #", "#### ", "#    ", "#    ", "#    ",
+        ],
+        'Q' => [
+            " ### ", "#   #", "#   #", "#   #", "# # #", "#  # ", " ## #",
+        ],
+        'R' => [
+            "#### ", "#   #", "#   #", "#### ", "# #  ", "#  # ", "#   #",
+        ],
+        'S' => [
+            " ####", "#    ", "#    ", " ### ", "    #", "    #", "#### ",
+        ],
+        'T' => [
+            "#####", "  #  ", "  #  ", "  #  ", "  #  ", "  #  ", "  #  ",
+        ],
+        'U' => [
+            "#   #", "#   #", "#   #", "#   #", "#   #", "#   #", " ### ",
+        ],
+        'V' => [
+            "#   #", "#   #", "#   #", "#   #", "#   #", " # # ", "  #  ",
+        ],
+        'W' => [
+            "#   #", "#   #", "#   #", "# # #", "# # #", "## ##", "#   #",
+        ],
+        'X' => [
+            "#   #", "#   #", " # # ", "  #  ", " # # ", "#   #", "#   #",
+        ],
+        'Y' => [
+            "#   #", "#   #", " # # ", "  #  ", "  #  ", "  #  ", "  #  ",
+        ],
+        'Z' => [
+            "#####", "    #", "   # ", "  #  ", " #   ", "#    ", "#####",
+        ],
+        '0' => [
+            " ### ", "#   #", "#  ##", "# # #", "##  #", "#   #", " ### ",
+        ],
+        '1' => [
+            "  #  ", " ##  ", "  #  ", "  #  ", "  #  ", "  #  ", " ### ",
+        ],
+        '2' => [
+            " ### ", "#   #", "    #", "   # ", "  #  ", " #   ", "#####",
+        ],
+        '3' => [
+            "#####", "   # ", "  #  ", "   # ", "    #", "#   #", " ### ",
+        ],
+        '4' => [
+            "   # ", "  ## ", " # # ", "#  # ", "#####", "   # ", "   # ",
+        ],
+        '5' => [
+            "#####", "#    ", "#### ", "    #", "    #", "#   #", " ### ",
+        ],
+        '6' => [
+            "  ## ", " #   ", "#    ", "#### ", "#   #", "#   #", " ### ",
+        ],
+        '7' => [
+            "#####", "    #", "   # ", "  #  ", " #   ", " #   ", " #   ",
+        ],
+        '8' => [
+            " ### ", "#   #", "#   #", " ### ", "#   #", "#   #", " ### ",
+        ],
+        '9' => [
+            " ### ", "#   #", "#   #", " ####", "    #", "   # ", " ##  ",
+        ],
+        _ => [
+            " ### ", "#   #", "    #", "   # ", "  #  ", "     ", "  #  ",
+        ],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn block_glyphs_sit_on_the_baseline() {
+        for ch in ('A'..='Z').chain('0'..='9') {
+            let g = block_glyph(ch, 100.0);
+            let b = g.outline.bounds();
+            assert!((b.y + b.h).abs() < 1e-3, "{ch}: {b:?}");
+            assert!(
+                (b.h - typography::CAP_HEIGHT * 100.0).abs() < 1e-3,
+                "{ch}: {b:?}"
+            );
+            assert!(b.x + b.w <= g.advance, "{ch}");
+        }
+    }
+
+    #[test]
+    fn block_rows_are_five_columns_wide() {
+        for ch in ('A'..='Z').chain('0'..='9').chain(['?']) {
+            assert!(block_rows(ch).iter().all(|row| row.len() == BLOCK_COLS));
+        }
+    }
+
+    #[test]
+    fn block_glyph_cells_fill_as_one_shape() {
+        let g = block_glyph('H', 100.0);
+        let cell = typography::CAP_HEIGHT * 100.0 / BLOCK_ROWS as f32;
+        // The crossbar (row 3 of 7, counted from the top) is inside, the gap
+        // above it is not.
+        let crossbar = Point::new(2.5 * cell, -3.5 * cell);
+        let gap = Point::new(2.5 * cell, -5.5 * cell);
+        assert_eq!(g.outline.winding(crossbar), 1);
+        assert_eq!(g.outline.winding(gap), 0);
+    }
+
+    #[cfg(feature = "embed-font")]
+    #[test]
+    fn geometry_comes_from_the_embedded_font() {
+        use crate::core::geometry::PathCmd;
+
+        let font = geometry_font().expect("embedded font");
+        let g = glyph(Some(font), 'R', 100.0);
+        let block = block_glyph('R', 100.0);
+        // The bowl of a real R has curves; block glyphs are all lines.
+        assert!(g
+            .outline
+            .cmds
+            .iter()
+            .any(|cmd| matches!(cmd, PathCmd::QuadTo { .. } | PathCmd::CubicTo { .. })));
+        assert_ne!(g.advance, block.advance);
+    }
+
+    #[cfg(not(feature = "embed-font"))]
+    #[test]
+    fn geometry_comes_from_the_block_font() {
+        assert!(geometry_font().is_none());
+        let g = glyph(geometry_font(), 'R', 100.0);
+        let block = block_glyph('R', 100.0);
+        assert_eq!(g.outline.cmds.len(), block.outline.cmds.len());
+        assert_eq!(g.advance, block.advance);
+    }
+
+    #[test]
+    fn lay_out_advances_each_glyph() {
+        let letters = lay_out(None, "II", 100.0, 5.0);
+        assert_eq!(letters.len(), 2);
+        let first = letters[0].bounds();
+        let second = letters[1].bounds();
+        let advance = block_glyph('I', 100.0).advance;
+        assert!((second.x - first.x - advance - 5.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn lowercase_and_unknown_characters_fall_back() {
+        let upper = block_glyph('K', 10.0).outline.cmds.len();
+        assert_eq!(block_glyph('k', 10.0).outline.cmds.len(), upper);
+        assert_eq!(
+            block_glyph('é', 10.0).outline.cmds.len(),
+            block_glyph('?', 10.0).outline.cmds.len()
+        );
+    }
+
+    #[test]
+    fn set_letter_centers_the_ink_at_cap_height() {
+        for ch in ['A', 'W', 'I'] {
+            let b = set_letter(None, ch, 60.0).bounds();
+            assert!((b.h - 60.0).abs() < 1e-3, "{ch}: {b:?}");
+            assert!((b.x + b.w / 2.0).abs() < 1e-3 && (b.y + b.h / 2.0).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn fit_in_fills_the_target_and_centers() {
+        let letters = lay_out(None, "HI", 100.0, 10.0);
+        let target = Rect {
+            x: 100.0,
+            y: 50.0,
+            w: 200.0,
+            h: 400.0,
+        };
+        let fitted = fit_in(&letters, target);
+        let (mut min, mut max) = (f32::MAX, f32::MIN);
+        let (mut top, mut bottom) = (f32::MAX, f32::MIN);
+        for (poly, _) in fitted.iter().flat_map(|glyph| glyph.flatten()) {
+            for p in poly {
+                (min, max) = (min.min(p.x), max.max(p.x));
+                (top, bottom) = (top.min(p.y), bottom.max(p.y));
+            }
+        }
+        // Wider than tall, so the width is the limit.
+        assert!((min - 100.0).abs() < 1e-3 && (max - 300.0).abs() < 1e-3);
+        assert!(((top + bottom) / 2.0 - 250.0).abs() < 1e-3);
+    }
+}
@@ -0,0 +1,237 @@
+//! L-systems and turtle graphics.
+//!
+//! An [`LSystem`] rewrites every symbol of a string in parallel; a
+//! [`Turtle`] then reads the result as drawing commands, producing
+//! polylines and leaf positions in turtle units (one unit per step).
+
+use super::geometry::{Path, Point};
+
+/// Expansion stops before the string would grow past this many symbols,
+/// so deep grammars cannot blow up.
+const MAX_SYMBOLS: usize = 50_000;
+
+/// A parallel rewriting system. Symbols without a rule are kept as they are.
+#[derive(Debug, Clone)]
+pub struct LSystem {
+    pub axiom: String,
+    pub rules: Vec<(char, String)>,
+}
+
+impl LSystem {
+    pub fn new(axiom: &str, rules: &[(char, &str)]) -> Self {
+        LSystem {
+            axiom: axiom.to_string(),
+            rules: rules.iter().map(|&(c, r)| (c, r.to_string())).collect(),
+        }
+    }
+
+    /// The axiom rewritten `iterations` times, or fewer if another pass
+    /// would exceed `MAX_SYMBOLS`.
+    pub fn expand(&self, iterations: usize) -> String {
+        let mut current = self.axiom.clone();
+        for _ in 0..iterations {
+            let mut next = String::with_capacity(current.len() * 2);
+            for ch in current.chars() {
+                match self.rules.iter().find(|(c, _)| *c == ch) {
+                    Some((_, replacement)) => next.push_str(replacement),
+                    None => next.push(ch),
+                }
+                if next.len() > MAX_SYMBOLS {
+                    return current;
+                }
+            }
+            current = next;
+        }
+        current
+    }
+}
+
+/// Turtle reading these symbols, ignoring any others:
+///
+/// - `F`: draw one step forward
+/// - `f`: move one step forward without drawing
+/// - `+` / `-`: turn left / right by `angle`
+/// - `(` / `)`: turn left / right by `bend`
+/// - `>`: scale later steps by `shrink`
+/// - `[` / `]`: save / restore position, heading and step
+/// - `L`: place a leaf
+///
+/// The turtle starts at the origin heading up the canvas (towards -y).
+#[derive(Debug, Clone, Copy)]
+pub struct Turtle {
+    /// Main turning angle in radians.
+    pub angle: f32,
+    /// Small turning angle in radians, for gently curving stems.
+    pub bend: f32,
+    /// Step scale applied by `>`.
+    pub shrink: f32,
+}
+
+/// Polyline drawn between two bracket events, at its bracket depth.
+#[derive(Debug, Clone)]
+pub struct Branch {
+    pub points: Vec<Point>,
+    pub depth: usize,
+}
+
+impl Branch {
+    pub fn to_path(&self) -> Path {
+        let mut path = Path::new();
+        for (i, &p) in self.points.iter().enumerate() {
+            if i == 0 {
+                path.move_to(p);
+            } else {
+                path.line_to(p);
+            }
+        }
+        path
+    }
+}
+
+/// Where the turtle placed a leaf.
+#[derive(Debug, Clone, Copy)]
+pub struct Leaf {
+    pub at: Point,
+    /// Heading in radians, with 0 pointing towards +x.
+    pub heading: f32,
+    /// Step length at the leaf, after any `>` scaling.
+    pub step: f32,
+    pub depth: usize,
+}
+
+/// Everything a turtle drew.
+#[derive(Debug, Clone, Default)]
+pub struct Drawing {
+    pub branches: Vec<Branch>,
+    pub leaves: Vec<Leaf>,
+}
+
+#[derive(Clone, Copy)]
+struct State {
+    at: Point,
+    heading: f32,
+    step: f32,
+}
+
+impl Turtle {
+    pub fn draw(&self, program: &str) -> Drawing {
+        let mut drawing = Drawing::default();
+        let mut state = State {
+            at: Point::new(0.0, 0.0),
+            heading: -std::f32::consts::FRAC_PI_2,
+            step: 1.0,
+        };
+        let mut stack: Vec<State> = Vec::new();
+        // Points of the branch being drawn, and whether the turtle turned
+        // since its last point (straight runs merge into one segment).
+        let mut current = vec![state.at];
+        let mut turned = true;
+
+        let finish = |drawing: &mut Drawing, points: &mut Vec<Point>, depth: usize| {
+            if points.len() > 1 {
+                drawing.branches.push(Branch {
+                    points: std::mem::take(points),
+                    depth,
+                });
+            }
+            points.clear();
+        };
+
+        for ch in program.chars() {
+            match ch {
+                'F' | 'f' => {
+                    let (sin, cos) = state.heading.sin_cos();
+                    state.at =
+                        Point::new(state.at.x + cos * state.step, state.at.y + sin * state.step);
+                    if ch == 'f' {
+                        finish(&mut drawing, &mut current, stack.len());
+                        current.push(state.at);
+                        turned = true;
+                    } else if turned || current.len() < 2 {
+                        current.push(state.at);
+                        turned = false;
+                    } else if let Some(last) = current.last_mut() {
+                        *last = state.at;
+                    }
+                }
+                '+' => (state.heading, turned) = (state.heading - self.angle, true),
+                '-' => (state.heading, turned) = (state.heading + self.angle, true),
+                '(' => (state.heading, turned) = (state.heading - self.bend, true),
+                ')' => (state.heading, turned) = (state.heading + self.bend, true),
+                '>' => state.step *= self.shrink,
+                '[' => {
+                    finish(&mut drawing, &mut current, stack.len());
+                    stack.push(state);
+                    current.push(state.at);
+                    turned = true;
+                }
+                ']' => {
+                    finish(&mut drawing, &mut current, stack.len());
+                    if let Some(saved) = stack.pop() {
+                        state = saved;
+                    }
+                    current.push(state.at);
+                    turned = true;
+                }
+                'L' => drawing.leaves.push(Leaf {
+                    at: state.at,
+                    heading: state.heading,
+                    step: state.step,
+                    depth: stack.len(),
+                }),
+                _ => {}
+            }
+        }
+        finish(&mut drawing, &mut current, stack.len());
+        drawing
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expands_in_parallel() {
+        let algae = LSystem::new("A", &[('A', "AB"), ('B', "A")]);
+        assert_eq!(algae.expand(0), "A");
+        assert_eq!(algae.expand(4), "ABAABABA");
+        // Symbols without rules survive.
+        let plant = LSystem::new("X", &[('X', "F[+X]")]);
+        assert_eq!(plant.expand(2), "F[+F[+X]]");
+    }
+
+    #[test]
+    fn expansion_is_capped() {
+        let doubling = LSystem::new("F", &[('F', "FF")]);
+        let s = doubling.expand(40);
+        assert!(s.len() <= MAX_SYMBOLS && s.len() > MAX_SYMBOLS / 2);
+    }
+
+    #[test]
+    fn turtle_merges_straight_runs_and_branches_at_brackets() {
+        let turtle = Turtle {
+            angle: std::f32::consts::FRAC_PI_2,
+            bend: 0.0,
+            shrink: 0.5,
+        };
+        let drawing = turtle.draw("FF[+F>F-FL]F");
+        let ends: Vec<(usize, usize)> = drawing
+            .branches
+            .iter()
+            .map(|b| (b.points.len(), b.depth))
+            .collect();
+        assert_eq!(ends, vec![(2, 0), (3, 1), (2, 0)]);
+
+        let trunk = &drawing.branches[0];
+        assert!((trunk.points[1].y + 2.0).abs() < 1e-5);
+        // The side branch turns left for one and a half steps, then right.
+        let side = &drawing.branches[1].points;
+        assert!((side[1].x + 1.5).abs() < 1e-5 && (side[2].y + 2.5).abs() < 1e-5);
+
+        let leaf = drawing.leaves[0];
+        assert_eq!(leaf.depth, 1);
+        assert!((leaf.step - 0.5).abs() < 1e-6);
+        assert!((drawing.branches[2].points[1].y + 3.0).abs() < 1e-5);
+    }
+}
@@ -2,11 +2,20 @@
 //!
 //! This module provides:
 //! - Seed derivation from input strings
-//! - Color palette generation
-//! - Typography utilities
-//! - Geometric primitives
+//! - Color palette generation and export
+//! - Typography utilities and glyph outlines
+//! - Geometric primitives, boolean path operations and Delaunay
+//!   triangulation
+//! - Seeded value noise
+//! - L-systems and turtle graphics
 
+pub mod boolean;
 pub mod geometry;
+pub mod glyphs;
+pub mod lsystem;
+pub mod noise;
 pub mod palette;
+pub mod palette_export;
 pub mod seed;
+pub mod triangulation;
 pub mod typography;
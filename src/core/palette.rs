@@ -1,4 +1,5 @@
-use rand::Rng;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha20Rng;
 
 // Palette generation constraints for logo-appropriate colors.
 const MIN_SATURATION: f32 = 0.55;
@@ -17,8 +18,15 @@ const MAX_BACKGROUND_SATURATION: f32 = 0.25;
 const MIN_BACKGROUND_LIGHTNESS: f32 = 0.92;
 const MAX_BACKGROUND_LIGHTNESS: f32 = 0.98;
 
+/// Minimum CIE76 ΔE the palette roles must keep under every simulated
+/// color-vision deficiency for a palette to count as CVD-safe.
+pub const MIN_CVD_DELTA_E: f32 = 12.0;
+
+/// Upper bound on palette re-samples before falling back to a fixed palette.
+const MAX_CVD_RESAMPLES: usize = 64;
+
 /// Simple RGB color.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Rgb {
     pub r: u8,
     pub g: u8,
@@ -29,6 +37,38 @@ impl Rgb {
     pub fn to_hex(&self) -> String {
         format!("#{:02X}{:02X}{:02X}", self.r, self.g, self.b)
     }
+
+    /// Parse `#RRGGBB` (the leading `#` is optional).
+    pub fn from_hex(s: &str) -> Option<Rgb> {
+        let hex = s.trim().trim_start_matches('#');
+        if hex.len() != 6 || !hex.is_ascii() {
+            return None;
+        }
+        let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+        Some(Rgb {
+            r: channel(0)?,
+            g: channel(2)?,
+            b: channel(4)?,
+        })
+    }
+
+    /// Linear interpolation towards `other` in sRGB, `t` in [0..1].
+    pub fn lerp(self, other: Rgb, t: f32) -> Rgb {
+        let t = t.clamp(0.0, 1.0);
+        let mix = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t).round() as u8;
+        Rgb {
+            r: mix(self.r, other.r),
+            g: mix(self.g, other.g),
+            b: mix(self.b, other.b),
+        }
+    }
+
+    /// The same hue and saturation with HSL lightness shifted by `delta`,
+    /// clamped to [0..1].
+    pub fn with_lightness_offset(self, delta: f32) -> Rgb {
+        let (h, s, l) = rgb_to_hsl(self);
+        hsl_to_rgb(h, s, l + delta)
+    }
 }
 
 /// Minimal HSL->RGB conversion. Good enough for stubs.
@@ -79,6 +119,34 @@ pub fn hsl_to_rgb(h: f32, s: f32, l: f32) -> Rgb {
     }
 }
 
+/// RGB->HSL conversion, the inverse of [`hsl_to_rgb`]. Hue is in degrees.
+pub fn rgb_to_hsl(c: Rgb) -> (f32, f32, f32) {
+    let r = c.r as f32 / 255.0;
+    let g = c.g as f32 / 255.0;
+    let b = c.b as f32 / 255.0;
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let l = (max + min) / 2.0;
+    let d = max - min;
+    if d == 0.0 {
+        return (0.0, 0.0, l);
+    }
+
+    let s = if l > 0.5 {
+        d / (2.0 - max - min)
+    } else {
+        d / (max + min)
+    };
+    let h = if max == r {
+        (g - b) / d + if g < b { 6.0 } else { 0.0 }
+    } else if max == g {
+        (b - r) / d + 2.0
+    } else {
+        (r - g) / d + 4.0
+    };
+    (h * 60.0, s, l)
+}
+
 #[derive(Debug, Clone)]
 pub struct Palette {
     pub background: Option<Rgb>,
@@ -143,3 +211,393 @@ pub fn derive_palette<R: Rng>(rng: &mut R, transparent_background: bool) -> Pale
         text_color,
     }
 }
+
+/// Color-vision deficiencies that palettes can be checked against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorVisionDeficiency {
+    /// Missing L cones (red-blind).
+    Protanopia,
+    /// Missing M cones (green-blind).
+    Deuteranopia,
+    /// Missing S cones (blue-blind).
+    Tritanopia,
+}
+
+impl ColorVisionDeficiency {
+    /// Returns all simulated deficiencies.
+    pub fn all() -> [ColorVisionDeficiency; 3] {
+        [
+            ColorVisionDeficiency::Protanopia,
+            ColorVisionDeficiency::Deuteranopia,
+            ColorVisionDeficiency::Tritanopia,
+        ]
+    }
+
+    /// Simulation matrix in linear RGB (Machado, Oliveira & Fernandes 2009,
+    /// severity 1.0).
+    fn matrix(&self) -> [[f32; 3]; 3] {
+        match self {
+            ColorVisionDeficiency::Protanopia => [
+                [0.152_286, 1.052_583, -0.204_868],
+                [0.114_503, 0.786_281, 0.099_216],
+                [-0.003_882, -0.048_116, 1.051_998],
+            ],
+            ColorVisionDeficiency::Deuteranopia => [
+                [0.367_322, 0.860_646, -0.227_968],
+                [0.280_085, 0.672_501, 0.047_413],
+                [-0.011_820, 0.042_940, 0.968_881],
+            ],
+            ColorVisionDeficiency::Tritanopia => [
+                [1.255_528, -0.076_749, -0.178_779],
+                [-0.078_411, 0.930_809, 0.147_602],
+                [0.004_733, 0.691_367, 0.303_900],
+            ],
+        }
+    }
+}
+
+fn srgb_to_linear(c: u8) -> f32 {
+    let c = c as f32 / 255.0;
+    if c <= 0.040_45 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(c: f32) -> u8 {
+    let c = c.clamp(0.0, 1.0);
+    let v = if c <= 0.003_130_8 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    };
+    (v * 255.0).round() as u8
+}
+
+/// Simulate how `color` appears to a viewer with the given deficiency.
+pub fn simulate_cvd(color: Rgb, cvd: ColorVisionDeficiency) -> Rgb {
+    let lin = [
+        srgb_to_linear(color.r),
+        srgb_to_linear(color.g),
+        srgb_to_linear(color.b),
+    ];
+    let m = cvd.matrix();
+    let mix = |row: [f32; 3]| row[0] * lin[0] + row[1] * lin[1] + row[2] * lin[2];
+    Rgb {
+        r: linear_to_srgb(mix(m[0])),
+        g: linear_to_srgb(mix(m[1])),
+        b: linear_to_srgb(mix(m[2])),
+    }
+}
+
+/// sRGB -> CIELAB (D65 white point).
+fn rgb_to_lab(color: Rgb) -> [f32; 3] {
+    let r = srgb_to_linear(color.r);
+    let g = srgb_to_linear(color.g);
+    let b = srgb_to_linear(color.b);
+
+    let x = (0.412_456_4 * r + 0.357_576_1 * g + 0.180_437_5 * b) / 0.950_47;
+    let y = 0.212_672_9 * r + 0.715_152_2 * g + 0.072_175 * b;
+    let z = (0.019_333_9 * r + 0.119_192 * g + 0.950_304_1 * b) / 1.088_83;
+
+    fn f(t: f32) -> f32 {
+        if t > 0.008_856 {
+            t.cbrt()
+        } else {
+            7.787 * t + 16.0 / 116.0
+        }
+    }
+
+    let (fx, fy, fz) = (f(x), f(y), f(z));
+    [116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz)]
+}
+
+/// Perceptual color difference (CIE76 ΔE*ab).
+pub fn delta_e(a: Rgb, b: Rgb) -> f32 {
+    let la = rgb_to_lab(a);
+    let lb = rgb_to_lab(b);
+    ((la[0] - lb[0]).powi(2) + (la[1] - lb[1]).powi(2) + (la[2] - lb[2]).powi(2)).sqrt()
+}
+
+impl Palette {
+    /// Color at `t` in [0..1] along the gradient primary -> secondary ->
+    /// tertiary.
+    pub fn gradient(&self, t: f32) -> Rgb {
+        let t = t.clamp(0.0, 1.0) * 2.0;
+        if t <= 1.0 {
+            self.primary.lerp(self.secondary, t)
+        } else {
+            self.secondary.lerp(self.tertiary, t - 1.0)
+        }
+    }
+
+    /// Smallest ΔE across all simulated color-vision deficiencies between
+    /// any two of the primary, secondary and tertiary roles, between the
+    /// text color and the primary it sits on, and between each role and the
+    /// background.
+    pub fn min_cvd_delta_e(&self) -> f32 {
+        let roles = [self.primary, self.secondary, self.tertiary];
+        let mut pairs: Vec<(Rgb, Rgb)> = Vec::new();
+        for i in 0..roles.len() {
+            for j in (i + 1)..roles.len() {
+                pairs.push((roles[i], roles[j]));
+            }
+        }
+        pairs.push((self.text_color, self.primary));
+        if let Some(background) = self.background {
+            for role in roles {
+                pairs.push((role, background));
+            }
+        }
+
+        let mut min = f32::MAX;
+        for cvd in ColorVisionDeficiency::all() {
+            for &(a, b) in &pairs {
+                min = min.min(delta_e(simulate_cvd(a, cvd), simulate_cvd(b, cvd)));
+            }
+        }
+        min
+    }
+
+    /// Whether the palette roles stay at least `min_delta_e` apart under
+    /// every simulated color-vision deficiency.
+    pub fn is_cvd_safe(&self, min_delta_e: f32) -> bool {
+        self.min_cvd_delta_e() >= min_delta_e
+    }
+}
+
+/// Okabe-Ito blue, orange and bluish green on an off-white background: a
+/// palette designed to stay distinguishable under every common deficiency.
+fn fallback_cvd_safe_palette(transparent_background: bool) -> Palette {
+    Palette {
+        background: (!transparent_background).then_some(Rgb {
+            r: 250,
+            g: 250,
+            b: 247,
+        }),
+        primary: Rgb {
+            r: 0,
+            g: 114,
+            b: 178,
+        },
+        secondary: Rgb {
+            r: 230,
+            g: 159,
+            b: 0,
+        },
+        tertiary: Rgb {
+            r: 0,
+            g: 158,
+            b: 115,
+        },
+        text_color: Rgb {
+            r: 255,
+            g: 255,
+            b: 255,
+        },
+    }
+}
+
+/// Like [`derive_palette`], but re-samples until the palette passes
+/// [`Palette::is_cvd_safe`] with [`MIN_CVD_DELTA_E`].
+///
+/// The first candidate takes exactly the draws [`derive_palette`] would, so
+/// everything drawn from `rng` afterwards is unchanged; re-samples come from
+/// a separate stream keyed on that candidate. If none of them passes within
+/// a bounded number of attempts, a fixed CVD-safe palette is used.
+pub fn derive_cvd_safe_palette<R: Rng>(rng: &mut R, transparent_background: bool) -> Palette {
+    let first = derive_palette(rng, transparent_background);
+    if first.is_cvd_safe(MIN_CVD_DELTA_E) {
+        return first;
+    }
+
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(b"cvd");
+    for c in [first.primary, first.secondary, first.tertiary] {
+        hasher.update(&[c.r, c.g, c.b]);
+    }
+    let mut retries = ChaCha20Rng::from_seed(*hasher.finalize().as_bytes());
+
+    for _ in 1..MAX_CVD_RESAMPLES {
+        let candidate = derive_palette(&mut retries, transparent_background);
+        if candidate.is_cvd_safe(MIN_CVD_DELTA_E) {
+            return candidate;
+        }
+    }
+    fallback_cvd_safe_palette(transparent_background)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn simulate_cvd_keeps_greys() {
+        let grey = Rgb {
+            r: 128,
+            g: 128,
+            b: 128,
+        };
+        for cvd in ColorVisionDeficiency::all() {
+            let sim = simulate_cvd(grey, cvd);
+            assert!(
+                delta_e(grey, sim) < 1.0,
+                "{:?} shifted grey to {:?}",
+                cvd,
+                sim
+            );
+        }
+    }
+
+    #[test]
+    fn red_and_green_collapse_under_deuteranopia() {
+        let red = Rgb {
+            r: 200,
+            g: 60,
+            b: 40,
+        };
+        let green = Rgb {
+            r: 90,
+            g: 140,
+            b: 40,
+        };
+        let normal = delta_e(red, green);
+        let sim = delta_e(
+            simulate_cvd(red, ColorVisionDeficiency::Deuteranopia),
+            simulate_cvd(green, ColorVisionDeficiency::Deuteranopia),
+        );
+        assert!(sim < normal / 2.0, "normal={normal} simulated={sim}");
+    }
+
+    #[test]
+    fn from_hex_round_trips() {
+        let c = Rgb {
+            r: 0x12,
+            g: 0xAB,
+            b: 0xEF,
+        };
+        assert_eq!(Rgb::from_hex(&c.to_hex()), Some(c));
+        assert_eq!(Rgb::from_hex("12abef"), Some(c));
+        assert_eq!(Rgb::from_hex("#12abe"), None);
+        assert_eq!(Rgb::from_hex("#12abeg"), None);
+    }
+
+    #[test]
+    fn hsl_round_trips() {
+        for c in [
+            Rgb {
+                r: 200,
+                g: 40,
+                b: 90,
+            },
+            Rgb {
+                r: 12,
+                g: 180,
+                b: 75,
+            },
+            Rgb {
+                r: 30,
+                g: 60,
+                b: 240,
+            },
+            Rgb {
+                r: 90,
+                g: 90,
+                b: 90,
+            },
+        ] {
+            let (h, s, l) = rgb_to_hsl(c);
+            assert_eq!(hsl_to_rgb(h, s, l), c);
+        }
+    }
+
+    #[test]
+    fn lightness_offset_keeps_hue() {
+        let c = hsl_to_rgb(210.0, 0.6, 0.45);
+        let (h, _, l) = rgb_to_hsl(c.with_lightness_offset(0.2));
+        assert!((h - 210.0).abs() < 2.0, "{h}");
+        assert!((l - 0.65).abs() < 0.01, "{l}");
+        assert_eq!(
+            c.with_lightness_offset(2.0),
+            Rgb {
+                r: 255,
+                g: 255,
+                b: 255
+            }
+        );
+    }
+
+    #[test]
+    fn gradient_passes_through_every_role() {
+        let mut rng = ChaCha8Rng::seed_from_u64(5);
+        let palette = derive_palette(&mut rng, false);
+        assert_eq!(palette.gradient(0.0), palette.primary);
+        assert_eq!(palette.gradient(0.5), palette.secondary);
+        assert_eq!(palette.gradient(1.0), palette.tertiary);
+        assert_eq!(
+            palette.gradient(0.25),
+            palette.primary.lerp(palette.secondary, 0.5)
+        );
+    }
+
+    #[test]
+    fn delta_e_identical_is_zero() {
+        let c = Rgb {
+            r: 10,
+            g: 20,
+            b: 30,
+        };
+        assert_eq!(delta_e(c, c), 0.0);
+    }
+
+    #[test]
+    fn cvd_safe_palette_passes_check() {
+        for seed in 0..32 {
+            let mut rng = ChaCha8Rng::seed_from_u64(seed);
+            let palette = derive_cvd_safe_palette(&mut rng, false);
+            assert!(
+                palette.is_cvd_safe(MIN_CVD_DELTA_E),
+                "seed {seed}: min ΔE {}",
+                palette.min_cvd_delta_e()
+            );
+        }
+    }
+
+    #[test]
+    fn cvd_safe_palette_leaves_the_rest_of_the_stream_alone() {
+        for seed in 0..32 {
+            let mut plain = ChaCha8Rng::seed_from_u64(seed);
+            let mut safe = ChaCha8Rng::seed_from_u64(seed);
+            derive_palette(&mut plain, false);
+            derive_cvd_safe_palette(&mut safe, false);
+            assert_eq!(plain.gen::<u64>(), safe.gen::<u64>(), "seed {seed}");
+        }
+    }
+
+    #[test]
+    fn cvd_check_covers_text_and_background() {
+        let mut palette = fallback_cvd_safe_palette(false);
+        assert!(palette.is_cvd_safe(MIN_CVD_DELTA_E));
+        assert!(fallback_cvd_safe_palette(true).is_cvd_safe(MIN_CVD_DELTA_E));
+
+        palette.text_color = palette.primary;
+        assert_eq!(palette.min_cvd_delta_e(), 0.0);
+        let mut palette = fallback_cvd_safe_palette(false);
+        palette.background = Some(palette.tertiary);
+        assert_eq!(palette.min_cvd_delta_e(), 0.0);
+    }
+
+    #[test]
+    fn cvd_safe_palette_is_deterministic() {
+        let mut a = ChaCha8Rng::seed_from_u64(7);
+        let mut b = ChaCha8Rng::seed_from_u64(7);
+        let pa = derive_cvd_safe_palette(&mut a, true);
+        let pb = derive_cvd_safe_palette(&mut b, true);
+        assert_eq!(pa.primary, pb.primary);
+        assert_eq!(pa.secondary, pb.secondary);
+        assert_eq!(pa.tertiary, pb.tertiary);
+    }
+}
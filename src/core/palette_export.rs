@@ -0,0 +1,281 @@
+//! Export of generated palettes to design-tool formats.
+
+use super::palette::{Palette, Rgb};
+use crate::LoGenError;
+
+/// File formats a [`Palette`] can be exported to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaletteFormat {
+    /// CSS custom properties on `:root`.
+    Css,
+    /// SCSS variables.
+    Scss,
+    /// W3C Design Tokens (Community Group format) JSON.
+    Json,
+    /// GIMP palette (`.gpl`).
+    Gpl,
+    /// Adobe Swatch Exchange (`.ase`).
+    Ase,
+}
+
+impl PaletteFormat {
+    /// Conventional file extension (without the dot).
+    pub fn extension(&self) -> &'static str {
+        match self {
+            PaletteFormat::Css => "css",
+            PaletteFormat::Scss => "scss",
+            PaletteFormat::Json => "tokens.json",
+            PaletteFormat::Gpl => "gpl",
+            PaletteFormat::Ase => "ase",
+        }
+    }
+
+    /// Returns all supported export formats.
+    pub fn all() -> Vec<PaletteFormat> {
+        vec![
+            PaletteFormat::Css,
+            PaletteFormat::Scss,
+            PaletteFormat::Json,
+            PaletteFormat::Gpl,
+            PaletteFormat::Ase,
+        ]
+    }
+}
+
+impl std::str::FromStr for PaletteFormat {
+    type Err = LoGenError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "css" => Ok(PaletteFormat::Css),
+            "scss" | "sass" => Ok(PaletteFormat::Scss),
+            "json" | "tokens" | "design-tokens" => Ok(PaletteFormat::Json),
+            "gpl" | "gimp" => Ok(PaletteFormat::Gpl),
+            "ase" | "adobe" => Ok(PaletteFormat::Ase),
+            _ => Err(LoGenError::InvalidOptions(format!(
+                "unknown palette format: {s}"
+            ))),
+        }
+    }
+}
+
+/// Named color roles of a palette, in export order. The background is
+/// omitted when the palette was derived for a transparent canvas.
+pub fn palette_roles(palette: &Palette) -> Vec<(&'static str, Rgb)> {
+    let mut roles = vec![
+        ("primary", palette.primary),
+        ("secondary", palette.secondary),
+        ("tertiary", palette.tertiary),
+        ("text", palette.text_color),
+    ];
+    if let Some(bg) = palette.background {
+        roles.push(("background", bg));
+    }
+    roles
+}
+
+/// Lowercase ASCII identifier usable as a CSS/SCSS/token prefix.
+fn slug(name: &str) -> String {
+    let mut out = String::with_capacity(name.len());
+    for ch in name.chars() {
+        if ch.is_ascii_alphanumeric() {
+            out.push(ch.to_ascii_lowercase());
+        } else if !out.is_empty() && !out.ends_with('-') {
+            out.push('-');
+        }
+    }
+    let out = out.trim_end_matches('-').to_string();
+    if out.is_empty() || out.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("logen-{out}").trim_end_matches('-').to_string()
+    } else {
+        out
+    }
+}
+
+/// Serialize `palette` in the requested format. `name` labels the palette
+/// (used as variable prefix, token group, GIMP palette name or ASE group).
+pub fn export_palette(palette: &Palette, format: PaletteFormat, name: &str) -> Vec<u8> {
+    match format {
+        PaletteFormat::Css => export_css(palette, name).into_bytes(),
+        PaletteFormat::Scss => export_scss(palette, name).into_bytes(),
+        PaletteFormat::Json => export_json(palette, name).into_bytes(),
+        PaletteFormat::Gpl => export_gpl(palette, name).into_bytes(),
+        PaletteFormat::Ase => export_ase(palette, name),
+    }
+}
+
+fn export_css(palette: &Palette, name: &str) -> String {
+    let prefix = slug(name);
+    let mut out = String::from(":root {\n");
+    for (role, color) in palette_roles(palette) {
+        out.push_str(&format!("  --{prefix}-{role}: {};\n", color.to_hex()));
+    }
+    out.push_str("}\n");
+    out
+}
+
+fn export_scss(palette: &Palette, name: &str) -> String {
+    let prefix = slug(name);
+    let mut out = String::new();
+    for (role, color) in palette_roles(palette) {
+        out.push_str(&format!("${prefix}-{role}: {};\n", color.to_hex()));
+    }
+    out
+}
+
+fn export_json(palette: &Palette, name: &str) -> String {
+    let roles = palette_roles(palette);
+    let mut out = format!("{{\n  \"{}\": {{\n", slug(name));
+    for (i, (role, color)) in roles.iter().enumerate() {
+        let sep = if i + 1 == roles.len() { "" } else { "," };
+        out.push_str(&format!(
+            "    \"{role}\": {{ \"$type\": \"color\", \"$value\": \"{}\" }}{sep}\n",
+            color.to_hex()
+        ));
+    }
+    out.push_str("  }\n}\n");
+    out
+}
+
+fn export_gpl(palette: &Palette, name: &str) -> String {
+    let title: String = name.chars().filter(|c| !c.is_control()).collect();
+    let mut out = format!("GIMP Palette\nName: {title}\nColumns: 0\n#\n");
+    for (role, color) in palette_roles(palette) {
+        out.push_str(&format!(
+            "{:3} {:3} {:3}\t{role}\n",
+            color.r, color.g, color.b
+        ));
+    }
+    out
+}
+
+const ASE_GROUP_START: u16 = 0xC001;
+const ASE_GROUP_END: u16 = 0xC002;
+const ASE_COLOR_ENTRY: u16 = 0x0001;
+/// ASE color type "normal" (neither global nor spot).
+const ASE_COLOR_NORMAL: u16 = 2;
+
+/// ASE names are length-prefixed, NUL-terminated UTF-16BE strings.
+fn ase_name(name: &str) -> Vec<u8> {
+    let units: Vec<u16> = name.encode_utf16().chain(std::iter::once(0)).collect();
+    let mut out = Vec::with_capacity(2 + units.len() * 2);
+    out.extend_from_slice(&(units.len() as u16).to_be_bytes());
+    for u in units {
+        out.extend_from_slice(&u.to_be_bytes());
+    }
+    out
+}
+
+fn ase_block(out: &mut Vec<u8>, kind: u16, body: &[u8]) {
+    out.extend_from_slice(&kind.to_be_bytes());
+    out.extend_from_slice(&(body.len() as u32).to_be_bytes());
+    out.extend_from_slice(body);
+}
+
+fn export_ase(palette: &Palette, name: &str) -> Vec<u8> {
+    let roles = palette_roles(palette);
+
+    let mut out = Vec::new();
+    out.extend_from_slice(b"ASEF");
+    out.extend_from_slice(&1u16.to_be_bytes());
+    out.extend_from_slice(&0u16.to_be_bytes());
+    out.extend_from_slice(&((roles.len() + 2) as u32).to_be_bytes());
+
+    ase_block(&mut out, ASE_GROUP_START, &ase_name(name));
+    for (role, color) in roles {
+        let mut body = ase_name(role);
+        body.extend_from_slice(b"RGB ");
+        for channel in [color.r, color.g, color.b] {
+            body.extend_from_slice(&(channel as f32 / 255.0).to_be_bytes());
+        }
+        body.extend_from_slice(&ASE_COLOR_NORMAL.to_be_bytes());
+        ase_block(&mut out, ASE_COLOR_ENTRY, &body);
+    }
+    ase_block(&mut out, ASE_GROUP_END, &[]);
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Palette {
+        Palette {
+            background: Some(Rgb {
+                r: 250,
+                g: 240,
+                b: 230,
+            }),
+            primary: Rgb { r: 255, g: 0, b: 0 },
+            secondary: Rgb { r: 0, g: 128, b: 0 },
+            tertiary: Rgb { r: 0, g: 0, b: 255 },
+            text_color: Rgb {
+                r: 255,
+                g: 255,
+                b: 255,
+            },
+        }
+    }
+
+    #[test]
+    fn slug_handles_spaces_and_digits() {
+        assert_eq!(slug("Acme Power"), "acme-power");
+        assert_eq!(slug("  Café 42! "), "caf-42");
+        assert_eq!(slug("42"), "logen-42");
+        assert_eq!(slug("☕"), "logen");
+    }
+
+    #[test]
+    fn css_lists_every_role() {
+        let css = String::from_utf8(export_palette(&sample(), PaletteFormat::Css, "Acme")).unwrap();
+        assert!(css.starts_with(":root {"));
+        assert!(css.contains("--acme-primary: #FF0000;"));
+        assert!(css.contains("--acme-background: #FAF0E6;"));
+    }
+
+    #[test]
+    fn json_uses_design_token_fields() {
+        let json =
+            String::from_utf8(export_palette(&sample(), PaletteFormat::Json, "Acme")).unwrap();
+        assert!(json.contains(r##""secondary": { "$type": "color", "$value": "#008000" },"##));
+        assert!(json.contains(r##""background": { "$type": "color", "$value": "#FAF0E6" }"##));
+    }
+
+    #[test]
+    fn transparent_palette_omits_background() {
+        let mut p = sample();
+        p.background = None;
+        let scss = String::from_utf8(export_palette(&p, PaletteFormat::Scss, "Acme")).unwrap();
+        assert_eq!(scss.lines().count(), 4);
+        assert!(!scss.contains("background"));
+    }
+
+    #[test]
+    fn gpl_header_and_rows() {
+        let gpl = String::from_utf8(export_palette(&sample(), PaletteFormat::Gpl, "Acme")).unwrap();
+        assert!(gpl.starts_with("GIMP Palette\nName: Acme\n"));
+        assert!(gpl.contains("255   0   0\tprimary"));
+    }
+
+    #[test]
+    fn ase_structure() {
+        let ase = export_palette(&sample(), PaletteFormat::Ase, "Acme");
+        assert_eq!(&ase[0..4], b"ASEF");
+        assert_eq!(u16::from_be_bytes([ase[4], ase[5]]), 1);
+        // group start + five colors + group end
+        assert_eq!(u32::from_be_bytes([ase[8], ase[9], ase[10], ase[11]]), 7);
+        assert_eq!(u16::from_be_bytes([ase[12], ase[13]]), ASE_GROUP_START);
+        assert_eq!(&ase[ase.len() - 6..], &[0xC0, 0x02, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn format_from_str_aliases() {
+        assert_eq!("GIMP".parse::<PaletteFormat>().unwrap(), PaletteFormat::Gpl);
+        assert_eq!(
+            "tokens".parse::<PaletteFormat>().unwrap(),
+            PaletteFormat::Json
+        );
+        assert!("pdf".parse::<PaletteFormat>().is_err());
+    }
+}
@@ -0,0 +1,321 @@
+//! Delaunay triangulation and Voronoi cells of point sets.
+//!
+//! A plain Bowyer-Watson implementation: quadratic, but the point sets the
+//! presets scatter stay in the low thousands. Arithmetic is done in `f64` so
+//! the result does not depend on accumulated `f32` rounding.
+
+use super::geometry::{Point, Rect};
+
+/// Size of the enclosing super triangle relative to the point set.
+const SUPER_TRIANGLE_SCALE: f64 = 1e5;
+
+/// A triangle with its circumcircle, in the working coordinates.
+struct Triangle {
+    v: [usize; 3],
+    cx: f64,
+    cy: f64,
+    r2: f64,
+}
+
+impl Triangle {
+    fn new(v: [usize; 3], pts: &[(f64, f64)]) -> Self {
+        let (ax, ay) = pts[v[0]];
+        let (bx, by) = pts[v[1]];
+        let (cx, cy) = pts[v[2]];
+        let d = 2.0 * (ax * (by - cy) + bx * (cy - ay) + cx * (ay - by));
+        if d.abs() < f64::EPSILON {
+            // Degenerate (collinear) triangle: any later point breaks it up.
+            return Triangle {
+                v,
+                cx: 0.0,
+                cy: 0.0,
+                r2: f64::INFINITY,
+            };
+        }
+        let a2 = ax * ax + ay * ay;
+        let b2 = bx * bx + by * by;
+        let c2 = cx * cx + cy * cy;
+        let ux = (a2 * (by - cy) + b2 * (cy - ay) + c2 * (ay - by)) / d;
+        let uy = (a2 * (cx - bx) + b2 * (ax - cx) + c2 * (bx - ax)) / d;
+        Triangle {
+            v,
+            cx: ux,
+            cy: uy,
+            r2: (ax - ux).powi(2) + (ay - uy).powi(2),
+        }
+    }
+
+    fn circumcircle_contains(&self, (x, y): (f64, f64)) -> bool {
+        (x - self.cx).powi(2) + (y - self.cy).powi(2) < self.r2
+    }
+}
+
+/// Delaunay triangulation of `points`, as index triples into `points`.
+///
+/// Duplicate points are ignored; fewer than three distinct, non-collinear
+/// points yield no triangles.
+pub fn delaunay(points: &[Point]) -> Vec<[usize; 3]> {
+    let n = points.len();
+    if n < 3 {
+        return Vec::new();
+    }
+
+    let mut pts: Vec<(f64, f64)> = points.iter().map(|p| (p.x as f64, p.y as f64)).collect();
+    let (mut min_x, mut min_y) = (f64::MAX, f64::MAX);
+    let (mut max_x, mut max_y) = (f64::MIN, f64::MIN);
+    for &(x, y) in &pts {
+        min_x = min_x.min(x);
+        min_y = min_y.min(y);
+        max_x = max_x.max(x);
+        max_y = max_y.max(y);
+    }
+
+    // Super triangle enclosing every point. It has to be far away: when its
+    // vertices are close, their triangles shadow hull edges, which then go
+    // missing from the result.
+    let span = (max_x - min_x).max(max_y - min_y).max(1.0) * SUPER_TRIANGLE_SCALE;
+    let (mid_x, mid_y) = ((min_x + max_x) / 2.0, (min_y + max_y) / 2.0);
+    pts.push((mid_x - span, mid_y - span));
+    pts.push((mid_x, mid_y + span));
+    pts.push((mid_x + span, mid_y - span));
+
+    let mut triangles = vec![Triangle::new([n, n + 1, n + 2], &pts)];
+    let mut edges: Vec<(usize, usize)> = Vec::new();
+    for i in 0..n {
+        let p = pts[i];
+        if pts[..i].contains(&p) {
+            continue;
+        }
+
+        // Remove every triangle whose circumcircle holds the point, keeping
+        // the boundary of the hole they leave.
+        edges.clear();
+        triangles.retain(|t| {
+            if !t.circumcircle_contains(p) {
+                return true;
+            }
+            for k in 0..3 {
+                let (a, b) = (t.v[k], t.v[(k + 1) % 3]);
+                let edge = (a.min(b), a.max(b));
+                match edges.iter().position(|&e| e == edge) {
+                    Some(shared) => {
+                        edges.swap_remove(shared);
+                    }
+                    None => edges.push(edge),
+                }
+            }
+            false
+        });
+
+        for &(a, b) in &edges {
+            triangles.push(Triangle::new([a, b, i], &pts));
+        }
+    }
+
+    triangles
+        .into_iter()
+        .filter(|t| t.v.iter().all(|&v| v < n))
+        .map(|t| t.v)
+        .collect()
+}
+
+/// Voronoi cells of `points` clipped to `bounds`, as convex polygons in the
+/// same order as `points`. Later duplicates of a point get an empty cell.
+///
+/// Each cell is `bounds` cut by the bisectors between its point and the
+/// point's Delaunay neighbours, which are the only points that can bound it.
+pub fn voronoi(points: &[Point], bounds: Rect) -> Vec<Vec<Point>> {
+    let mut neighbours: Vec<Vec<usize>> = vec![Vec::new(); points.len()];
+    let triangles = delaunay(points);
+    if triangles.is_empty() {
+        // Fewer than three distinct or only collinear points: every other
+        // point is a neighbour.
+        for (i, list) in neighbours.iter_mut().enumerate() {
+            list.extend((0..points.len()).filter(|&j| j != i));
+        }
+    }
+    for [a, b, c] in triangles {
+        for (i, j) in [(a, b), (b, c), (c, a)] {
+            if !neighbours[i].contains(&j) {
+                neighbours[i].push(j);
+                neighbours[j].push(i);
+            }
+        }
+    }
+
+    let corners = vec![
+        Point::new(bounds.x, bounds.y),
+        Point::new(bounds.x + bounds.w, bounds.y),
+        Point::new(bounds.x + bounds.w, bounds.y + bounds.h),
+        Point::new(bounds.x, bounds.y + bounds.h),
+    ];
+    points
+        .iter()
+        .enumerate()
+        .map(|(i, &p)| {
+            if points[..i].contains(&p) {
+                return Vec::new();
+            }
+            neighbours[i]
+                .iter()
+                .filter(|&&j| points[j] != p)
+                .fold(corners.clone(), |cell, &j| {
+                    clip_to_side(&cell, p, points[j])
+                })
+        })
+        .collect()
+}
+
+/// The part of the convex `polygon` on `p`'s side of the bisector between
+/// `p` and `q` (Sutherland-Hodgman against a single half-plane).
+fn clip_to_side(polygon: &[Point], p: Point, q: Point) -> Vec<Point> {
+    let mid = p.lerp(q, 0.5);
+    let (dx, dy) = (q.x - p.x, q.y - p.y);
+    // Positive on `q`'s side.
+    let side = |a: Point| (a.x - mid.x) * dx + (a.y - mid.y) * dy;
+
+    let mut out = Vec::with_capacity(polygon.len() + 1);
+    for (k, &a) in polygon.iter().enumerate() {
+        let b = polygon[(k + 1) % polygon.len()];
+        let (sa, sb) = (side(a), side(b));
+        if sa <= 0.0 {
+            out.push(a);
+        }
+        if (sa < 0.0 && sb > 0.0) || (sa > 0.0 && sb < 0.0) {
+            out.push(a.lerp(b, sa / (sa - sb)));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
+
+    fn scattered(count: usize) -> Vec<Point> {
+        let mut rng = ChaCha8Rng::seed_from_u64(17);
+        (0..count)
+            .map(|_| Point::new(rng.gen_range(0.0..100.0), rng.gen_range(0.0..100.0)))
+            .collect()
+    }
+
+    fn area(points: &[Point], t: [usize; 3]) -> f32 {
+        let [a, b, c] = t.map(|i| points[i]);
+        ((b.x - a.x) * (c.y - a.y) - (c.x - a.x) * (b.y - a.y)).abs() / 2.0
+    }
+
+    #[test]
+    fn square_splits_into_two_triangles() {
+        let square = [
+            Point::new(0.0, 0.0),
+            Point::new(10.0, 0.0),
+            Point::new(10.0, 10.0),
+            Point::new(0.0, 11.0),
+        ];
+        let tris = delaunay(&square);
+        assert_eq!(tris.len(), 2);
+        let total: f32 = tris.iter().map(|&t| area(&square, t)).sum();
+        assert!((total - 105.0).abs() < 1e-3, "{total}");
+    }
+
+    #[test]
+    fn no_point_inside_any_circumcircle() {
+        let points = scattered(200);
+        let tris = delaunay(&points);
+        let pts: Vec<(f64, f64)> = points.iter().map(|p| (p.x as f64, p.y as f64)).collect();
+        for &v in &tris {
+            let t = Triangle::new(v, &pts);
+            for (i, &p) in pts.iter().enumerate() {
+                if v.contains(&i) {
+                    continue;
+                }
+                let d2 = (p.0 - t.cx).powi(2) + (p.1 - t.cy).powi(2);
+                assert!(d2 >= t.r2 * (1.0 - 1e-9), "point {i} inside {v:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn covers_the_convex_hull() {
+        // Corners plus interior points: the hull is the square, so the
+        // triangles must tile its whole area.
+        let mut points = vec![
+            Point::new(0.0, 0.0),
+            Point::new(100.0, 0.0),
+            Point::new(100.0, 100.0),
+            Point::new(0.0, 100.0),
+        ];
+        points.extend(
+            scattered(50)
+                .into_iter()
+                .map(|p| Point::new(1.0 + p.x * 0.98, 1.0 + p.y * 0.98)),
+        );
+        let total: f32 = delaunay(&points).iter().map(|&t| area(&points, t)).sum();
+        assert!((total - 10_000.0).abs() < 0.5, "{total}");
+    }
+
+    fn polygon_area(poly: &[Point]) -> f32 {
+        let n = poly.len();
+        (0..n)
+            .map(|i| {
+                let (a, b) = (poly[i], poly[(i + 1) % n]);
+                a.x * b.y - b.x * a.y
+            })
+            .sum::<f32>()
+            .abs()
+            / 2.0
+    }
+
+    const SQUARE: Rect = Rect {
+        x: 0.0,
+        y: 0.0,
+        w: 100.0,
+        h: 100.0,
+    };
+
+    #[test]
+    fn voronoi_cells_tile_the_bounds() {
+        let points = scattered(80);
+        let cells = voronoi(&points, SQUARE);
+        assert_eq!(cells.len(), points.len());
+        let total: f32 = cells.iter().map(|c| polygon_area(c)).sum();
+        assert!((total - 10_000.0).abs() < 0.5, "{total}");
+    }
+
+    #[test]
+    fn voronoi_cells_hold_their_nearest_points() {
+        let points = scattered(40);
+        let cells = voronoi(&points, SQUARE);
+        let mut rng = ChaCha8Rng::seed_from_u64(3);
+        for _ in 0..200 {
+            let probe = Point::new(rng.gen_range(0.0..100.0), rng.gen_range(0.0..100.0));
+            let nearest = (0..points.len())
+                .min_by(|&a, &b| {
+                    probe
+                        .distance(points[a])
+                        .total_cmp(&probe.distance(points[b]))
+                })
+                .unwrap();
+            let cell = crate::core::geometry::Path::polygon(&cells[nearest]);
+            assert!(cell.winding(probe) != 0 || cell.outline_distance(probe) < 1e-3);
+        }
+    }
+
+    #[test]
+    fn voronoi_of_two_points_splits_along_the_bisector() {
+        let cells = voronoi(&[Point::new(25.0, 50.0), Point::new(75.0, 50.0)], SQUARE);
+        assert!((polygon_area(&cells[0]) - 5_000.0).abs() < 1e-2);
+        assert!(cells[0].iter().all(|p| p.x <= 50.0 + 1e-4));
+        assert!(voronoi(&[Point::new(5.0, 5.0), Point::new(5.0, 5.0)], SQUARE)[1].is_empty());
+    }
+
+    #[test]
+    fn ignores_duplicates_and_tiny_inputs() {
+        assert!(delaunay(&[Point::new(0.0, 0.0), Point::new(1.0, 1.0)]).is_empty());
+        let p = Point::new(5.0, 5.0);
+        let tris = delaunay(&[Point::new(0.0, 0.0), Point::new(10.0, 0.0), p, p]);
+        assert_eq!(tris.len(), 1);
+    }
+}
@@ -7,6 +7,8 @@
 pub struct Typography {
     pub family: &'static str,
     pub weight: u16,
+    /// How to estimate the widths of text set in `family`.
+    pub metrics: Metrics,
 }
 
 impl Default for Typography {
@@ -14,6 +16,146 @@ impl Default for Typography {
         Self {
             family: "system-ui, -apple-system, Segoe UI, Roboto, Arial, sans-serif",
             weight: 700,
+            metrics: Metrics::Proportional(1.05),
         }
     }
 }
+
+/// Width estimate for a font stack, covering the widest font in it so text
+/// measured this way does not overflow whichever one the renderer picks.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Metrics {
+    /// The bold grotesque advances of [`advance`], scaled by this factor.
+    Proportional(f32),
+    /// Every glyph advances this fraction of the font size.
+    Monospace(f32),
+}
+
+impl Typography {
+    /// Estimated advance of `ch` in this stack as fraction of the font size.
+    pub fn advance(&self, ch: char) -> f32 {
+        match self.metrics {
+            Metrics::Proportional(scale) => advance(ch) * scale,
+            Metrics::Monospace(advance) => advance,
+        }
+    }
+
+    /// Like [`text_width`], measured with this stack's metrics.
+    pub fn text_width(&self, text: &str, font_size: f32, letter_spacing: f32) -> f32 {
+        text.chars()
+            .map(|ch| self.advance(ch) * font_size + letter_spacing)
+            .sum()
+    }
+}
+
+/// Font stacks presets may pick from deterministically. Advances of the
+/// widest bold faces run up to about 1.2 times the grotesque table (Century
+/// Gothic) and 0.602 em for monospace (Menlo, DejaVu Sans Mono).
+pub const FONT_STACKS: &[Typography] = &[
+    Typography {
+        family: "system-ui, -apple-system, Segoe UI, Roboto, Arial, sans-serif",
+        weight: 700,
+        metrics: Metrics::Proportional(1.05),
+    },
+    Typography {
+        family: "Futura, Century Gothic, Avenir Next, Montserrat, sans-serif",
+        weight: 600,
+        metrics: Metrics::Proportional(1.2),
+    },
+    Typography {
+        family: "Gill Sans, Optima, Candara, Noto Sans, sans-serif",
+        weight: 600,
+        metrics: Metrics::Proportional(1.05),
+    },
+    Typography {
+        family: "Georgia, Cambria, Times New Roman, serif",
+        weight: 700,
+        metrics: Metrics::Proportional(1.15),
+    },
+    Typography {
+        family: "Menlo, Consolas, DejaVu Sans Mono, monospace",
+        weight: 700,
+        metrics: Metrics::Monospace(0.61),
+    },
+];
+
+/// Cap height as fraction of the font size.
+pub const CAP_HEIGHT: f32 = 0.72;
+/// x-height as fraction of the font size. Text is vertically centered the
+/// way SVG's `dominant-baseline="middle"` does it: the baseline sits half an
+/// x-height below the text's `y`.
+pub const X_HEIGHT: f32 = 0.53;
+/// Descender depth as fraction of the font size.
+pub const DESCENDER: f32 = 0.21;
+
+/// Advance used for characters missing from the width table, in 1/1000 em.
+const DEFAULT_ADVANCE: u16 = 600;
+
+/// Advance widths of printable ASCII (space to tilde) in 1/1000 em, from the
+/// metrics of a bold grotesque. Renderers use their own fonts; this only
+/// needs to be close enough to lay out text before rendering.
+const ASCII_ADVANCES: [u16; 95] = [
+    278, 333, 474, 556, 556, 889, 722, 238, // ' '..'\''
+    333, 333, 389, 584, 278, 333, 278, 278, // '('..'/'
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, // '0'..'9'
+    333, 333, 584, 584, 584, 611, 975, // ':'..'@'
+    722, 722, 722, 722, 667, 611, 778, 722, 278, 556, 722, 611, 833, // 'A'..'M'
+    722, 778, 667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, // 'N'..'Z'
+    333, 278, 333, 584, 556, 333, // '['..'`'
+    556, 611, 556, 611, 556, 333, 611, 611, 278, 278, 556, 278, 889, // 'a'..'m'
+    611, 611, 611, 611, 389, 556, 333, 611, 556, 778, 556, 556, 500, // 'n'..'z'
+    389, 280, 389, 584, // '{'..'~'
+];
+
+/// Estimated advance of `ch` as fraction of the font size.
+pub fn advance(ch: char) -> f32 {
+    let units = match ch {
+        ' '..='~' => ASCII_ADVANCES[ch as usize - ' ' as usize],
+        _ => DEFAULT_ADVANCE,
+    };
+    units as f32 / 1000.0
+}
+
+/// Estimated width of `text` set at `font_size` with `letter_spacing` added
+/// after every glyph (as SVG does, including after the last one).
+pub fn text_width(text: &str, font_size: f32, letter_spacing: f32) -> f32 {
+    text.chars()
+        .map(|ch| advance(ch) * font_size + letter_spacing)
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn advances_cover_printable_ascii() {
+        assert_eq!(advance(' '), 0.278);
+        assert_eq!(advance('W'), 0.944);
+        assert_eq!(advance('~'), 0.584);
+        assert_eq!(advance('é'), 0.6);
+    }
+
+    #[test]
+    fn stacks_measure_at_least_their_widest_font() {
+        for typo in FONT_STACKS {
+            match typo.metrics {
+                Metrics::Proportional(scale) => assert!(scale >= 1.0, "{}", typo.family),
+                // Menlo and DejaVu Sans Mono advance 0.602 em.
+                Metrics::Monospace(advance) => assert!(advance >= 0.602, "{}", typo.family),
+            }
+        }
+        let mono = &FONT_STACKS[4];
+        assert_eq!(
+            mono.text_width("il", 10.0, 0.0),
+            mono.text_width("MW", 10.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn width_includes_letter_spacing() {
+        let plain = text_width("Acme", 100.0, 0.0);
+        assert!((plain - (72.2 + 55.6 + 88.9 + 55.6)).abs() < 1e-3);
+        assert!((text_width("Acme", 100.0, 5.0) - plain - 20.0).abs() < 1e-3);
+    }
+}
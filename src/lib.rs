@@ -8,8 +8,17 @@ pub mod cli;
 pub mod core;
 pub mod render;
 
+use std::collections::BTreeMap;
+
 use thiserror::Error;
 
+pub use crate::algorithms::params::{Param, ParamKind};
+pub use crate::algorithms::truchet::TileSet;
+pub use crate::core::geometry::MaskShape;
+
+use crate::core::palette::{Palette, Rgb};
+use crate::core::palette_export::{export_palette, PaletteFormat};
+
 /// Output format for generated logos.
 #[derive(Debug, Clone, Copy)]
 pub enum OutputFormat {
@@ -24,25 +33,71 @@ pub enum OutputFormat {
 pub struct RenderOptions {
     /// Output size in pixels (used for PNG; SVG uses it as width/height attributes).
     pub size_px: u32,
-    /// Padding in [0.0..0.5] of the canvas size.
+    /// Canvas width in pixels for non-square output; `None` uses `size_px`.
+    pub width_px: Option<u32>,
+    /// Canvas height in pixels for non-square output; `None` uses `size_px`.
+    pub height_px: Option<u32>,
+    /// Padding in [0.0..0.5] of the canvas size, applied per axis.
     pub padding_frac: f32,
     /// If set, XORed into the derived seed to allow variants per same input.
     pub variant: Option<u64>,
     /// Transparent background for PNG/SVG.
     pub transparent_background: bool,
+    /// Re-sample the palette until its roles stay distinguishable under
+    /// simulated protanopia, deuteranopia and tritanopia.
+    pub cvd_safe: bool,
+    /// If set, render every palette role in this single ink over a
+    /// transparent canvas, separating shapes with knockouts.
+    pub monochrome: Option<Rgb>,
+    /// App icon outline the whole output, background included, is clipped
+    /// to, fitted to the canvas; `None` leaves it unmasked.
+    pub mask: Option<MaskShape>,
+    /// Overrides of the preset's parameters by name; see `Preset::params`.
+    pub params: BTreeMap<String, f64>,
 }
 
 impl Default for RenderOptions {
     fn default() -> Self {
         Self {
             size_px: 512,
+            width_px: None,
+            height_px: None,
             padding_frac: 0.12,
             variant: None,
             transparent_background: false,
+            cvd_safe: false,
+            monochrome: None,
+            mask: None,
+            params: BTreeMap::new(),
         }
     }
 }
 
+impl RenderOptions {
+    /// Canvas width and height: `width_px`/`height_px` where set, otherwise
+    /// `size_px`.
+    pub fn canvas_size(&self) -> (u32, u32) {
+        (
+            self.width_px.unwrap_or(self.size_px),
+            self.height_px.unwrap_or(self.size_px),
+        )
+    }
+
+    /// Whether the caller fixed either canvas dimension explicitly.
+    pub fn has_explicit_canvas(&self) -> bool {
+        self.width_px.is_some() || self.height_px.is_some()
+    }
+
+    /// Padding in pixels along the x and y axes.
+    pub fn padding_px(&self) -> (f32, f32) {
+        let (w, h) = self.canvas_size();
+        (
+            (self.padding_frac * w as f32).round(),
+            (self.padding_frac * h as f32).round(),
+        )
+    }
+}
+
 /// Logo generation algorithm presets.
 #[derive(Debug, Clone, Copy)]
 pub enum Preset {
@@ -50,6 +105,44 @@ pub enum Preset {
     MonogramBadge,
     /// Generates overlapping geometric shapes with a lettermark.
     GeometricPattern,
+    /// Generates a horizontally mirrored grid of seeded cells.
+    Identicon,
+    /// Generates a seeded tiling of glyphs and shapes with a plated lettermark.
+    GlyphGridPattern,
+    /// Woven Bezier ribbons clipped to a circular badge.
+    RibbonMark,
+    /// Seal with the full name around concentric rings and centered initials.
+    Emblem,
+    /// Full name in a seeded typeface with an accent shape, on a canvas sized to the text.
+    Wordmark,
+    /// Delaunay-triangulated mosaic shaded along the palette gradient.
+    LowPoly,
+    /// Maze-like Truchet tiles clipped to a badge.
+    Truchet,
+    /// 8-bit creature sprite drawn as crisp pixels.
+    PixelAvatar,
+    /// Concentric rings and sunburst rays.
+    Sunburst,
+    /// Initials extruded into isometric 3D blocks.
+    Isometric,
+    /// Smooth organic blobs layered with tinted overlaps.
+    Blob,
+    /// Voronoi cell mosaic, optionally highlighting the initials.
+    VoronoiMosaic,
+    /// Initial carved out of the gaps between solid shapes.
+    NegativeSpace,
+    /// Contour lines of a seeded terrain stroked inside a badge.
+    Topographic,
+    /// Halftone dot screen of a gradient or the initials.
+    Halftone,
+    /// Two to four woven interlocking rings.
+    InterlockingRings,
+    /// Orthogonal circuit traces routed out from a chip.
+    CircuitTraces,
+    /// Seeded L-system sprig, leaf or tree inside a badge.
+    Plant,
+    /// Overlapping initials interlocking where they cross.
+    StackedMonogram,
 }
 
 impl Preset {
@@ -57,6 +150,25 @@ impl Preset {
         match self {
             Preset::MonogramBadge => "monogram-badge",
             Preset::GeometricPattern => "geometric-pattern",
+            Preset::Identicon => "identicon",
+            Preset::GlyphGridPattern => "glyph-grid-pattern",
+            Preset::RibbonMark => "ribbon-mark",
+            Preset::Emblem => "emblem",
+            Preset::Wordmark => "wordmark",
+            Preset::LowPoly => "low-poly",
+            Preset::Truchet => "truchet",
+            Preset::PixelAvatar => "pixel-avatar",
+            Preset::Sunburst => "sunburst",
+            Preset::Isometric => "isometric",
+            Preset::Blob => "blob",
+            Preset::VoronoiMosaic => "voronoi",
+            Preset::NegativeSpace => "negative-space",
+            Preset::Topographic => "topographic",
+            Preset::Halftone => "halftone",
+            Preset::InterlockingRings => "interlocking-rings",
+            Preset::CircuitTraces => "circuit",
+            Preset::Plant => "plant",
+            Preset::StackedMonogram => "stacked-monogram",
         }
     }
 
@@ -68,6 +180,63 @@ impl Preset {
             Preset::GeometricPattern => {
                 "Overlapping geometric shapes (circles/rectangles) with centered lettermark"
             }
+            Preset::Identicon => {
+                "GitHub-style identicon: mirrored grid of cells filled from the seed"
+            }
+            Preset::GlyphGridPattern => {
+                "Seeded grid of small glyphs and shapes from the input characters, with lettermark on a backing plate"
+            }
+            Preset::RibbonMark => {
+                "Two to four flowing Bezier ribbons clipped to a circular badge, weaving over and under each other"
+            }
+            Preset::Emblem => {
+                "Seal with concentric rings, the full name set around the circumference and initials in the center"
+            }
+            Preset::Wordmark => {
+                "Horizontal logo setting the full name with seeded typeface, case, letter spacing and accent shape"
+            }
+            Preset::LowPoly => {
+                "Low-poly mosaic of Delaunay triangles shaded along the palette gradient, with optional lettermark"
+            }
+            Preset::Truchet => {
+                "Maze-like grid of seeded Truchet tiles (quarter arcs or diagonals) clipped to a badge"
+            }
+            Preset::PixelAvatar => {
+                "8-bit style avatar: mirrored creature sprite with outline and eyes on a coarse pixel grid, with crisp edges"
+            }
+            Preset::Sunburst => {
+                "Concentric rings and/or sunburst rays with seeded counts and thicknesses, with optional lettermark"
+            }
+            Preset::Isometric => {
+                "Initials extruded into isometric 3D blocks with shaded top and side faces"
+            }
+            Preset::Blob => {
+                "Smooth organic blob shapes drawn as cubic splines, layered with soft tinted overlaps"
+            }
+            Preset::VoronoiMosaic => {
+                "Seeded Voronoi cell mosaic with palette colors and lightness jitter, optionally highlighting the initials"
+            }
+            Preset::NegativeSpace => {
+                "Initial letter formed by the negative space between solid shapes cut from a badge"
+            }
+            Preset::Topographic => {
+                "Topographic contour lines of a seeded noise terrain stroked inside a badge, with heavier index contours"
+            }
+            Preset::Halftone => {
+                "Halftone dot screen inside a badge, dot sizes encoding a seeded gradient or the initials"
+            }
+            Preset::InterlockingRings => {
+                "Two to four interlocking rings woven over and under, counted from the input's words"
+            }
+            Preset::CircuitTraces => {
+                "Circuit board traces routed without crossings from a chip carrying the initials to via pads"
+            }
+            Preset::Plant => {
+                "A sprig, leaf or tree grown from a seeded L-system grammar inside a badge"
+            }
+            Preset::StackedMonogram => {
+                "Stacked monogram: overlapping initials in their own colors, interlocking where they cross"
+            }
         }
     }
 
@@ -75,12 +244,59 @@ impl Preset {
         match self {
             Preset::MonogramBadge => "Badge",
             Preset::GeometricPattern => "Abstract",
+            Preset::Identicon => "Avatar",
+            Preset::GlyphGridPattern => "Pattern",
+            Preset::RibbonMark => "Abstract",
+            Preset::Emblem => "Badge",
+            Preset::Wordmark => "Typographic",
+            Preset::LowPoly => "Abstract",
+            Preset::Truchet => "Pattern",
+            Preset::PixelAvatar => "Avatar",
+            Preset::Sunburst => "Abstract",
+            Preset::Isometric => "Typographic",
+            Preset::Blob => "Abstract",
+            Preset::VoronoiMosaic => "Pattern",
+            Preset::NegativeSpace => "Badge",
+            Preset::Topographic => "Abstract",
+            Preset::Halftone => "Pattern",
+            Preset::InterlockingRings => "Abstract",
+            Preset::CircuitTraces => "Badge",
+            Preset::Plant => "Badge",
+            Preset::StackedMonogram => "Typographic",
         }
     }
 
+    /// Tunable parameters, with their types, ranges and defaults, that
+    /// `RenderOptions::params` can override.
+    pub fn params(&self) -> &'static [Param] {
+        algorithms::params_for(*self)
+    }
+
     /// Returns all available presets.
     pub fn all() -> Vec<Preset> {
-        vec![Preset::MonogramBadge, Preset::GeometricPattern]
+        vec![
+            Preset::MonogramBadge,
+            Preset::GeometricPattern,
+            Preset::Identicon,
+            Preset::GlyphGridPattern,
+            Preset::RibbonMark,
+            Preset::Emblem,
+            Preset::Wordmark,
+            Preset::LowPoly,
+            Preset::Truchet,
+            Preset::PixelAvatar,
+            Preset::Sunburst,
+            Preset::Isometric,
+            Preset::Blob,
+            Preset::VoronoiMosaic,
+            Preset::NegativeSpace,
+            Preset::Topographic,
+            Preset::Halftone,
+            Preset::InterlockingRings,
+            Preset::CircuitTraces,
+            Preset::Plant,
+            Preset::StackedMonogram,
+        ]
     }
 }
 
@@ -91,6 +307,25 @@ impl std::str::FromStr for Preset {
         match s.trim().to_ascii_lowercase().as_str() {
             "monogram-badge" | "monogram" | "badge" => Ok(Preset::MonogramBadge),
             "geometric-pattern" | "geometric" | "pattern" => Ok(Preset::GeometricPattern),
+            "identicon" | "github" | "mirror-grid" => Ok(Preset::Identicon),
+            "glyph-grid-pattern" | "glyph-grid" | "glyphs" => Ok(Preset::GlyphGridPattern),
+            "ribbon-mark" | "ribbon" | "ribbons" => Ok(Preset::RibbonMark),
+            "emblem" | "seal" | "stamp" => Ok(Preset::Emblem),
+            "wordmark" | "word" | "logotype" => Ok(Preset::Wordmark),
+            "low-poly" | "lowpoly" | "triangles" => Ok(Preset::LowPoly),
+            "truchet" | "maze" => Ok(Preset::Truchet),
+            "pixel-avatar" | "pixel" | "sprite" => Ok(Preset::PixelAvatar),
+            "sunburst" | "radial" => Ok(Preset::Sunburst),
+            "isometric" | "iso" | "blocks" | "3d" => Ok(Preset::Isometric),
+            "blob" | "blobs" | "organic" => Ok(Preset::Blob),
+            "voronoi" | "mosaic" | "cells" => Ok(Preset::VoronoiMosaic),
+            "negative-space" | "negative" | "cutout" => Ok(Preset::NegativeSpace),
+            "topographic" | "topo" | "contours" => Ok(Preset::Topographic),
+            "halftone" | "dots" | "screen" => Ok(Preset::Halftone),
+            "interlocking-rings" | "rings" | "interlock" | "links" => Ok(Preset::InterlockingRings),
+            "circuit" | "pcb" | "traces" => Ok(Preset::CircuitTraces),
+            "plant" | "lsystem" | "botanical" => Ok(Preset::Plant),
+            "stacked-monogram" | "interlocked" | "luxury" => Ok(Preset::StackedMonogram),
             _ => Err(LoGenError::UnknownPreset(s.to_string())),
         }
     }
@@ -179,6 +414,29 @@ impl LoGen {
         let fb_ref = font_bytes.as_deref();
         Self::generate_png_with_font(input, preset, opts, fb_ref)
     }
+
+    /// Return the palette the given preset picks for `input`.
+    pub fn palette(
+        input: &str,
+        preset: Preset,
+        opts: &RenderOptions,
+    ) -> Result<Palette, LoGenError> {
+        let scene = algorithms::build_scene(input, preset, opts)?;
+        Ok(scene.palette)
+    }
+
+    /// Export the palette picked for `input` in a design-tool format. The
+    /// normalized input is used as the palette name.
+    pub fn export_palette(
+        input: &str,
+        preset: Preset,
+        opts: &RenderOptions,
+        format: PaletteFormat,
+    ) -> Result<Vec<u8>, LoGenError> {
+        let palette = Self::palette(input, preset, opts)?;
+        let name = core::seed::normalize_input(input);
+        Ok(export_palette(&palette, format, &name))
+    }
 }
 
 #[cfg(test)]
@@ -204,24 +462,41 @@ mod tests {
     fn render_options_default() {
         let opts = RenderOptions::default();
         assert_eq!(opts.size_px, 512);
+        assert!(opts.width_px.is_none());
+        assert!(opts.height_px.is_none());
+        assert_eq!(opts.canvas_size(), (512, 512));
         assert!((opts.padding_frac - 0.12).abs() < 0.001);
         assert!(opts.variant.is_none());
         assert!(!opts.transparent_background);
+        assert!(!opts.cvd_safe);
+        assert!(opts.monochrome.is_none());
+        assert!(opts.mask.is_none());
     }
 
     #[test]
     fn render_options_clone() {
         let opts = RenderOptions {
             size_px: 256,
+            width_px: Some(1200),
+            height_px: Some(630),
             padding_frac: 0.15,
             variant: Some(42),
             transparent_background: true,
+            cvd_safe: true,
+            monochrome: Some(Rgb { r: 0, g: 0, b: 0 }),
+            mask: Some(MaskShape::Squircle),
+            params: BTreeMap::from([("density".to_string(), 0.5)]),
         };
         let cloned = opts.clone();
         assert_eq!(cloned.size_px, 256);
+        assert_eq!(cloned.canvas_size(), (1200, 630));
         assert!((cloned.padding_frac - 0.15).abs() < 0.001);
         assert_eq!(cloned.variant, Some(42));
         assert!(cloned.transparent_background);
+        assert!(cloned.cvd_safe);
+        assert_eq!(cloned.monochrome, Some(Rgb { r: 0, g: 0, b: 0 }));
+        assert_eq!(cloned.mask, Some(MaskShape::Squircle));
+        assert_eq!(cloned.params.get("density"), Some(&0.5));
     }
 
     #[test]
@@ -243,6 +518,25 @@ mod tests {
     fn preset_id() {
         assert_eq!(Preset::MonogramBadge.id(), "monogram-badge");
         assert_eq!(Preset::GeometricPattern.id(), "geometric-pattern");
+        assert_eq!(Preset::Identicon.id(), "identicon");
+        assert_eq!(Preset::GlyphGridPattern.id(), "glyph-grid-pattern");
+        assert_eq!(Preset::RibbonMark.id(), "ribbon-mark");
+        assert_eq!(Preset::Emblem.id(), "emblem");
+        assert_eq!(Preset::Wordmark.id(), "wordmark");
+        assert_eq!(Preset::LowPoly.id(), "low-poly");
+        assert_eq!(Preset::Truchet.id(), "truchet");
+        assert_eq!(Preset::PixelAvatar.id(), "pixel-avatar");
+        assert_eq!(Preset::Sunburst.id(), "sunburst");
+        assert_eq!(Preset::Isometric.id(), "isometric");
+        assert_eq!(Preset::Blob.id(), "blob");
+        assert_eq!(Preset::VoronoiMosaic.id(), "voronoi");
+        assert_eq!(Preset::NegativeSpace.id(), "negative-space");
+        assert_eq!(Preset::Topographic.id(), "topographic");
+        assert_eq!(Preset::Halftone.id(), "halftone");
+        assert_eq!(Preset::InterlockingRings.id(), "interlocking-rings");
+        assert_eq!(Preset::CircuitTraces.id(), "circuit");
+        assert_eq!(Preset::Plant.id(), "plant");
+        assert_eq!(Preset::StackedMonogram.id(), "stacked-monogram");
     }
 
     #[test]
@@ -254,20 +548,158 @@ mod tests {
         let gp_desc = Preset::GeometricPattern.description();
         assert!(gp_desc.contains("geometric"));
         assert!(gp_desc.contains("shapes"));
+
+        let id_desc = Preset::Identicon.description();
+        assert!(id_desc.contains("identicon"));
+        assert!(id_desc.contains("mirrored"));
+
+        let desc = Preset::GlyphGridPattern.description();
+        assert!(desc.contains("grid"));
+        assert!(desc.contains("glyphs"));
+        assert!(desc.contains("lettermark"));
+
+        let desc = Preset::RibbonMark.description();
+        assert!(desc.contains("Bezier"));
+        assert!(desc.contains("ribbons"));
+        assert!(desc.contains("weaving"));
+
+        let desc = Preset::Emblem.description();
+        assert!(desc.contains("rings"));
+        assert!(desc.contains("circumference"));
+        assert!(desc.contains("initials"));
+
+        let desc = Preset::Wordmark.description();
+        assert!(desc.contains("full name"));
+        assert!(desc.contains("letter spacing"));
+        assert!(desc.contains("accent"));
+
+        let desc = Preset::LowPoly.description();
+        assert!(desc.contains("Delaunay"));
+        assert!(desc.contains("gradient"));
+
+        let desc = Preset::Truchet.description();
+        assert!(desc.contains("Truchet"));
+        assert!(desc.contains("badge"));
+
+        let desc = Preset::PixelAvatar.description();
+        assert!(desc.contains("8-bit"));
+        assert!(desc.contains("sprite"));
+
+        let desc = Preset::Sunburst.description();
+        assert!(desc.contains("rings"));
+        assert!(desc.contains("sunburst"));
+
+        let desc = Preset::Isometric.description();
+        assert!(desc.contains("isometric"));
+        assert!(desc.contains("extruded"));
+
+        let desc = Preset::Blob.description();
+        assert!(desc.contains("organic"));
+        assert!(desc.contains("blob"));
+
+        let desc = Preset::VoronoiMosaic.description();
+        assert!(desc.contains("Voronoi"));
+        assert!(desc.contains("mosaic"));
+
+        let desc = Preset::NegativeSpace.description();
+        assert!(desc.contains("negative space"));
+        assert!(desc.contains("letter"));
+
+        let desc = Preset::Topographic.description();
+        assert!(desc.contains("contour"));
+        assert!(desc.contains("badge"));
+
+        let desc = Preset::Halftone.description();
+        assert!(desc.contains("Halftone"));
+        assert!(desc.contains("dot"));
+
+        let desc = Preset::InterlockingRings.description();
+        assert!(desc.contains("rings"));
+        assert!(desc.contains("woven"));
+
+        let desc = Preset::CircuitTraces.description();
+        assert!(desc.contains("traces"));
+        assert!(desc.contains("initials"));
+
+        let desc = Preset::Plant.description();
+        assert!(desc.contains("L-system"));
+        assert!(desc.contains("badge"));
+
+        let desc = Preset::StackedMonogram.description();
+        assert!(desc.contains("Stacked"));
+        assert!(desc.contains("interlocking"));
     }
 
     #[test]
     fn preset_category() {
         assert_eq!(Preset::MonogramBadge.category(), "Badge");
         assert_eq!(Preset::GeometricPattern.category(), "Abstract");
+        assert_eq!(Preset::Identicon.category(), "Avatar");
+        assert_eq!(Preset::GlyphGridPattern.category(), "Pattern");
+        assert_eq!(Preset::RibbonMark.category(), "Abstract");
+        assert_eq!(Preset::Emblem.category(), "Badge");
+        assert_eq!(Preset::Wordmark.category(), "Typographic");
+        assert_eq!(Preset::LowPoly.category(), "Abstract");
+        assert_eq!(Preset::Truchet.category(), "Pattern");
+        assert_eq!(Preset::PixelAvatar.category(), "Avatar");
+        assert_eq!(Preset::Sunburst.category(), "Abstract");
+        assert_eq!(Preset::Isometric.category(), "Typographic");
+        assert_eq!(Preset::Blob.category(), "Abstract");
+        assert_eq!(Preset::VoronoiMosaic.category(), "Pattern");
+        assert_eq!(Preset::NegativeSpace.category(), "Badge");
+        assert_eq!(Preset::Topographic.category(), "Abstract");
+        assert_eq!(Preset::Halftone.category(), "Pattern");
+        assert_eq!(Preset::InterlockingRings.category(), "Abstract");
+        assert_eq!(Preset::CircuitTraces.category(), "Badge");
+        assert_eq!(Preset::Plant.category(), "Badge");
+        assert_eq!(Preset::StackedMonogram.category(), "Typographic");
+    }
+
+    #[test]
+    fn preset_params_are_well_formed() {
+        for preset in Preset::all() {
+            let params = preset.params();
+            assert!(!params.is_empty(), "{}", preset.id());
+            for (i, p) in params.iter().enumerate() {
+                assert!(
+                    p.min <= p.default && p.default <= p.max,
+                    "{}: {}",
+                    preset.id(),
+                    p.name
+                );
+                if p.kind == ParamKind::Int {
+                    assert_eq!(p.default.fract(), 0.0, "{}: {}", preset.id(), p.name);
+                }
+                assert!(params[i + 1..].iter().all(|q| q.name != p.name));
+            }
+        }
     }
 
     #[test]
     fn preset_all() {
         let presets = Preset::all();
-        assert_eq!(presets.len(), 2);
+        assert_eq!(presets.len(), 21);
         assert!(matches!(presets[0], Preset::MonogramBadge));
         assert!(matches!(presets[1], Preset::GeometricPattern));
+        assert!(matches!(presets[2], Preset::Identicon));
+        assert!(matches!(presets[3], Preset::GlyphGridPattern));
+        assert!(matches!(presets[4], Preset::RibbonMark));
+        assert!(matches!(presets[5], Preset::Emblem));
+        assert!(matches!(presets[6], Preset::Wordmark));
+        assert!(matches!(presets[7], Preset::LowPoly));
+        assert!(matches!(presets[8], Preset::Truchet));
+        assert!(matches!(presets[9], Preset::PixelAvatar));
+        assert!(matches!(presets[10], Preset::Sunburst));
+        assert!(matches!(presets[11], Preset::Isometric));
+        assert!(matches!(presets[12], Preset::Blob));
+        assert!(matches!(presets[13], Preset::VoronoiMosaic));
+        assert!(matches!(presets[14], Preset::NegativeSpace));
+        assert!(matches!(presets[15], Preset::Topographic));
+        assert!(matches!(presets[16], Preset::Halftone));
+        assert!(matches!(presets[17], Preset::InterlockingRings));
+        assert!(matches!(presets[18], Preset::CircuitTraces));
+        assert!(matches!(presets[19], Preset::Plant));
+        assert!(matches!(presets[20], Preset::StackedMonogram));
     }
 
     #[test]
@@ -303,6 +735,288 @@ mod tests {
         assert!(matches!(p4, Preset::GeometricPattern));
     }
 
+    #[test]
+    fn preset_from_str_identicon() {
+        let p1: Preset = "identicon".parse().expect("parse");
+        assert!(matches!(p1, Preset::Identicon));
+
+        let p2: Preset = "github".parse().expect("parse");
+        assert!(matches!(p2, Preset::Identicon));
+
+        let p3: Preset = "Mirror-Grid".parse().expect("parse");
+        assert!(matches!(p3, Preset::Identicon));
+    }
+
+    #[test]
+    fn preset_from_str_glyph_grid_pattern() {
+        let p1: Preset = "glyph-grid-pattern".parse().expect("parse");
+        assert!(matches!(p1, Preset::GlyphGridPattern));
+
+        let p2: Preset = "glyph-grid".parse().expect("parse");
+        assert!(matches!(p2, Preset::GlyphGridPattern));
+
+        let p3: Preset = "glyphs".parse().expect("parse");
+        assert!(matches!(p3, Preset::GlyphGridPattern));
+
+        let p4: Preset = "GLYPH-GRID-PATTERN".parse().expect("parse");
+        assert!(matches!(p4, Preset::GlyphGridPattern));
+    }
+
+    #[test]
+    fn preset_from_str_ribbon_mark() {
+        let p1: Preset = "ribbon-mark".parse().expect("parse");
+        assert!(matches!(p1, Preset::RibbonMark));
+
+        let p2: Preset = "ribbon".parse().expect("parse");
+        assert!(matches!(p2, Preset::RibbonMark));
+
+        let p3: Preset = "ribbons".parse().expect("parse");
+        assert!(matches!(p3, Preset::RibbonMark));
+
+        let p4: Preset = "RIBBON-MARK".parse().expect("parse");
+        assert!(matches!(p4, Preset::RibbonMark));
+    }
+
+    #[test]
+    fn preset_from_str_emblem() {
+        let p1: Preset = "emblem".parse().expect("parse");
+        assert!(matches!(p1, Preset::Emblem));
+
+        let p2: Preset = "seal".parse().expect("parse");
+        assert!(matches!(p2, Preset::Emblem));
+
+        let p3: Preset = "stamp".parse().expect("parse");
+        assert!(matches!(p3, Preset::Emblem));
+
+        let p4: Preset = "EMBLEM".parse().expect("parse");
+        assert!(matches!(p4, Preset::Emblem));
+    }
+
+    #[test]
+    fn preset_from_str_wordmark() {
+        let p1: Preset = "wordmark".parse().expect("parse");
+        assert!(matches!(p1, Preset::Wordmark));
+
+        let p2: Preset = "word".parse().expect("parse");
+        assert!(matches!(p2, Preset::Wordmark));
+
+        let p3: Preset = "logotype".parse().expect("parse");
+        assert!(matches!(p3, Preset::Wordmark));
+
+        let p4: Preset = "WORDMARK".parse().expect("parse");
+        assert!(matches!(p4, Preset::Wordmark));
+    }
+
+    #[test]
+    fn preset_from_str_low_poly() {
+        let p1: Preset = "low-poly".parse().expect("parse");
+        assert!(matches!(p1, Preset::LowPoly));
+
+        let p2: Preset = "lowpoly".parse().expect("parse");
+        assert!(matches!(p2, Preset::LowPoly));
+
+        let p3: Preset = "triangles".parse().expect("parse");
+        assert!(matches!(p3, Preset::LowPoly));
+
+        let p4: Preset = "LOW-POLY".parse().expect("parse");
+        assert!(matches!(p4, Preset::LowPoly));
+    }
+
+    #[test]
+    fn preset_from_str_truchet() {
+        let p1: Preset = "truchet".parse().expect("parse");
+        assert!(matches!(p1, Preset::Truchet));
+
+        let p2: Preset = "maze".parse().expect("parse");
+        assert!(matches!(p2, Preset::Truchet));
+
+        let p3: Preset = "TRUCHET".parse().expect("parse");
+        assert!(matches!(p3, Preset::Truchet));
+    }
+
+    #[test]
+    fn preset_from_str_pixel_avatar() {
+        let p1: Preset = "pixel-avatar".parse().expect("parse");
+        assert!(matches!(p1, Preset::PixelAvatar));
+
+        let p2: Preset = "pixel".parse().expect("parse");
+        assert!(matches!(p2, Preset::PixelAvatar));
+
+        let p3: Preset = "sprite".parse().expect("parse");
+        assert!(matches!(p3, Preset::PixelAvatar));
+
+        let p4: Preset = "PIXEL-AVATAR".parse().expect("parse");
+        assert!(matches!(p4, Preset::PixelAvatar));
+    }
+
+    #[test]
+    fn preset_from_str_sunburst() {
+        let p1: Preset = "sunburst".parse().expect("parse");
+        assert!(matches!(p1, Preset::Sunburst));
+
+        let p2: Preset = "radial".parse().expect("parse");
+        assert!(matches!(p2, Preset::Sunburst));
+
+        let p3: Preset = "SUNBURST".parse().expect("parse");
+        assert!(matches!(p3, Preset::Sunburst));
+    }
+
+    #[test]
+    fn preset_from_str_isometric() {
+        let p1: Preset = "isometric".parse().expect("parse");
+        assert!(matches!(p1, Preset::Isometric));
+
+        let p2: Preset = "iso".parse().expect("parse");
+        assert!(matches!(p2, Preset::Isometric));
+
+        let p3: Preset = "blocks".parse().expect("parse");
+        assert!(matches!(p3, Preset::Isometric));
+
+        let p4: Preset = "3d".parse().expect("parse");
+        assert!(matches!(p4, Preset::Isometric));
+
+        let p5: Preset = "ISOMETRIC".parse().expect("parse");
+        assert!(matches!(p5, Preset::Isometric));
+    }
+
+    #[test]
+    fn preset_from_str_blob() {
+        let p1: Preset = "blob".parse().expect("parse");
+        assert!(matches!(p1, Preset::Blob));
+
+        let p2: Preset = "blobs".parse().expect("parse");
+        assert!(matches!(p2, Preset::Blob));
+
+        let p3: Preset = "organic".parse().expect("parse");
+        assert!(matches!(p3, Preset::Blob));
+
+        let p4: Preset = "BLOB".parse().expect("parse");
+        assert!(matches!(p4, Preset::Blob));
+    }
+
+    #[test]
+    fn preset_from_str_voronoi() {
+        let p1: Preset = "voronoi".parse().expect("parse");
+        assert!(matches!(p1, Preset::VoronoiMosaic));
+
+        let p2: Preset = "mosaic".parse().expect("parse");
+        assert!(matches!(p2, Preset::VoronoiMosaic));
+
+        let p3: Preset = "cells".parse().expect("parse");
+        assert!(matches!(p3, Preset::VoronoiMosaic));
+
+        let p4: Preset = "VORONOI".parse().expect("parse");
+        assert!(matches!(p4, Preset::VoronoiMosaic));
+    }
+
+    #[test]
+    fn preset_from_str_negative_space() {
+        let p1: Preset = "negative-space".parse().expect("parse");
+        assert!(matches!(p1, Preset::NegativeSpace));
+
+        let p2: Preset = "negative".parse().expect("parse");
+        assert!(matches!(p2, Preset::NegativeSpace));
+
+        let p3: Preset = "cutout".parse().expect("parse");
+        assert!(matches!(p3, Preset::NegativeSpace));
+
+        let p4: Preset = "NEGATIVE-SPACE".parse().expect("parse");
+        assert!(matches!(p4, Preset::NegativeSpace));
+    }
+
+    #[test]
+    fn preset_from_str_topographic() {
+        let p1: Preset = "topographic".parse().expect("parse");
+        assert!(matches!(p1, Preset::Topographic));
+
+        let p2: Preset = "topo".parse().expect("parse");
+        assert!(matches!(p2, Preset::Topographic));
+
+        let p3: Preset = "contours".parse().expect("parse");
+        assert!(matches!(p3, Preset::Topographic));
+
+        let p4: Preset = "TOPOGRAPHIC".parse().expect("parse");
+        assert!(matches!(p4, Preset::Topographic));
+    }
+
+    #[test]
+    fn preset_from_str_halftone() {
+        let p1: Preset = "halftone".parse().expect("parse");
+        assert!(matches!(p1, Preset::Halftone));
+
+        let p2: Preset = "dots".parse().expect("parse");
+        assert!(matches!(p2, Preset::Halftone));
+
+        let p3: Preset = "screen".parse().expect("parse");
+        assert!(matches!(p3, Preset::Halftone));
+
+        let p4: Preset = "HALFTONE".parse().expect("parse");
+        assert!(matches!(p4, Preset::Halftone));
+    }
+
+    #[test]
+    fn preset_from_str_interlocking_rings() {
+        let p1: Preset = "interlocking-rings".parse().expect("parse");
+        assert!(matches!(p1, Preset::InterlockingRings));
+
+        let p2: Preset = "interlock".parse().expect("parse");
+        assert!(matches!(p2, Preset::InterlockingRings));
+
+        let p3: Preset = "links".parse().expect("parse");
+        assert!(matches!(p3, Preset::InterlockingRings));
+
+        let p4: Preset = "INTERLOCKING-RINGS".parse().expect("parse");
+        assert!(matches!(p4, Preset::InterlockingRings));
+
+        let p5: Preset = "rings".parse().expect("parse");
+        assert!(matches!(p5, Preset::InterlockingRings));
+    }
+
+    #[test]
+    fn preset_from_str_circuit() {
+        let p1: Preset = "circuit".parse().expect("parse");
+        assert!(matches!(p1, Preset::CircuitTraces));
+
+        let p2: Preset = "pcb".parse().expect("parse");
+        assert!(matches!(p2, Preset::CircuitTraces));
+
+        let p3: Preset = "traces".parse().expect("parse");
+        assert!(matches!(p3, Preset::CircuitTraces));
+
+        let p4: Preset = "CIRCUIT".parse().expect("parse");
+        assert!(matches!(p4, Preset::CircuitTraces));
+    }
+
+    #[test]
+    fn preset_from_str_plant() {
+        let p1: Preset = "plant".parse().expect("parse");
+        assert!(matches!(p1, Preset::Plant));
+
+        let p2: Preset = "lsystem".parse().expect("parse");
+        assert!(matches!(p2, Preset::Plant));
+
+        let p3: Preset = "botanical".parse().expect("parse");
+        assert!(matches!(p3, Preset::Plant));
+
+        let p4: Preset = "PLANT".parse().expect("parse");
+        assert!(matches!(p4, Preset::Plant));
+    }
+
+    #[test]
+    fn preset_from_str_stacked_monogram() {
+        let p1: Preset = "stacked-monogram".parse().expect("parse");
+        assert!(matches!(p1, Preset::StackedMonogram));
+
+        let p2: Preset = "interlocked".parse().expect("parse");
+        assert!(matches!(p2, Preset::StackedMonogram));
+
+        let p3: Preset = "luxury".parse().expect("parse");
+        assert!(matches!(p3, Preset::StackedMonogram));
+
+        let p4: Preset = "STACKED-MONOGRAM".parse().expect("parse");
+        assert!(matches!(p4, Preset::StackedMonogram));
+    }
+
     #[test]
     fn preset_from_str_unknown() {
         let r: Result<Preset, _> = "no-such-preset".parse();
@@ -355,6 +1069,286 @@ mod tests {
         assert!(svg.contains("</svg>"));
     }
 
+    #[test]
+    fn generate_svg_identicon() {
+        let opts = RenderOptions::default();
+        let svg = LoGen::generate_svg("Test", Preset::Identicon, &opts).expect("svg gen");
+        assert!(svg.contains("<svg"));
+        assert!(svg.contains("</svg>"));
+    }
+
+    #[test]
+    fn generate_svg_glyph_grid_pattern() {
+        let opts = RenderOptions::default();
+        let svg = LoGen::generate_svg("Test", Preset::GlyphGridPattern, &opts).expect("svg gen");
+        assert!(svg.contains("<svg"));
+        assert!(svg.contains("</svg>"));
+    }
+
+    #[test]
+    fn generate_png_glyph_grid_pattern() {
+        let opts = RenderOptions::default();
+        let png = LoGen::generate_png("Test", Preset::GlyphGridPattern, &opts).expect("png gen");
+        assert_eq!(&png[1..4], b"PNG");
+    }
+
+    #[test]
+    fn generate_svg_ribbon_mark() {
+        let opts = RenderOptions::default();
+        let svg = LoGen::generate_svg("Test", Preset::RibbonMark, &opts).expect("svg gen");
+        assert!(svg.contains("<svg"));
+        assert!(svg.contains("</svg>"));
+    }
+
+    #[test]
+    fn generate_png_ribbon_mark() {
+        let opts = RenderOptions::default();
+        let png = LoGen::generate_png("Test", Preset::RibbonMark, &opts).expect("png gen");
+        assert_eq!(&png[1..4], b"PNG");
+    }
+
+    #[test]
+    fn generate_svg_emblem() {
+        let opts = RenderOptions::default();
+        let svg = LoGen::generate_svg("Test", Preset::Emblem, &opts).expect("svg gen");
+        assert!(svg.contains("<svg"));
+        assert!(svg.contains("</svg>"));
+    }
+
+    #[test]
+    fn generate_png_emblem() {
+        let opts = RenderOptions::default();
+        let png = LoGen::generate_png("Test", Preset::Emblem, &opts).expect("png gen");
+        assert_eq!(&png[1..4], b"PNG");
+    }
+
+    #[test]
+    fn generate_svg_wordmark() {
+        let opts = RenderOptions::default();
+        let svg = LoGen::generate_svg("Test", Preset::Wordmark, &opts).expect("svg gen");
+        assert!(svg.contains("<svg"));
+        assert!(svg.contains("</svg>"));
+    }
+
+    #[test]
+    fn generate_png_wordmark() {
+        let opts = RenderOptions::default();
+        let png = LoGen::generate_png("Test", Preset::Wordmark, &opts).expect("png gen");
+        assert_eq!(&png[1..4], b"PNG");
+    }
+
+    #[test]
+    fn generate_svg_low_poly() {
+        let opts = RenderOptions::default();
+        let svg = LoGen::generate_svg("Test", Preset::LowPoly, &opts).expect("svg gen");
+        assert!(svg.contains("<svg"));
+        assert!(svg.contains("</svg>"));
+    }
+
+    #[test]
+    fn generate_png_low_poly() {
+        let opts = RenderOptions::default();
+        let png = LoGen::generate_png("Test", Preset::LowPoly, &opts).expect("png gen");
+        assert_eq!(&png[1..4], b"PNG");
+    }
+
+    #[test]
+    fn generate_svg_truchet() {
+        let opts = RenderOptions::default();
+        let svg = LoGen::generate_svg("Test", Preset::Truchet, &opts).expect("svg gen");
+        assert!(svg.contains("<svg"));
+        assert!(svg.contains("</svg>"));
+    }
+
+    #[test]
+    fn generate_png_truchet() {
+        let opts = RenderOptions::default();
+        let png = LoGen::generate_png("Test", Preset::Truchet, &opts).expect("png gen");
+        assert_eq!(&png[1..4], b"PNG");
+    }
+
+    #[test]
+    fn generate_svg_pixel_avatar() {
+        let opts = RenderOptions::default();
+        let svg = LoGen::generate_svg("Test", Preset::PixelAvatar, &opts).expect("svg gen");
+        assert!(svg.contains("<svg"));
+        assert!(svg.contains("</svg>"));
+    }
+
+    #[test]
+    fn generate_png_pixel_avatar() {
+        let opts = RenderOptions::default();
+        let png = LoGen::generate_png("Test", Preset::PixelAvatar, &opts).expect("png gen");
+        assert_eq!(&png[1..4], b"PNG");
+    }
+
+    #[test]
+    fn generate_svg_sunburst() {
+        let opts = RenderOptions::default();
+        let svg = LoGen::generate_svg("Test", Preset::Sunburst, &opts).expect("svg gen");
+        assert!(svg.contains("<svg"));
+        assert!(svg.contains("</svg>"));
+    }
+
+    #[test]
+    fn generate_png_sunburst() {
+        let opts = RenderOptions::default();
+        let png = LoGen::generate_png("Test", Preset::Sunburst, &opts).expect("png gen");
+        assert_eq!(&png[1..4], b"PNG");
+    }
+
+    #[test]
+    fn generate_svg_isometric() {
+        let opts = RenderOptions::default();
+        let svg = LoGen::generate_svg("Test", Preset::Isometric, &opts).expect("svg gen");
+        assert!(svg.contains("<svg"));
+        assert!(svg.contains("</svg>"));
+    }
+
+    #[test]
+    fn generate_png_isometric() {
+        let opts = RenderOptions::default();
+        let png = LoGen::generate_png("Test", Preset::Isometric, &opts).expect("png gen");
+        assert_eq!(&png[1..4], b"PNG");
+    }
+
+    #[test]
+    fn generate_svg_blob() {
+        let opts = RenderOptions::default();
+        let svg = LoGen::generate_svg("Test", Preset::Blob, &opts).expect("svg gen");
+        assert!(svg.contains("<svg"));
+        assert!(svg.contains("</svg>"));
+    }
+
+    #[test]
+    fn generate_png_blob() {
+        let opts = RenderOptions::default();
+        let png = LoGen::generate_png("Test", Preset::Blob, &opts).expect("png gen");
+        assert_eq!(&png[1..4], b"PNG");
+    }
+
+    #[test]
+    fn generate_svg_voronoi() {
+        let opts = RenderOptions::default();
+        let svg = LoGen::generate_svg("Test", Preset::VoronoiMosaic, &opts).expect("svg gen");
+        assert!(svg.contains("<svg"));
+        assert!(svg.contains("</svg>"));
+    }
+
+    #[test]
+    fn generate_png_voronoi() {
+        let opts = RenderOptions::default();
+        let png = LoGen::generate_png("Test", Preset::VoronoiMosaic, &opts).expect("png gen");
+        assert_eq!(&png[1..4], b"PNG");
+    }
+
+    #[test]
+    fn generate_svg_negative_space() {
+        let opts = RenderOptions::default();
+        let svg = LoGen::generate_svg("Test", Preset::NegativeSpace, &opts).expect("svg gen");
+        assert!(svg.contains("<svg"));
+        assert!(svg.contains("</svg>"));
+    }
+
+    #[test]
+    fn generate_png_negative_space() {
+        let opts = RenderOptions::default();
+        let png = LoGen::generate_png("Test", Preset::NegativeSpace, &opts).expect("png gen");
+        assert_eq!(&png[1..4], b"PNG");
+    }
+
+    #[test]
+    fn generate_svg_topographic() {
+        let opts = RenderOptions::default();
+        let svg = LoGen::generate_svg("Test", Preset::Topographic, &opts).expect("svg gen");
+        assert!(svg.contains("<svg"));
+        assert!(svg.contains("</svg>"));
+    }
+
+    #[test]
+    fn generate_png_topographic() {
+        let opts = RenderOptions::default();
+        let png = LoGen::generate_png("Test", Preset::Topographic, &opts).expect("png gen");
+        assert_eq!(&png[1..4], b"PNG");
+    }
+
+    #[test]
+    fn generate_svg_halftone() {
+        let opts = RenderOptions::default();
+        let svg = LoGen::generate_svg("Test", Preset::Halftone, &opts).expect("svg gen");
+        assert!(svg.contains("<svg"));
+        assert!(svg.contains("</svg>"));
+    }
+
+    #[test]
+    fn generate_png_halftone() {
+        let opts = RenderOptions::default();
+        let png = LoGen::generate_png("Test", Preset::Halftone, &opts).expect("png gen");
+        assert_eq!(&png[1..4], b"PNG");
+    }
+
+    #[test]
+    fn generate_svg_interlocking_rings() {
+        let opts = RenderOptions::default();
+        let svg = LoGen::generate_svg("Test", Preset::InterlockingRings, &opts).expect("svg gen");
+        assert!(svg.contains("<svg"));
+        assert!(svg.contains("</svg>"));
+    }
+
+    #[test]
+    fn generate_png_interlocking_rings() {
+        let opts = RenderOptions::default();
+        let png = LoGen::generate_png("Test", Preset::InterlockingRings, &opts).expect("png gen");
+        assert_eq!(&png[1..4], b"PNG");
+    }
+
+    #[test]
+    fn generate_svg_circuit() {
+        let opts = RenderOptions::default();
+        let svg = LoGen::generate_svg("Dev Tools", Preset::CircuitTraces, &opts).expect("svg gen");
+        assert!(svg.contains("<svg"));
+        assert!(svg.contains("</svg>"));
+    }
+
+    #[test]
+    fn generate_png_circuit() {
+        let opts = RenderOptions::default();
+        let png = LoGen::generate_png("Dev Tools", Preset::CircuitTraces, &opts).expect("png gen");
+        assert_eq!(&png[1..4], b"PNG");
+    }
+
+    #[test]
+    fn generate_svg_plant() {
+        let opts = RenderOptions::default();
+        let svg = LoGen::generate_svg("Green Leaf", Preset::Plant, &opts).expect("svg gen");
+        assert!(svg.contains("<svg"));
+        assert!(svg.contains("</svg>"));
+    }
+
+    #[test]
+    fn generate_png_plant() {
+        let opts = RenderOptions::default();
+        let png = LoGen::generate_png("Green Leaf", Preset::Plant, &opts).expect("png gen");
+        assert_eq!(&png[1..4], b"PNG");
+    }
+
+    #[test]
+    fn generate_svg_stacked_monogram() {
+        let opts = RenderOptions::default();
+        let svg =
+            LoGen::generate_svg("Acme Widgets", Preset::StackedMonogram, &opts).expect("svg gen");
+        assert!(svg.contains("<svg"));
+        assert!(svg.contains("</svg>"));
+    }
+
+    #[test]
+    fn generate_png_stacked_monogram() {
+        let opts = RenderOptions::default();
+        let png =
+            LoGen::generate_png("Acme Widgets", Preset::StackedMonogram, &opts).expect("png gen");
+        assert_eq!(&png[1..4], b"PNG");
+    }
+
     #[test]
     fn generate_svg_with_variant() {
         let opts = RenderOptions {
@@ -375,6 +1369,113 @@ mod tests {
         assert!(svg.contains("<svg"));
     }
 
+    #[test]
+    fn generate_svg_cvd_safe_is_deterministic() {
+        let opts = RenderOptions {
+            cvd_safe: true,
+            ..Default::default()
+        };
+        let a = LoGen::generate_svg("Test", Preset::GeometricPattern, &opts).expect("svg gen");
+        let b = LoGen::generate_svg("Test", Preset::GeometricPattern, &opts).expect("svg gen");
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn generate_monochrome_uses_single_ink() {
+        let ink = Rgb {
+            r: 0x11,
+            g: 0x22,
+            b: 0x33,
+        };
+        let opts = RenderOptions {
+            monochrome: Some(ink),
+            ..Default::default()
+        };
+        for preset in Preset::all() {
+            let svg = LoGen::generate_svg("Acme Power", preset, &opts).expect("svg gen");
+            for fill in svg.split("fill=\"").skip(1) {
+                let color = &fill[..fill.find('"').unwrap()];
+                assert!(
+                    ["#112233", "none", "#FFFFFF", "#000000"].contains(&color),
+                    "unexpected fill {color}"
+                );
+            }
+            let png = LoGen::generate_png("Acme Power", preset, &opts).expect("png gen");
+            assert_eq!(&png[1..4], b"PNG");
+        }
+    }
+
+    #[test]
+    fn mask_clips_every_preset() {
+        for (preset, mask) in Preset::all().into_iter().zip(MaskShape::ALL.iter().cycle()) {
+            let opts = RenderOptions {
+                mask: Some(*mask),
+                ..Default::default()
+            };
+            let scene = algorithms::build_scene("Acme Power", preset, &opts).expect("scene");
+            let clipped = match mask {
+                MaskShape::AdaptiveIcon => matches!(
+                    scene.ops[..],
+                    [
+                        algorithms::DrawOp::Background { .. },
+                        algorithms::DrawOp::Group { clip: Some(_), .. }
+                    ]
+                ),
+                _ => matches!(
+                    scene.ops[..],
+                    [algorithms::DrawOp::Group { clip: Some(_), .. }]
+                ),
+            };
+            assert!(clipped, "{}", preset.id());
+            let svg = LoGen::generate_svg("Acme Power", preset, &opts).expect("svg gen");
+            assert!(svg.contains("<clipPath"));
+        }
+    }
+
+    #[test]
+    fn palette_matches_rendered_colors() {
+        let opts = RenderOptions::default();
+        let palette = LoGen::palette("Test", Preset::MonogramBadge, &opts).expect("palette");
+        let svg = LoGen::generate_svg("Test", Preset::MonogramBadge, &opts).expect("svg gen");
+        assert!(svg.contains(&palette.primary.to_hex()));
+    }
+
+    #[test]
+    fn export_palette_css() {
+        let opts = RenderOptions::default();
+        let css = LoGen::export_palette(
+            "Acme Power",
+            Preset::MonogramBadge,
+            &opts,
+            PaletteFormat::Css,
+        )
+        .expect("export");
+        let css = String::from_utf8(css).expect("utf8");
+        assert!(css.contains("--acme-power-primary: #"));
+    }
+
+    #[test]
+    fn export_palette_monochrome_reports_the_ink() {
+        let opts = RenderOptions {
+            monochrome: Some(Rgb {
+                r: 0x11,
+                g: 0x22,
+                b: 0x33,
+            }),
+            ..Default::default()
+        };
+        let css = LoGen::export_palette(
+            "Acme Power",
+            Preset::MonogramBadge,
+            &opts,
+            PaletteFormat::Css,
+        )
+        .expect("export");
+        let css = String::from_utf8(css).expect("utf8");
+        let colors: Vec<&str> = css.lines().filter_map(|l| l.split(": ").nth(1)).collect();
+        assert_eq!(colors, ["#112233;"; 4]);
+    }
+
     #[test]
     fn generate_png_monogram_badge() {
         let opts = RenderOptions::default();
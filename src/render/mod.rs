@@ -1,4 +1,5 @@
 //! Rendering backends for SVG and PNG output formats.
 
 pub mod png;
+mod raster;
 pub mod svg;
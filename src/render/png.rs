@@ -1,20 +1,16 @@
+use super::raster::{for_each_fill_coverage, for_each_stroke_coverage};
 use crate::algorithms::{DrawOp, Scene};
-use crate::core::geometry::Shape;
+use crate::core::geometry::{self, Shape};
+use crate::core::glyphs;
+use crate::core::palette::Rgb;
+use crate::core::typography;
 use crate::{LoGenError, RenderOptions};
-use ab_glyph::{Font, FontRef, PxScale, ScaleFont};
+use ab_glyph::{point, Font, FontRef, OutlineCurve, PxScale, ScaleFont};
 use image::{ImageEncoder, Rgba, RgbaImage};
 use imageproc::drawing::{
-    draw_filled_circle_mut, draw_filled_rect_mut, draw_hollow_circle_mut, draw_hollow_rect_mut,
-    draw_text_mut,
+    draw_filled_circle_mut, draw_filled_rect_mut, draw_hollow_rect_mut, draw_text_mut,
 };
 use imageproc::rect::Rect as IRect;
-use std::path::Path;
-
-// Optional embedded font bytes controlled by the `embed-font` Cargo feature.
-// In CI or environments where the font file is not present we fall back to
-// `None` so tests/builds don't fail. If you need embedding, place the font
-// at `assets/fonts/LiberationSans-Bold.ttf` and enable the feature.
-const EMBED_FONT_BYTES: Option<&[u8]> = None;
 
 pub fn render_png(
     scene: &Scene,
@@ -28,35 +24,47 @@ pub fn render_png(
     }
 
     // Determine font to use: prefer `font_bytes` passed by caller (already
-    // leaked to 'static), otherwise attempt to load a runtime font from
-    // `assets/fonts/`, falling back to the embedded bytes if available.
-    let font = if let Some(bytes) = font_bytes {
-        FontRef::try_from_slice(bytes).ok()
-    } else {
-        let runtime_path =
-            Path::new(env!("CARGO_MANIFEST_DIR")).join("assets/fonts/LiberationSans-Bold.ttf");
-        if let Ok(bytes) = std::fs::read(&runtime_path) {
-            // Leak the bytes so we can obtain a &'static slice for FontRef.
-            let boxed = bytes.into_boxed_slice();
-            let leaked: &'static [u8] = Box::leak(boxed);
-            FontRef::try_from_slice(leaked)
-                .ok()
-                .or_else(|| EMBED_FONT_BYTES.and_then(|b| FontRef::try_from_slice(b).ok()))
-        } else {
-            EMBED_FONT_BYTES.and_then(|b| FontRef::try_from_slice(b).ok())
-        }
+    // leaked to 'static), otherwise the runtime font from `assets/fonts/`
+    // or the embedded bytes if available.
+    let font = match font_bytes {
+        Some(bytes) => FontRef::try_from_slice(bytes).ok(),
+        None => glyphs::runtime_font().cloned(),
     };
 
-    for op in &scene.ops {
+    draw_ops(&mut img, &scene.ops, font.as_ref());
+
+    let mut buf = Vec::new();
+    {
+        let encoder = image::codecs::png::PngEncoder::new(&mut buf);
+        encoder
+            .write_image(
+                &img,
+                scene.width,
+                scene.height,
+                image::ExtendedColorType::Rgba8,
+            )
+            .map_err(|e: image::ImageError| LoGenError::Render(e.to_string()))?;
+    }
+    Ok(buf)
+}
+
+/// Filled circles with a smaller radius in pixels are antialiased; larger
+/// ones keep the integer rasterizer.
+const SMALL_CIRCLE_PX: f32 = 8.0;
+
+/// Draw `ops` in order onto `img`, which acts as the current layer.
+fn draw_ops(img: &mut RgbaImage, ops: &[DrawOp], font: Option<&FontRef>) {
+    let (width, height) = img.dimensions();
+    for op in ops {
         match op {
             DrawOp::Background { color } => {
                 if let Some(c) = color {
                     fill_rect(
-                        &mut img,
+                        img,
                         0.0,
                         0.0,
-                        scene.width as f32,
-                        scene.height as f32,
+                        width as f32,
+                        height as f32,
                         Rgba([c.r, c.g, c.b, 255]),
                     );
                 }
@@ -64,9 +72,18 @@ pub fn render_png(
             DrawOp::ShapeFill { shape, color } => {
                 let rgba = Rgba([color.r, color.g, color.b, 255]);
                 match shape {
+                    // Integer centers and radii would snap small dots, such as
+                    // halftone screens, to a few sizes; their flattened
+                    // outline keeps the exact area and position.
+                    Shape::Circle(circ) if circ.r < SMALL_CIRCLE_PX => {
+                        let outline = Shape::Path(shape.to_path());
+                        for_each_fill_coverage(width, height, &outline, |x, y, c| {
+                            blend_over(img, x, y, *color, c);
+                        });
+                    }
                     Shape::Circle(circ) => {
                         draw_filled_circle_mut(
-                            &mut img,
+                            img,
                             (circ.cx as i32, circ.cy as i32),
                             circ.r as i32,
                             rgba,
@@ -74,58 +91,50 @@ pub fn render_png(
                     }
                     Shape::Rect { rect, rx, ry } => {
                         if *rx > 0.0 || *ry > 0.0 {
-                            draw_rounded_rect(&mut img, rect.x, rect.y, rect.w, rect.h, *rx, rgba);
+                            draw_rounded_rect(img, rect.x, rect.y, rect.w, rect.h, *rx, rgba);
                         } else if rect.w > 0.0 && rect.h > 0.0 {
                             draw_filled_rect_mut(
-                                &mut img,
+                                img,
                                 IRect::at(rect.x as i32, rect.y as i32)
                                     .of_size(rect.w as u32, rect.h as u32),
                                 rgba,
                             );
                         }
                     }
+                    Shape::Path(_) => {
+                        for_each_fill_coverage(width, height, shape, |x, y, c| {
+                            blend_over(img, x, y, *color, c);
+                        });
+                    }
                 }
             }
             DrawOp::ShapeStroke {
                 shape,
                 color,
-                width,
+                width: stroke,
             } => {
                 let rgba = Rgba([color.r, color.g, color.b, 255]);
                 match shape {
-                    Shape::Circle(circ) => {
-                        draw_hollow_circle_mut(
-                            &mut img,
-                            (circ.cx as i32, circ.cy as i32),
-                            circ.r as i32,
-                            rgba,
-                        );
-                        // For thicker strokes, draw multiple circles
-                        let thick = *width as i32;
-                        for i in 1..thick {
-                            draw_hollow_circle_mut(
-                                &mut img,
-                                (circ.cx as i32, circ.cy as i32),
-                                (circ.r as i32) + i,
+                    Shape::Rect { rect, rx, ry } if *rx <= 0.0 && *ry <= 0.0 => {
+                        let thick = *stroke as i32;
+                        for i in 0..thick {
+                            draw_hollow_rect_mut(
+                                img,
+                                IRect::at((rect.x as i32) - i, (rect.y as i32) - i).of_size(
+                                    (rect.w as u32) + (i as u32) * 2,
+                                    (rect.h as u32) + (i as u32) * 2,
+                                ),
                                 rgba,
                             );
                         }
                     }
-                    Shape::Rect { rect, rx, ry } => {
-                        // Simplified stroke for rectangles (no rounded corner stroking)
-                        if *rx == 0.0 && *ry == 0.0 {
-                            let thick = *width as i32;
-                            for i in 0..thick {
-                                draw_hollow_rect_mut(
-                                    &mut img,
-                                    IRect::at((rect.x as i32) - i, (rect.y as i32) - i).of_size(
-                                        (rect.w as u32) + (i as u32) * 2,
-                                        (rect.h as u32) + (i as u32) * 2,
-                                    ),
-                                    rgba,
-                                );
-                            }
-                        }
+                    // Circles and paths are stroked centered on the outline
+                    // like SVG strokes, so knockouts of the same stroke line
+                    // up with it.
+                    _ => {
+                        for_each_stroke_coverage(width, height, shape, *stroke, |x, y, c| {
+                            blend_over(img, x, y, *color, c);
+                        });
                     }
                 }
             }
@@ -137,46 +146,88 @@ pub fn render_png(
                 color,
                 anchor_middle,
                 ..
-            } => {
+            } if is_plain_text(op) => {
                 // If font failed to load, skip drawing text rather than erroring
-                if let Some(ref font) = font {
+                if let Some(font) = font {
                     let scale = PxScale::from(*font_size);
                     let rgba = Rgba([color.r, color.g, color.b, 255]);
-
-                    // Calculate text position with vertical centering to match SVG's dominant-baseline="middle"
-                    let scaled_font = font.as_scaled(scale);
-                    let ascent = scaled_font.ascent();
-                    let descent = scaled_font.descent();
-                    let text_height = ascent - descent;
-
-                    let (text_x, text_y) = if *anchor_middle {
-                        let text_width = measure_text_width(font, scale, text);
-                        (
-                            (x - text_width / 2.0) as i32,
-                            (y - text_height / 2.0 - descent) as i32,
-                        )
-                    } else {
-                        (*x as i32, (y - text_height / 2.0 - descent) as i32)
-                    };
-                    draw_text_mut(&mut img, rgba, text_x, text_y, scale, &font, text);
+                    let (text_x, text_y) = text_origin(font, scale, text, *x, *y, *anchor_middle);
+                    draw_text_mut(img, rgba, text_x, text_y, scale, font, text);
+                }
+            }
+            DrawOp::Text { color, .. } => {
+                if let Some(shape) = font.and_then(|font| text_outline(font, op)) {
+                    for_each_fill_coverage(width, height, &shape, |x, y, c| {
+                        blend_over(img, x, y, *color, c);
+                    });
+                }
+            }
+            DrawOp::Pixels { .. } => {
+                for_each_pixel_cell(img.dimensions(), op, |x, y, color| {
+                    img.put_pixel(x, y, Rgba([color.r, color.g, color.b, 255]));
+                });
+            }
+            DrawOp::Knockout { op } => knockout(img, op, font),
+            DrawOp::Group { clip, ops } => {
+                let layer = render_group(width, height, clip.as_ref(), ops, font);
+                for (x, y, px) in layer.enumerate_pixels() {
+                    if px[3] > 0 {
+                        let c = Rgb {
+                            r: px[0],
+                            g: px[1],
+                            b: px[2],
+                        };
+                        blend_over(img, x, y, c, px[3] as f32 / 255.0);
+                    }
                 }
             }
         }
     }
+}
 
-    let mut buf = Vec::new();
-    {
-        let encoder = image::codecs::png::PngEncoder::new(&mut buf);
-        encoder
-            .write_image(
-                &img,
-                scene.width,
-                scene.height,
-                image::ExtendedColorType::Rgba8,
-            )
-            .map_err(|e: image::ImageError| LoGenError::Render(e.to_string()))?;
+/// Render a group into its own transparent layer, with the clip applied.
+fn render_group(
+    width: u32,
+    height: u32,
+    clip: Option<&Shape>,
+    ops: &[DrawOp],
+    font: Option<&FontRef>,
+) -> RgbaImage {
+    let mut layer = RgbaImage::new(width, height);
+    draw_ops(&mut layer, ops, font);
+    if let Some(clip) = clip {
+        let mut mask = vec![0.0f32; (width * height) as usize];
+        for_each_fill_coverage(width, height, clip, |x, y, c| {
+            mask[(y * width + x) as usize] = c;
+        });
+        for (x, y, px) in layer.enumerate_pixels_mut() {
+            px[3] = (px[3] as f32 * mask[(y * width + x) as usize]).round() as u8;
+        }
     }
-    Ok(buf)
+    layer
+}
+
+/// Source-over composite of `color` with the given coverage, handling
+/// transparent destinations (straight alpha).
+fn blend_over(img: &mut RgbaImage, x: u32, y: u32, color: Rgb, coverage: f32) {
+    let a = coverage.clamp(0.0, 1.0);
+    if a <= 0.0 {
+        return;
+    }
+    let dst = img.get_pixel_mut(x, y);
+    let da = dst[3] as f32 / 255.0;
+    let out_a = a + da * (1.0 - a);
+    let mix = |s: u8, d: u8| -> u8 {
+        ((s as f32 * a + d as f32 * da * (1.0 - a)) / out_a)
+            .round()
+            .clamp(0.0, 255.0) as u8
+    };
+    *dst = Rgba([
+        mix(color.r, dst[0]),
+        mix(color.g, dst[1]),
+        mix(color.b, dst[2]),
+        (out_a * 255.0).round() as u8,
+    ]);
 }
 
 fn fill_rect(img: &mut RgbaImage, x: f32, y: f32, w: f32, h: f32, rgba: Rgba<u8>) {
@@ -278,13 +329,376 @@ fn blend_rgba(bg: Rgba<u8>, fg: Rgba<u8>, alpha: u8) -> Rgba<u8> {
     ])
 }
 
-fn measure_text_width(font: &FontRef, scale: PxScale, text: &str) -> f32 {
-    use ab_glyph::{Font, ScaleFont};
+/// Erase the coverage of `op` from the image by scaling down pixel alpha.
+fn knockout(img: &mut RgbaImage, op: &DrawOp, font: Option<&FontRef>) {
+    fn erase(img: &mut RgbaImage, x: u32, y: u32, coverage: f32) {
+        let pixel = img.get_pixel_mut(x, y);
+        pixel[3] = (pixel[3] as f32 * (1.0 - coverage.clamp(0.0, 1.0))).round() as u8;
+    }
+
+    let (width, height) = img.dimensions();
+    match op {
+        DrawOp::Background { .. } => {
+            for px in img.pixels_mut() {
+                px[3] = 0;
+            }
+        }
+        DrawOp::ShapeFill { shape, .. } => {
+            for_each_fill_coverage(width, height, shape, |x, y, c| erase(img, x, y, c));
+        }
+        DrawOp::ShapeStroke {
+            shape, width: w, ..
+        } => {
+            for_each_stroke_coverage(width, height, shape, *w, |x, y, c| erase(img, x, y, c));
+        }
+        DrawOp::Text {
+            text,
+            x,
+            y,
+            font_size,
+            anchor_middle,
+            ..
+        } if is_plain_text(op) => {
+            if let Some(font) = font {
+                let scale = PxScale::from(*font_size);
+                let origin = text_origin(font, scale, text, *x, *y, *anchor_middle);
+                for_each_text_coverage(width, height, font, scale, text, origin, |x, y, c| {
+                    erase(img, x, y, c)
+                });
+            }
+        }
+        DrawOp::Text { .. } => {
+            if let Some(shape) = font.and_then(|font| text_outline(font, op)) {
+                for_each_fill_coverage(width, height, &shape, |x, y, c| erase(img, x, y, c));
+            }
+        }
+        DrawOp::Pixels { .. } => {
+            for_each_pixel_cell((width, height), op, |x, y, _| erase(img, x, y, 1.0));
+        }
+        DrawOp::Knockout { .. } => {}
+        DrawOp::Group { clip, ops } => {
+            let layer = render_group(width, height, clip.as_ref(), ops, font);
+            for (x, y, px) in layer.enumerate_pixels() {
+                if px[3] > 0 {
+                    erase(img, x, y, px[3] as f32 / 255.0);
+                }
+            }
+        }
+    }
+}
+
+/// Call `f(x, y, color)` for every canvas pixel whose center falls in a
+/// filled cell of a `DrawOp::Pixels` grid (nearest-neighbour sampling, so
+/// cell edges stay crisp at any scale).
+fn for_each_pixel_cell(size: (u32, u32), op: &DrawOp, mut f: impl FnMut(u32, u32, Rgb)) {
+    let DrawOp::Pixels {
+        x,
+        y,
+        cell,
+        cols,
+        cells,
+    } = op
+    else {
+        return;
+    };
+    if *cols == 0 || *cell <= 0.0 {
+        return;
+    }
+    let rows = cells.len() / cols;
+    let x0 = x.max(0.0).floor() as u32;
+    let y0 = y.max(0.0).floor() as u32;
+    let x1 = ((x + *cols as f32 * cell).ceil().max(0.0) as u32).min(size.0);
+    let y1 = ((y + rows as f32 * cell).ceil().max(0.0) as u32).min(size.1);
+    for py in y0..y1 {
+        let row = ((py as f32 + 0.5 - y) / cell).floor();
+        if row < 0.0 || row >= rows as f32 {
+            continue;
+        }
+        for px in x0..x1 {
+            let col = ((px as f32 + 0.5 - x) / cell).floor();
+            if col < 0.0 || col >= *cols as f32 {
+                continue;
+            }
+            if let Some(color) = cells[row as usize * cols + col as usize] {
+                f(px, py, color);
+            }
+        }
+    }
+}
+
+/// Whether `op` is text on a straight line without letter spacing or a
+/// set length. Such text is drawn by `draw_text_mut`; the rest goes through
+/// glyph outlines.
+fn is_plain_text(op: &DrawOp) -> bool {
+    matches!(
+        op,
+        DrawOp::Text {
+            path: None,
+            letter_spacing,
+            text_length: None,
+            ..
+        } if *letter_spacing == 0.0
+    )
+}
+
+/// Top-left origin for `draw_text_mut`, with vertical centering to match
+/// SVG's dominant-baseline="middle".
+fn text_origin(
+    font: &FontRef,
+    scale: PxScale,
+    text: &str,
+    x: f32,
+    y: f32,
+    anchor_middle: bool,
+) -> (i32, i32) {
+    let scaled_font = font.as_scaled(scale);
+    let ascent = scaled_font.ascent();
+    let descent = scaled_font.descent();
+    let text_height = ascent - descent;
+
+    if anchor_middle {
+        let text_width = measure_text_width(font, scale, text, 0.0);
+        (
+            (x - text_width / 2.0) as i32,
+            (y - text_height / 2.0 - descent) as i32,
+        )
+    } else {
+        (x as i32, (y - text_height / 2.0 - descent) as i32)
+    }
+}
+
+/// Glyph coverage for `text` laid out the same way as `draw_text_mut`.
+fn for_each_text_coverage(
+    width: u32,
+    height: u32,
+    font: &FontRef,
+    scale: PxScale,
+    text: &str,
+    origin: (i32, i32),
+    mut f: impl FnMut(u32, u32, f32),
+) {
+    let scaled_font = font.as_scaled(scale);
+    let mut caret = 0.0;
+    for ch in text.chars() {
+        let glyph_id = scaled_font.glyph_id(ch);
+        let glyph = glyph_id.with_scale_and_position(scale, point(caret, scaled_font.ascent()));
+        caret += scaled_font.h_advance(glyph_id);
+        if let Some(outlined) = font.outline_glyph(glyph) {
+            let bb = outlined.px_bounds();
+            outlined.draw(|gx, gy, c| {
+                let px = gx as i32 + origin.0 + bb.min.x.round() as i32;
+                let py = gy as i32 + origin.1 + bb.min.y.round() as i32;
+                if px >= 0 && py >= 0 && (px as u32) < width && (py as u32) < height {
+                    f(px as u32, py as u32, c);
+                }
+            });
+        }
+    }
+}
+
+/// Glyph outlines of a text op in canvas coordinates.
+///
+/// Glyphs are vertically centered the way SVG's `dominant-baseline="middle"`
+/// does it, with the baseline half an x-height below the text line. Text
+/// with a `path` is placed glyph by glyph along it, each glyph rotated to
+/// the path direction at its center.
+fn text_outline(font: &FontRef, op: &DrawOp) -> Option<Shape> {
+    let DrawOp::Text {
+        text,
+        x,
+        y,
+        font_size,
+        anchor_middle,
+        letter_spacing,
+        text_length,
+        path,
+        ..
+    } = op
+    else {
+        return None;
+    };
+
+    let scale = PxScale::from(*font_size);
     let scaled_font = font.as_scaled(scale);
-    let mut width = 0.0;
+    let (sx, sy) = (scaled_font.h_scale_factor(), scaled_font.v_scale_factor());
+    let baseline_shift = x_height(font, scale) / 2.0;
+
+    let length = path.as_ref().map_or(0.0, |p| p.length());
+    // A set length spreads the difference to the font's own width evenly
+    // between the glyphs.
+    let (letter_spacing, text_width) = match text_length {
+        Some(text_length) => {
+            let gaps = text.chars().count().saturating_sub(1).max(1);
+            let natural = measure_text_width(font, scale, text, 0.0);
+            ((text_length - natural) / gaps as f32, *text_length)
+        }
+        None => (
+            *letter_spacing,
+            measure_text_width(font, scale, text, *letter_spacing),
+        ),
+    };
+    let mut caret = match (path, anchor_middle) {
+        (Some(_), true) => (length - text_width) / 2.0,
+        (Some(_), false) => 0.0,
+        (None, true) => -text_width / 2.0,
+        (None, false) => 0.0,
+    };
+
+    let mut outline_path = geometry::Path::new();
     for ch in text.chars() {
         let glyph_id = font.glyph_id(ch);
-        width += scaled_font.h_advance(glyph_id);
+        let advance = scaled_font.h_advance(glyph_id);
+        let center = caret + advance / 2.0;
+        caret += advance + letter_spacing;
+        let Some(outline) = font.outline(glyph_id) else {
+            continue;
+        };
+        let (anchor, angle) = match path {
+            Some(path) => match path.point_at_length(center) {
+                Some(located) => located,
+                None => continue,
+            },
+            None => (geometry::Point::new(x + center, *y), 0.0),
+        };
+        let (sin, cos) = angle.sin_cos();
+        // Font units (y up, origin at the glyph's baseline start) to canvas.
+        let place = |p: ab_glyph::Point| {
+            let lx = p.x * sx - advance / 2.0;
+            let ly = baseline_shift - p.y * sy;
+            geometry::Point::new(
+                anchor.x + lx * cos - ly * sin,
+                anchor.y + lx * sin + ly * cos,
+            )
+        };
+
+        glyphs::append_outline(&mut outline_path, &outline, place);
+    }
+    Some(Shape::Path(outline_path))
+}
+
+/// Height of the font's lowercase `x`, falling back to the typical ratio.
+fn x_height(font: &FontRef, scale: PxScale) -> f32 {
+    let top = font.outline(font.glyph_id('x')).map(|outline| {
+        outline
+            .curves
+            .iter()
+            .flat_map(|curve| match *curve {
+                OutlineCurve::Line(a, b) => vec![a.y, b.y],
+                OutlineCurve::Quad(a, b, c) => vec![a.y, b.y, c.y],
+                OutlineCurve::Cubic(a, b, c, d) => vec![a.y, b.y, c.y, d.y],
+            })
+            .fold(0.0f32, f32::max)
+    });
+    match top {
+        Some(top) if top > 0.0 => top * font.as_scaled(scale).v_scale_factor(),
+        _ => typography::X_HEIGHT * scale.y,
+    }
+}
+
+/// Advance width of `text`, with `letter_spacing` after every glyph as SVG
+/// lays it out.
+fn measure_text_width(font: &FontRef, scale: PxScale, text: &str, letter_spacing: f32) -> f32 {
+    let scaled_font = font.as_scaled(scale);
+    text.chars()
+        .map(|ch| scaled_font.h_advance(font.glyph_id(ch)) + letter_spacing)
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::geometry::Circle;
+
+    #[test]
+    fn circle_strokes_are_centered_on_the_radius() {
+        let ink = Rgb { r: 0, g: 0, b: 0 };
+        let circle = Shape::Circle(Circle {
+            cx: 32.0,
+            cy: 32.0,
+            r: 20.0,
+        });
+        let stroke = DrawOp::ShapeStroke {
+            shape: circle.clone(),
+            color: ink,
+            width: 8.0,
+        };
+        let mut img = RgbaImage::new(64, 64);
+        draw_ops(&mut img, std::slice::from_ref(&stroke), None);
+        // Inked from r - 4 to r + 4 on either side of the center.
+        for (x, inked) in [
+            (6, false),
+            (9, true),
+            (14, true),
+            (18, false),
+            (46, false),
+            (49, true),
+            (54, true),
+            (58, false),
+        ] {
+            assert_eq!(img.get_pixel(x, 32)[3] > 127, inked, "x = {x}");
+        }
+
+        // A knockout of the same stroke erases all of it, up to the
+        // antialiased edge pixels it only partly covers.
+        draw_ops(
+            &mut img,
+            &[DrawOp::Knockout {
+                op: Box::new(stroke),
+            }],
+            None,
+        );
+        assert!(img.pixels().all(|px| px[3] <= 64));
+    }
+
+    #[cfg(feature = "embed-font")]
+    #[test]
+    fn wordmark_dot_follows_the_rendered_text() {
+        use crate::algorithms::build_scene;
+        use crate::{Preset, RenderOptions};
+
+        let font = crate::core::glyphs::runtime_font().expect("font");
+        let mut dots = 0;
+        for variant in 0..24 {
+            let opts = RenderOptions {
+                variant: Some(variant),
+                ..Default::default()
+            };
+            let scene = build_scene("Acme Power", Preset::Wordmark, &opts).unwrap();
+            let (
+                Some(text),
+                Some(DrawOp::ShapeFill {
+                    shape: Shape::Circle(dot),
+                    ..
+                }),
+            ) = (scene.ops.get(1), scene.ops.last())
+            else {
+                continue;
+            };
+            let DrawOp::Text {
+                text: chars,
+                font_size,
+                ..
+            } = text
+            else {
+                panic!("expected the text, got {:?}", text);
+            };
+            dots += 1;
+
+            // The ink of the last glyph ends its side bearing before the
+            // end of the text.
+            let last = font.glyph_id(chars.chars().last().unwrap());
+            let scaled = font.as_scaled(PxScale::from(*font_size));
+            let bearing = font.outline(last).map_or(0.0, |o| {
+                scaled.h_advance(last) - o.bounds.max.x * scaled.h_scale_factor()
+            });
+            let ink = text_outline(font, text).unwrap().bounds();
+            let text_end = ink.x + ink.w + bearing;
+            let gap = dot.cx - dot.r - text_end;
+            assert!(
+                gap >= -0.5 && gap <= 0.06 * font_size + 0.5,
+                "variant {variant}: {gap} px from the text"
+            );
+        }
+        assert!(dots > 0);
     }
-    width
 }
@@ -0,0 +1,253 @@
+//! Anti-aliased coverage computation shared by the PNG renderer.
+//!
+//! Circles and rounded rects are covered analytically through their signed
+//! distance field. Paths are filled with an accumulation rasterizer (non-zero
+//! winding) and stroked by distance to their flattened outline, which yields
+//! round caps and joins.
+
+use ab_glyph_rasterizer::{point as rpoint, Rasterizer};
+
+use crate::core::geometry::{segment_distance, Point, Shape};
+
+/// Integer pixel bounds `[x0, x1) x [y0, y1)` of `shape` grown by `grow`,
+/// clamped to the canvas. `None` if nothing is visible.
+fn pixel_bounds(width: u32, height: u32, shape: &Shape, grow: f32) -> Option<(u32, u32, u32, u32)> {
+    let b = shape.bounds();
+    let x0 = (b.x - grow).floor().max(0.0) as u32;
+    let y0 = (b.y - grow).floor().max(0.0) as u32;
+    let x1 = ((b.x + b.w + grow).ceil().max(0.0) as u32).min(width);
+    let y1 = ((b.y + b.h + grow).ceil().max(0.0) as u32).min(height);
+    if x0 >= x1 || y0 >= y1 {
+        None
+    } else {
+        Some((x0, y0, x1, y1))
+    }
+}
+
+/// Call `f(x, y, coverage)` for every canvas pixel covered by the fill of
+/// `shape`.
+pub(crate) fn for_each_fill_coverage(
+    width: u32,
+    height: u32,
+    shape: &Shape,
+    mut f: impl FnMut(u32, u32, f32),
+) {
+    let Some((x0, y0, x1, y1)) = pixel_bounds(width, height, shape, 1.0) else {
+        return;
+    };
+
+    if let Shape::Path(path) = shape {
+        let rw = (x1 - x0) as usize;
+        let rh = (y1 - y0) as usize;
+        // One spare column so edges clamped to the right border do not spill
+        // into the next row of the accumulation buffer.
+        let mut raster = Rasterizer::new(rw + 2, rh);
+        for (poly, _) in path.flatten() {
+            for i in 0..poly.len() {
+                let a = poly[i];
+                let b = poly[(i + 1) % poly.len()];
+                let a = Point::new(a.x - x0 as f32, a.y - y0 as f32);
+                let b = Point::new(b.x - x0 as f32, b.y - y0 as f32);
+                draw_clipped_line(&mut raster, a, b, rw as f32, rh as f32);
+            }
+        }
+        raster.for_each_pixel_2d(|x, y, c| {
+            if (x as usize) < rw && c > 0.0 {
+                f(x0 + x, y0 + y, c.min(1.0));
+            }
+        });
+        return;
+    }
+
+    for yy in y0..y1 {
+        for xx in x0..x1 {
+            let sd = shape.signed_distance(Point::new(xx as f32 + 0.5, yy as f32 + 0.5));
+            let coverage = (0.5 - sd).clamp(0.0, 1.0);
+            if coverage > 0.0 {
+                f(xx, yy, coverage);
+            }
+        }
+    }
+}
+
+/// Call `f(x, y, coverage)` for every canvas pixel covered by a stroke of
+/// `stroke_width` centered on the outline of `shape`.
+pub(crate) fn for_each_stroke_coverage(
+    width: u32,
+    height: u32,
+    shape: &Shape,
+    stroke_width: f32,
+    mut f: impl FnMut(u32, u32, f32),
+) {
+    let half = stroke_width / 2.0;
+    let Some((x0, y0, x1, y1)) = pixel_bounds(width, height, shape, half + 1.0) else {
+        return;
+    };
+
+    if let Shape::Path(path) = shape {
+        // Visit only the pixels near each segment and keep the best coverage.
+        let rw = (x1 - x0) as usize;
+        let mut coverage = vec![0.0f32; rw * (y1 - y0) as usize];
+        for (poly, closed) in path.flatten() {
+            let n = poly.len();
+            let edges = if closed { n } else { n - 1 };
+            for i in 0..edges {
+                let a = poly[i];
+                let b = poly[(i + 1) % n];
+                let sx0 = (a.x.min(b.x) - half - 1.0).floor().max(x0 as f32) as u32;
+                let sy0 = (a.y.min(b.y) - half - 1.0).floor().max(y0 as f32) as u32;
+                let sx1 = ((a.x.max(b.x) + half + 1.0).ceil().max(0.0) as u32).min(x1);
+                let sy1 = ((a.y.max(b.y) + half + 1.0).ceil().max(0.0) as u32).min(y1);
+                for yy in sy0..sy1 {
+                    for xx in sx0..sx1 {
+                        let d =
+                            segment_distance(Point::new(xx as f32 + 0.5, yy as f32 + 0.5), a, b);
+                        let c = (half + 0.5 - d).clamp(0.0, 1.0);
+                        let idx = (yy - y0) as usize * rw + (xx - x0) as usize;
+                        if c > coverage[idx] {
+                            coverage[idx] = c;
+                        }
+                    }
+                }
+            }
+        }
+        for (idx, c) in coverage.into_iter().enumerate() {
+            if c > 0.0 {
+                f(x0 + (idx % rw) as u32, y0 + (idx / rw) as u32, c);
+            }
+        }
+        return;
+    }
+
+    for yy in y0..y1 {
+        for xx in x0..x1 {
+            let sd = shape.signed_distance(Point::new(xx as f32 + 0.5, yy as f32 + 0.5));
+            let coverage = (half + 0.5 - sd.abs()).clamp(0.0, 1.0);
+            if coverage > 0.0 {
+                f(xx, yy, coverage);
+            }
+        }
+    }
+}
+
+/// Add the edge a->b to the rasterizer after clipping it vertically to
+/// `[0, h]` and clamping it horizontally to `[0, w + 1]`. Horizontal clamping
+/// keeps the winding contribution of off-canvas parts intact.
+fn draw_clipped_line(raster: &mut Rasterizer, a: Point, b: Point, w: f32, h: f32) {
+    let (mut a, mut b) = (a, b);
+    if (a.y < 0.0 && b.y < 0.0) || (a.y > h && b.y > h) || a.y == b.y {
+        return;
+    }
+    let at_y =
+        |p: Point, q: Point, y: f32| Point::new(p.x + (q.x - p.x) * (y - p.y) / (q.y - p.y), y);
+    if a.y < 0.0 {
+        a = at_y(a, b, 0.0);
+    } else if b.y < 0.0 {
+        b = at_y(a, b, 0.0);
+    }
+    if a.y > h {
+        a = at_y(a, b, h);
+    } else if b.y > h {
+        b = at_y(a, b, h);
+    }
+
+    // Split where the edge crosses the left/right borders so each piece can
+    // be clamped without bending the in-canvas part.
+    let right = w + 1.0;
+    let mut pts = vec![a];
+    let mut cuts: Vec<f32> = [0.0, right]
+        .iter()
+        .filter_map(|&x| {
+            let t = (x - a.x) / (b.x - a.x);
+            (t > 0.0 && t < 1.0).then_some(t)
+        })
+        .collect();
+    cuts.sort_by(|p, q| p.total_cmp(q));
+    for t in cuts {
+        pts.push(a.lerp(b, t));
+    }
+    pts.push(b);
+
+    for pair in pts.windows(2) {
+        let p = pair[0];
+        let q = pair[1];
+        raster.draw_line(
+            rpoint(p.x.clamp(0.0, right), p.y),
+            rpoint(q.x.clamp(0.0, right), q.y),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::geometry::Path;
+
+    fn total_coverage(width: u32, height: u32, shape: &Shape) -> f32 {
+        let mut sum = 0.0;
+        for_each_fill_coverage(width, height, shape, |_, _, c| sum += c);
+        sum
+    }
+
+    #[test]
+    fn square_path_covers_its_area() {
+        let square = Shape::Path(Path::polygon(&[
+            Point::new(10.0, 10.0),
+            Point::new(30.0, 10.0),
+            Point::new(30.0, 30.0),
+            Point::new(10.0, 30.0),
+        ]));
+        assert!((total_coverage(64, 64, &square) - 400.0).abs() < 0.5);
+    }
+
+    #[test]
+    fn small_circle_outline_covers_its_area() {
+        // What the PNG renderer fills halftone dots with.
+        for r in [0.4, 1.3, 2.7, 5.5] {
+            let circle = Shape::Circle(crate::core::geometry::Circle {
+                cx: 20.3,
+                cy: 17.8,
+                r,
+            });
+            let area = std::f32::consts::PI * r * r;
+            let sum = total_coverage(64, 64, &Shape::Path(circle.to_path()));
+            assert!((sum - area).abs() < 0.04 * area + 0.05, "r {r}: {sum}");
+        }
+    }
+
+    #[test]
+    fn off_canvas_path_is_clipped() {
+        // Half of this 40x40 square hangs off the left and top edges.
+        let square = Shape::Path(Path::polygon(&[
+            Point::new(-20.0, -20.0),
+            Point::new(20.0, -20.0),
+            Point::new(20.0, 20.0),
+            Point::new(-20.0, 20.0),
+        ]));
+        assert!((total_coverage(64, 64, &square) - 400.0).abs() < 0.5);
+
+        // ...and this one off the right and bottom edges.
+        let square = Shape::Path(Path::polygon(&[
+            Point::new(44.0, 44.0),
+            Point::new(84.0, 44.0),
+            Point::new(84.0, 84.0),
+            Point::new(44.0, 84.0),
+        ]));
+        assert!((total_coverage(64, 64, &square) - 400.0).abs() < 0.5);
+    }
+
+    #[test]
+    fn open_path_stroke_has_round_caps() {
+        let mut line = Path::new();
+        line.move_to(Point::new(20.0, 32.0));
+        line.line_to(Point::new(44.0, 32.0));
+        let mut sum = 0.0;
+        for_each_stroke_coverage(64, 64, &Shape::Path(line), 8.0, |_, _, c| sum += c);
+        // 24x8 body plus a full circle of radius 4 from the two caps.
+        let expected = 24.0 * 8.0 + std::f32::consts::PI * 16.0;
+        assert!(
+            (sum - expected).abs() < 3.0,
+            "got {sum}, expected {expected}"
+        );
+    }
+}
@@ -1,7 +1,20 @@
 use crate::algorithms::{DrawOp, Scene};
 use crate::core::geometry::Shape;
+use crate::core::palette::Rgb;
 use crate::{LoGenError, RenderOptions};
 
+/// Knockout masks paint kept coverage white and erased coverage black.
+const MASK_WHITE: Rgb = Rgb {
+    r: 255,
+    g: 255,
+    b: 255,
+};
+const MASK_BLACK: Rgb = Rgb { r: 0, g: 0, b: 0 };
+
+/// Runs of at least this many same-colored circles (halftone dots) are
+/// written as `<use>` references to one shared circle per radius.
+const DOT_RUN: usize = 16;
+
 fn esc(s: &str) -> String {
     s.replace('&', "&amp;")
         .replace('<', "&lt;")
@@ -10,6 +23,18 @@ fn esc(s: &str) -> String {
         .replace('\'', "&apos;")
 }
 
+/// Counters for ids of `<defs>` entries.
+#[derive(Default)]
+struct Defs {
+    out: String,
+    knockouts: usize,
+    clips: usize,
+    text_paths: usize,
+    /// Radii, as written, of the shared dot circles; `dot-{i + 1}` is the
+    /// circle of radius `dots[i]`.
+    dots: Vec<String>,
+}
+
 pub fn render_svg(scene: &Scene, _opts: &RenderOptions) -> Result<String, LoGenError> {
     let w = scene.width;
     let h = scene.height;
@@ -22,80 +47,499 @@ pub fn render_svg(scene: &Scene, _opts: &RenderOptions) -> Result<String, LoGenE
     ));
     out.push('\n');
 
-    for op in &scene.ops {
-        match op {
-            DrawOp::Background { color } => {
-                if let Some(c) = color {
-                    out.push_str(&format!(
-                        r#"<rect x="0" y="0" width="{w}" height="{h}" fill="{}"/>"#,
-                        c.to_hex()
-                    ));
-                    out.push('\n');
-                }
+    let mut defs = Defs::default();
+    let mut body = String::new();
+    push_ops(&mut body, &scene.ops, w, h, &mut defs);
+
+    if !defs.out.is_empty() {
+        out.push_str("<defs>\n");
+        out.push_str(&defs.out);
+        out.push_str("</defs>\n");
+    }
+    out.push_str(&body);
+    out.push_str("</svg>\n");
+    Ok(out)
+}
+
+/// `textLength` or else `letter-spacing` attributes (with their leading
+/// space), or nothing when there is no extra spacing.
+fn spacing_attr(letter_spacing: f32, text_length: Option<f32>) -> String {
+    match text_length {
+        Some(length) => format!(r#" textLength="{length:.2}" lengthAdjust="spacing""#),
+        None if letter_spacing == 0.0 => String::new(),
+        None => format!(r#" letter-spacing="{:.2}""#, letter_spacing),
+    }
+}
+
+/// Bare geometry element for use inside `<clipPath>`.
+fn clip_element(shape: &Shape) -> String {
+    match shape {
+        Shape::Circle(circ) => format!(
+            r#"<circle cx="{:.2}" cy="{:.2}" r="{:.2}"/>"#,
+            circ.cx, circ.cy, circ.r
+        ),
+        Shape::Rect { rect, rx, ry } => format!(
+            r#"<rect x="{:.2}" y="{:.2}" width="{:.2}" height="{:.2}" rx="{:.2}" ry="{:.2}"/>"#,
+            rect.x, rect.y, rect.w, rect.h, rx, ry
+        ),
+        Shape::Path(path) => format!(r#"<path d="{}"/>"#, path.to_svg_data()),
+    }
+}
+
+/// Cover the filled cells of a grid `cols` wide with same-colored
+/// rectangles `(col, row, w, h, color)`: each run of equal cells in a row is
+/// extended downwards for as long as the rows below repeat it.
+fn merge_cells(cols: usize, cells: &[Option<Rgb>]) -> Vec<(usize, usize, usize, usize, Rgb)> {
+    if cols == 0 {
+        return Vec::new();
+    }
+    let rows = cells.len() / cols;
+    let mut done = vec![false; cells.len()];
+    let mut rects = Vec::new();
+    for row in 0..rows {
+        for col in 0..cols {
+            let i = row * cols + col;
+            let Some(color) = cells[i] else {
+                continue;
+            };
+            if done[i] {
+                continue;
             }
-            DrawOp::ShapeFill { shape, color } => match shape {
-                Shape::Circle(circ) => {
-                    out.push_str(&format!(
-                        r#"<circle cx="{:.2}" cy="{:.2}" r="{:.2}" fill="{}"/>"#,
-                        circ.cx,
-                        circ.cy,
-                        circ.r,
-                        color.to_hex()
-                    ));
-                    out.push('\n');
-                }
-                Shape::Rect { rect, rx, ry } => {
-                    out.push_str(&format!(
-                        r#"<rect x="{:.2}" y="{:.2}" width="{:.2}" height="{:.2}" rx="{:.2}" ry="{:.2}" fill="{}"/>"#,
-                        rect.x, rect.y, rect.w, rect.h, rx, ry, color.to_hex()
-                    ));
-                    out.push('\n');
-                }
-            },
-            DrawOp::ShapeStroke {
-                shape,
-                color,
-                width,
-            } => match shape {
-                Shape::Circle(circ) => {
-                    out.push_str(&format!(
-                        r#"<circle cx="{:.2}" cy="{:.2}" r="{:.2}" stroke="{}" stroke-width="{:.2}" fill="none"/>"#,
-                        circ.cx,
-                        circ.cy,
-                        circ.r,
-                        color.to_hex(),
-                        width
-                    ));
-                    out.push('\n');
-                }
-                Shape::Rect { rect, rx, ry } => {
-                    out.push_str(&format!(
-                        r#"<rect x="{:.2}" y="{:.2}" width="{:.2}" height="{:.2}" rx="{:.2}" ry="{:.2}" stroke="{}" stroke-width="{:.2}" fill="none"/>"#,
-                        rect.x, rect.y, rect.w, rect.h, rx, ry, color.to_hex(), width
-                    ));
-                    out.push('\n');
+            let free = |j: usize, done: &[bool]| !done[j] && cells[j] == Some(color);
+            let mut w = 1;
+            while col + w < cols && free(i + w, &done) {
+                w += 1;
+            }
+            let mut h = 1;
+            while row + h < rows && (0..w).all(|k| free((row + h) * cols + col + k, &done)) {
+                h += 1;
+            }
+            for r in row..row + h {
+                for c in col..col + w {
+                    done[r * cols + c] = true;
                 }
-            },
-            DrawOp::Text {
-                text,
-                x,
-                y,
-                font_family,
-                font_weight,
-                font_size,
-                color,
-                anchor_middle,
-            } => {
-                let anchor = if *anchor_middle { "middle" } else { "start" };
+            }
+            rects.push((col, row, w, h, color));
+        }
+    }
+    rects
+}
+
+/// Append `ops`, one layer, to `out`.
+///
+/// A knockout erases everything drawn before it in its layer. Rather than
+/// nesting a mask per knockout, the ops up to the last knockout are drawn
+/// once under a single mask built as the ops go: it starts white, and from
+/// the first knockout on each op paints its coverage white and each
+/// knockout black, so a pixel shows exactly when the last op covering it is
+/// not a knockout. Ops after the last knockout are drawn unmasked.
+fn push_ops(out: &mut String, ops: &[DrawOp], w: u32, h: u32, defs: &mut Defs) {
+    let is_knockout = |op: &DrawOp| matches!(op, DrawOp::Knockout { .. });
+    let (Some(first), Some(last)) = (
+        ops.iter().position(is_knockout),
+        ops.iter().rposition(is_knockout),
+    ) else {
+        push_plain_ops(out, ops, w, h, defs);
+        return;
+    };
+
+    let ink: Vec<DrawOp> = ops[..last]
+        .iter()
+        .filter(|op| !is_knockout(op))
+        .cloned()
+        .collect();
+    let coverage: Vec<DrawOp> = ops[first..=last]
+        .iter()
+        .map(|op| match op {
+            DrawOp::Knockout { op } => op.recolored(MASK_BLACK),
+            op => op.recolored(MASK_WHITE),
+        })
+        .collect();
+
+    let mut body = String::new();
+    push_plain_ops(&mut body, &ink, w, h, defs);
+    let mut mask = format!(
+        "<rect x=\"0\" y=\"0\" width=\"{w}\" height=\"{h}\" fill=\"{}\"/>\n",
+        MASK_WHITE.to_hex()
+    );
+    push_plain_ops(&mut mask, &coverage, w, h, defs);
+
+    defs.knockouts += 1;
+    let id = format!("knockout-{}", defs.knockouts);
+    defs.out.push_str(&format!(
+        "<mask id=\"{id}\" maskUnits=\"userSpaceOnUse\" x=\"0\" y=\"0\" width=\"{w}\" height=\"{h}\">\n{mask}</mask>\n"
+    ));
+    out.push_str(&format!("<g mask=\"url(#{id})\">\n{body}</g>\n"));
+    push_ops(out, &ops[last + 1..], w, h, defs);
+}
+
+/// Append knockout-free `ops` to `out` in order, writing long runs of
+/// same-colored circles as `<use>`s of shared dots so halftones of
+/// thousands of dots stay compact.
+fn push_plain_ops(out: &mut String, ops: &[DrawOp], w: u32, h: u32, defs: &mut Defs) {
+    let mut rest = ops;
+    while let Some(first) = rest.first() {
+        let run = circle_run(rest);
+        if run >= DOT_RUN {
+            push_dots(out, &rest[..run], defs);
+            rest = &rest[run..];
+        } else {
+            push_op(out, first, w, h, defs);
+            rest = &rest[1..];
+        }
+    }
+}
+
+/// Number of leading circle fills in `ops` sharing the first one's color.
+fn circle_run(ops: &[DrawOp]) -> usize {
+    let color = match ops.first() {
+        Some(DrawOp::ShapeFill {
+            shape: Shape::Circle(_),
+            color,
+        }) => *color,
+        _ => return 0,
+    };
+    ops.iter()
+        .take_while(|op| {
+            matches!(op, DrawOp::ShapeFill { shape: Shape::Circle(_), color: c } if *c == color)
+        })
+        .count()
+}
+
+/// Write a run of same-colored circle fills as one group of `<use>`s.
+fn push_dots(out: &mut String, ops: &[DrawOp], defs: &mut Defs) {
+    let mut color = None;
+    let mut uses = String::new();
+    for op in ops {
+        let DrawOp::ShapeFill {
+            shape: Shape::Circle(circ),
+            color: c,
+        } = op
+        else {
+            continue;
+        };
+        color = Some(*c);
+        let r = format!("{:.2}", circ.r);
+        let id = match defs.dots.iter().position(|d| *d == r) {
+            Some(i) => i + 1,
+            None => {
+                defs.out.push_str(&format!(
+                    "<circle id=\"dot-{}\" r=\"{r}\"/>\n",
+                    defs.dots.len() + 1
+                ));
+                defs.dots.push(r);
+                defs.dots.len()
+            }
+        };
+        uses.push_str(&format!(
+            r##"<use href="#dot-{id}" x="{:.2}" y="{:.2}"/>"##,
+            circ.cx, circ.cy
+        ));
+        uses.push('\n');
+    }
+    if let Some(c) = color {
+        out.push_str(&format!("<g fill=\"{}\">\n{uses}</g>\n", c.to_hex()));
+    }
+}
+
+/// Append `op` to `out`, the body of the current layer.
+fn push_op(out: &mut String, op: &DrawOp, w: u32, h: u32, defs: &mut Defs) {
+    match op {
+        DrawOp::Background { color } => {
+            if let Some(c) = color {
+                out.push_str(&format!(
+                    r#"<rect x="0" y="0" width="{w}" height="{h}" fill="{}"/>"#,
+                    c.to_hex()
+                ));
+                out.push('\n');
+            }
+        }
+        DrawOp::ShapeFill { shape, color } => match shape {
+            Shape::Circle(circ) => {
+                out.push_str(&format!(
+                    r#"<circle cx="{:.2}" cy="{:.2}" r="{:.2}" fill="{}"/>"#,
+                    circ.cx,
+                    circ.cy,
+                    circ.r,
+                    color.to_hex()
+                ));
+                out.push('\n');
+            }
+            Shape::Rect { rect, rx, ry } => {
+                out.push_str(&format!(
+                    r#"<rect x="{:.2}" y="{:.2}" width="{:.2}" height="{:.2}" rx="{:.2}" ry="{:.2}" fill="{}"/>"#,
+                    rect.x, rect.y, rect.w, rect.h, rx, ry, color.to_hex()
+                ));
+                out.push('\n');
+            }
+            Shape::Path(path) => {
+                out.push_str(&format!(
+                    r#"<path d="{}" fill="{}"/>"#,
+                    path.to_svg_data(),
+                    color.to_hex()
+                ));
+                out.push('\n');
+            }
+        },
+        DrawOp::ShapeStroke {
+            shape,
+            color,
+            width,
+        } => match shape {
+            Shape::Circle(circ) => {
+                out.push_str(&format!(
+                    r#"<circle cx="{:.2}" cy="{:.2}" r="{:.2}" stroke="{}" stroke-width="{:.2}" fill="none"/>"#,
+                    circ.cx,
+                    circ.cy,
+                    circ.r,
+                    color.to_hex(),
+                    width
+                ));
+                out.push('\n');
+            }
+            Shape::Rect { rect, rx, ry } => {
+                out.push_str(&format!(
+                    r#"<rect x="{:.2}" y="{:.2}" width="{:.2}" height="{:.2}" rx="{:.2}" ry="{:.2}" stroke="{}" stroke-width="{:.2}" fill="none"/>"#,
+                    rect.x, rect.y, rect.w, rect.h, rx, ry, color.to_hex(), width
+                ));
+                out.push('\n');
+            }
+            Shape::Path(path) => {
+                // Round caps/joins match the distance-based stroking of the PNG renderer.
                 out.push_str(&format!(
-                    r#"<text x="{:.2}" y="{:.2}" text-anchor="{anchor}" dominant-baseline="middle" font-family="{}" font-weight="{}" font-size="{:.2}" fill="{}">{}</text>"#,
-                    x, y, esc(font_family), font_weight, font_size, color.to_hex(), esc(text)
+                    r#"<path d="{}" stroke="{}" stroke-width="{:.2}" stroke-linecap="round" stroke-linejoin="round" fill="none"/>"#,
+                    path.to_svg_data(),
+                    color.to_hex(),
+                    width
                 ));
                 out.push('\n');
             }
+        },
+        DrawOp::Text {
+            text,
+            font_family,
+            font_weight,
+            font_size,
+            color,
+            anchor_middle,
+            letter_spacing,
+            text_length,
+            path: Some(path),
+            ..
+        } => {
+            defs.text_paths += 1;
+            let id = format!("text-path-{}", defs.text_paths);
+            defs.out.push_str(&format!(
+                r#"<path id="{id}" d="{}" fill="none"/>"#,
+                path.to_svg_data()
+            ));
+            defs.out.push('\n');
+            let (anchor, offset) = if *anchor_middle {
+                ("middle", "50%")
+            } else {
+                ("start", "0%")
+            };
+            out.push_str(&format!(
+                r##"<text text-anchor="{anchor}" dominant-baseline="middle" font-family="{}" font-weight="{}" font-size="{:.2}"{} fill="{}"><textPath href="#{id}" startOffset="{offset}">{}</textPath></text>"##,
+                esc(font_family), font_weight, font_size, spacing_attr(*letter_spacing, *text_length), color.to_hex(), esc(text)
+            ));
+            out.push('\n');
+        }
+        DrawOp::Text {
+            text,
+            x,
+            y,
+            font_family,
+            font_weight,
+            font_size,
+            color,
+            anchor_middle,
+            letter_spacing,
+            text_length,
+            path: None,
+        } => {
+            let anchor = if *anchor_middle { "middle" } else { "start" };
+            out.push_str(&format!(
+                r#"<text x="{:.2}" y="{:.2}" text-anchor="{anchor}" dominant-baseline="middle" font-family="{}" font-weight="{}" font-size="{:.2}"{} fill="{}">{}</text>"#,
+                x, y, esc(font_family), font_weight, font_size, spacing_attr(*letter_spacing, *text_length), color.to_hex(), esc(text)
+            ));
+            out.push('\n');
         }
+        DrawOp::Pixels {
+            x,
+            y,
+            cell,
+            cols,
+            cells,
+        } => {
+            out.push_str("<g shape-rendering=\"crispEdges\">\n");
+            for (col, row, cw, ch, color) in merge_cells(*cols, cells) {
+                out.push_str(&format!(
+                    r#"<rect x="{:.2}" y="{:.2}" width="{:.2}" height="{:.2}" fill="{}"/>"#,
+                    x + col as f32 * cell,
+                    y + row as f32 * cell,
+                    cw as f32 * cell,
+                    ch as f32 * cell,
+                    color.to_hex()
+                ));
+                out.push('\n');
+            }
+            out.push_str("</g>\n");
+        }
+        // Knockouts are drawn into the mask of their layer by `push_ops`.
+        DrawOp::Knockout { .. } => {}
+        DrawOp::Group { clip, ops } => {
+            let mut layer = String::new();
+            push_ops(&mut layer, ops, w, h, defs);
+            match clip {
+                Some(shape) => {
+                    defs.clips += 1;
+                    let id = format!("clip-{}", defs.clips);
+                    defs.out.push_str(&format!(
+                        "<clipPath id=\"{id}\">\n{}\n</clipPath>\n",
+                        clip_element(shape)
+                    ));
+                    out.push_str(&format!("<g clip-path=\"url(#{id})\">\n{layer}</g>\n"));
+                }
+                None => out.push_str(&format!("<g>\n{layer}</g>\n")),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{merge_cells, render_svg, DOT_RUN};
+    use crate::algorithms::{DrawOp, Scene};
+    use crate::core::geometry::{Circle, Shape};
+    use crate::core::palette::{self, Rgb};
+    use crate::RenderOptions;
+
+    const A: Option<Rgb> = Some(Rgb { r: 1, g: 2, b: 3 });
+    const B: Option<Rgb> = Some(Rgb { r: 4, g: 5, b: 6 });
+
+    #[test]
+    fn merges_runs_and_repeated_rows() {
+        #[rustfmt::skip]
+        let cells = [
+            A, A, None,
+            A, A, B,
+            None, B, B,
+        ];
+        let rects: Vec<_> = merge_cells(3, &cells)
+            .into_iter()
+            .map(|(c, r, w, h, _)| (c, r, w, h))
+            .collect();
+        assert_eq!(rects, [(0, 0, 2, 2), (2, 1, 1, 2), (1, 2, 1, 1)]);
     }
 
-    out.push_str("</svg>\n");
-    Ok(out)
+    #[test]
+    fn covers_every_filled_cell_once() {
+        let cells: Vec<Option<Rgb>> = (0..64)
+            .map(|i| match i % 5 {
+                0 => None,
+                1 | 2 => A,
+                _ => B,
+            })
+            .collect();
+        let mut covered = vec![0; cells.len()];
+        for (c, r, w, h, color) in merge_cells(8, &cells) {
+            for row in r..r + h {
+                for col in c..c + w {
+                    assert_eq!(cells[row * 8 + col], Some(color));
+                    covered[row * 8 + col] += 1;
+                }
+            }
+        }
+        for (i, cell) in cells.iter().enumerate() {
+            assert_eq!(covered[i], cell.is_some() as i32, "cell {i}");
+        }
+    }
+
+    #[test]
+    fn long_circle_runs_share_dot_definitions() {
+        let dot = |i: usize, color: Rgb| DrawOp::ShapeFill {
+            shape: Shape::Circle(Circle {
+                cx: i as f32 * 10.0,
+                cy: 5.0,
+                r: (i % 3 + 1) as f32,
+            }),
+            color,
+        };
+        let (a, b) = (A.unwrap(), B.unwrap());
+        let mut ops: Vec<DrawOp> = (0..DOT_RUN + 4).map(|i| dot(i, a)).collect();
+        // A short run stays as plain circles.
+        ops.extend((0..3).map(|i| dot(i, b)));
+        let scene = Scene {
+            width: 400,
+            height: 20,
+            ops,
+            palette: palette::Palette {
+                background: None,
+                primary: a,
+                secondary: b,
+                tertiary: b,
+                text_color: a,
+            },
+        };
+        let svg = render_svg(&scene, &RenderOptions::default()).expect("svg");
+        assert_eq!(svg.matches("<use ").count(), DOT_RUN + 4);
+        assert_eq!(svg.matches("<circle id=\"dot-").count(), 3);
+        assert_eq!(svg.matches("<circle cx=").count(), 3);
+        assert_eq!(svg.matches("<g fill=\"#010203\">").count(), 1);
+    }
+
+    #[test]
+    fn knockouts_share_one_flat_mask_per_layer() {
+        let (a, b) = (A.unwrap(), B.unwrap());
+        let square = |x: f32| Shape::Rect {
+            rect: crate::core::geometry::Rect {
+                x,
+                y: 0.0,
+                w: 10.0,
+                h: 10.0,
+            },
+            rx: 0.0,
+            ry: 0.0,
+        };
+        let mut ops = Vec::new();
+        for i in 0..8 {
+            let shape = square(i as f32 * 5.0);
+            ops.push(DrawOp::Knockout {
+                op: Box::new(DrawOp::ShapeStroke {
+                    shape: shape.clone(),
+                    color: a,
+                    width: 2.0,
+                }),
+            });
+            ops.push(DrawOp::ShapeFill { shape, color: a });
+        }
+        ops.push(DrawOp::ShapeFill {
+            shape: square(60.0),
+            color: b,
+        });
+        let scene = Scene {
+            width: 80,
+            height: 20,
+            ops,
+            palette: palette::Palette {
+                background: None,
+                primary: a,
+                secondary: b,
+                tertiary: b,
+                text_color: a,
+            },
+        };
+        let svg = render_svg(&scene, &RenderOptions::default()).expect("svg");
+        assert_eq!(svg.matches("<mask ").count(), 1);
+        assert_eq!(svg.matches("<g mask=").count(), 1);
+        let (mask, body) = svg.split_once("</defs>").expect("defs");
+        assert_eq!(mask.matches("stroke=\"#000000\"").count(), 8);
+        // The mask starts white; ops after the last knockout stay unmasked.
+        assert_eq!(mask.matches("fill=\"#FFFFFF\"").count(), 1 + 7);
+        assert_eq!(body.matches("fill=\"#010203\"").count(), 8);
+        let (masked, unmasked) = body.split_once("</g>").expect("masked group");
+        assert_eq!(masked.matches("fill=\"#010203\"").count(), 7);
+        assert!(unmasked.contains("fill=\"#040506\""));
+    }
 }
@@ -11,7 +11,7 @@ fn test_monogram_badge_golden_svg() {
         size_px: 256,
         padding_frac: 0.12,
         variant: Some(42), // Fixed seed for determinism
-        transparent_background: false,
+        ..Default::default()
     };
 
     let svg =
@@ -19,18 +19,13 @@ fn test_monogram_badge_golden_svg() {
 
     let golden_path = format!("{}/monogram_badge_256.svg", GOLDEN_DIR);
 
-    if !std::path::Path::new(&golden_path).exists() {
-        // Generate golden file if it doesn't exist
-        fs::create_dir_all(GOLDEN_DIR).ok();
-        fs::write(&golden_path, &svg).expect("write golden file");
-        println!("Generated golden file: {}", golden_path);
-    } else {
-        let golden = fs::read_to_string(&golden_path).expect("read golden file");
-        assert_eq!(
-            svg, golden,
-            "SVG output differs from golden file. If this is intentional, delete the golden file and rerun tests."
-        );
-    }
+    // Committed, so a missing file is an error rather than a fresh baseline.
+    let golden = fs::read_to_string(&golden_path)
+        .unwrap_or_else(|e| panic!("read golden file {golden_path}: {e}"));
+    assert_eq!(
+        svg, golden,
+        "SVG output differs from golden file. If this is intentional, regenerate {golden_path}."
+    );
 }
 
 #[test]
@@ -39,7 +34,7 @@ fn test_geometric_pattern_golden_svg() {
         size_px: 256,
         padding_frac: 0.12,
         variant: Some(99), // Fixed seed
-        transparent_background: false,
+        ..Default::default()
     };
 
     let svg =
@@ -47,15 +42,38 @@ fn test_geometric_pattern_golden_svg() {
 
     let golden_path = format!("{}/geometric_pattern_256.svg", GOLDEN_DIR);
 
-    if !std::path::Path::new(&golden_path).exists() {
-        fs::create_dir_all(GOLDEN_DIR).ok();
-        fs::write(&golden_path, &svg).expect("write golden file");
-        println!("Generated golden file: {}", golden_path);
-    } else {
-        let golden = fs::read_to_string(&golden_path).expect("read golden file");
-        assert_eq!(
-            svg, golden,
-            "SVG output differs from golden file. If this is intentional, delete the golden file and rerun tests."
-        );
-    }
+    // Committed, so a missing file is an error rather than a fresh baseline.
+    let golden = fs::read_to_string(&golden_path)
+        .unwrap_or_else(|e| panic!("read golden file {golden_path}: {e}"));
+    assert_eq!(
+        svg, golden,
+        "SVG output differs from golden file. If this is intentional, regenerate {golden_path}."
+    );
+}
+
+#[test]
+fn test_truchet_golden_svg() {
+    let opts = RenderOptions {
+        size_px: 256,
+        padding_frac: 0.12,
+        variant: Some(7), // Fixed seed
+        params: [
+            ("grid_size".to_string(), 8.0),
+            ("tile_set".to_string(), logen::TileSet::Arcs.param_value()),
+        ]
+        .into(),
+        ..Default::default()
+    };
+
+    let svg = LoGen::generate_svg("Maze Works", Preset::Truchet, &opts).expect("svg generation");
+
+    let golden_path = format!("{}/truchet_256.svg", GOLDEN_DIR);
+
+    // Committed, so a missing file is an error rather than a fresh baseline.
+    let golden = fs::read_to_string(&golden_path)
+        .unwrap_or_else(|e| panic!("read golden file {golden_path}: {e}"));
+    assert_eq!(
+        svg, golden,
+        "SVG output differs from golden file. If this is intentional, regenerate {golden_path}."
+    );
 }
@@ -1,4 +1,12 @@
-use logen::{LoGen, LoGenError, Preset, RenderOptions};
+use logen::{LoGen, LoGenError, Preset, RenderOptions, TileSet};
+
+fn with_param(name: &str, value: f64) -> RenderOptions {
+    RenderOptions {
+        size_px: 128,
+        params: [(name.to_string(), value)].into_iter().collect(),
+        ..Default::default()
+    }
+}
 
 #[test]
 fn test_empty_input() {
@@ -54,6 +62,43 @@ fn test_size_too_large() {
     assert!(matches!(result, Err(LoGenError::InvalidOptions(_))));
 }
 
+#[test]
+fn test_canvas_dimensions_out_of_range() {
+    for px in [32, 10000] {
+        for opts in [
+            RenderOptions {
+                width_px: Some(px),
+                ..Default::default()
+            },
+            RenderOptions {
+                height_px: Some(px),
+                ..Default::default()
+            },
+        ] {
+            let result = LoGen::generate_svg("Test", Preset::MonogramBadge, &opts);
+            assert!(matches!(result, Err(LoGenError::InvalidOptions(_))));
+        }
+    }
+}
+
+#[test]
+fn test_grid_size_out_of_range() {
+    for grid_size in [1, 65] {
+        let opts = with_param("grid_size", grid_size as f64);
+        let result = LoGen::generate_svg("Test", Preset::Identicon, &opts);
+        assert!(matches!(result, Err(LoGenError::InvalidOptions(_))));
+    }
+}
+
+#[test]
+fn test_density_out_of_range() {
+    for density in [-0.1, 1.5] {
+        let opts = with_param("density", density);
+        let result = LoGen::generate_svg("Test", Preset::GlyphGridPattern, &opts);
+        assert!(matches!(result, Err(LoGenError::InvalidOptions(_))));
+    }
+}
+
 #[test]
 fn test_valid_edge_case_padding() {
     let opts = RenderOptions {
@@ -64,6 +109,23 @@ fn test_valid_edge_case_padding() {
     assert!(result.is_ok());
 }
 
+#[test]
+fn test_full_padding_builds_every_preset() {
+    // Padding of one half leaves no room for the mark; every preset must
+    // still build rather than panic on the empty square.
+    let opts = RenderOptions {
+        size_px: 128,
+        padding_frac: 0.5,
+        ..Default::default()
+    };
+    for preset in Preset::all() {
+        for input in ["Test", "Acme Widgets", "Half Tone Studio"] {
+            let result = LoGen::generate_svg(input, preset, &opts);
+            assert!(result.is_ok(), "{} {input}: {result:?}", preset.id());
+        }
+    }
+}
+
 #[test]
 fn test_valid_edge_case_size_min() {
     let opts = RenderOptions {
@@ -83,3 +145,85 @@ fn test_valid_edge_case_size_max() {
     let result = LoGen::generate_svg("Test", Preset::MonogramBadge, &opts);
     assert!(result.is_ok());
 }
+
+#[test]
+fn test_param_override_changes_output() {
+    let svg =
+        |opts: &RenderOptions| LoGen::generate_svg("Test", Preset::MonogramBadge, opts).unwrap();
+    let plain = RenderOptions {
+        size_px: 128,
+        ..Default::default()
+    };
+    // Overriding with the default changes nothing.
+    assert_eq!(svg(&with_param("circle_probability", 0.35)), svg(&plain));
+    let circle = svg(&with_param("circle_probability", 1.0));
+    let square = svg(&with_param("circle_probability", 0.0));
+    assert!(circle.contains("<circle") && !square.contains("<circle"));
+}
+
+#[test]
+fn test_cli_flags_have_params() {
+    // --grid-size, --density, --tile-set, --dot-pitch and --dot-angle write
+    // these parameters.
+    let has = |preset: Preset, name: &str| preset.params().iter().any(|p| p.name == name);
+    for preset in [Preset::Identicon, Preset::Truchet, Preset::CircuitTraces] {
+        assert!(has(preset, "grid_size"), "{}", preset.id());
+    }
+    assert!(has(Preset::GlyphGridPattern, "density"));
+    assert!(has(Preset::Truchet, "tile_set"));
+    assert!(has(Preset::Halftone, "dot_pitch") && has(Preset::Halftone, "dot_angle"));
+
+    let svg = |preset: Preset, opts: &RenderOptions| LoGen::generate_svg("Test", preset, opts);
+    let arcs = with_param("tile_set", TileSet::Arcs.param_value());
+    let diagonals = with_param("tile_set", TileSet::Diagonals.param_value());
+    assert_ne!(
+        svg(Preset::Truchet, &arcs).unwrap(),
+        svg(Preset::Truchet, &diagonals).unwrap()
+    );
+    assert!(matches!(
+        svg(Preset::Halftone, &with_param("dot_pitch", 0.5)),
+        Err(LoGenError::InvalidOptions(_))
+    ));
+}
+
+#[test]
+fn test_invalid_params() {
+    for opts in [
+        with_param("no_such_param", 1.0),
+        with_param("circle_probability", 1.5),
+        with_param("min_font_size", 0.8),
+    ] {
+        let result = LoGen::generate_svg("Test", Preset::MonogramBadge, &opts);
+        assert!(matches!(result, Err(LoGenError::InvalidOptions(_))));
+    }
+    // Parameters belong to their preset.
+    let result = LoGen::generate_svg("Test", Preset::Identicon, &with_param("min_font_size", 0.5));
+    assert!(matches!(result, Err(LoGenError::InvalidOptions(_))));
+    let result = LoGen::generate_svg(
+        "Test",
+        Preset::GeometricPattern,
+        &with_param("min_shapes", 2.5),
+    );
+    assert!(matches!(result, Err(LoGenError::InvalidOptions(_))));
+}
+
+#[test]
+fn test_every_param_builds_at_its_extremes() {
+    for preset in Preset::all() {
+        for param in preset.params() {
+            for value in [param.min, param.max] {
+                let result =
+                    LoGen::generate_svg("Acme Widgets", preset, &with_param(param.name, value));
+                // Pushing a min_* past its max_* (or the reverse) is rejected;
+                // everything else must render.
+                match result {
+                    Ok(_) => {}
+                    Err(LoGenError::InvalidOptions(msg)) => {
+                        assert!(msg.contains("must not exceed"), "{}: {msg}", preset.id())
+                    }
+                    Err(e) => panic!("{} {}={value}: {e}", preset.id(), param.name),
+                }
+            }
+        }
+    }
+}
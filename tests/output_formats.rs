@@ -127,3 +127,33 @@ fn test_png_different_sizes_different_output() {
     let png_large = LoGen::generate_png("Test", Preset::MonogramBadge, &opts_large).unwrap();
     assert_ne!(png_small, png_large);
 }
+
+#[test]
+fn test_non_square_canvas_all_presets() {
+    let opts = RenderOptions {
+        width_px: Some(1200),
+        height_px: Some(630),
+        ..Default::default()
+    };
+    for preset in Preset::all() {
+        let svg = LoGen::generate_svg("Acme Power", preset, &opts).unwrap();
+        assert!(
+            svg.contains("viewBox=\"0 0 1200 630\""),
+            "{} ignored the canvas size",
+            preset.id()
+        );
+    }
+}
+
+#[test]
+fn test_png_non_square_dimensions() {
+    let opts = RenderOptions {
+        width_px: Some(600),
+        height_px: Some(200),
+        ..Default::default()
+    };
+    let png = LoGen::generate_png("Test", Preset::Identicon, &opts).unwrap();
+    // IHDR width and height, big-endian, right after the chunk header.
+    assert_eq!(u32::from_be_bytes(png[16..20].try_into().unwrap()), 600);
+    assert_eq!(u32::from_be_bytes(png[20..24].try_into().unwrap()), 200);
+}